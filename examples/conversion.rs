@@ -1,3 +1,5 @@
+// The conversion builds its data step by step, as the rest of the example does.
+#![allow(clippy::vec_init_then_push, clippy::explicit_counter_loop)]
 
 use qrate::{ Header, Question, QBank, Choices };
 use qrate::{ Student, SBank };
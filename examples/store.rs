@@ -1,22 +1,22 @@
-use qrate::{ Generator, QBank, SBank, SQLiteDB };
+use qrate::{ Error, Generator, QBank, SBank, SQLiteDB };
 
-fn main() -> Result<(), String>
+fn main() -> Result<(), Error>
 {
-    let sbank = load_students().ok_or("No Students DB!".to_string())?;
-    let qbank = load_questions().ok_or("No Questions DB!".to_string())?;
-    let generator = Generator::new(&qbank, 1, 51, 10, &sbank).ok_or("Index Error!")?;
+    let sbank = load_students()?;
+    let qbank = load_questions()?;
+    let generator = Generator::new(&qbank, 1, 51, 10, &sbank)?;
     generator.save_shuffled_exams("./IS.path".to_string(), "txt")?;
     generator.save_shuffled_exams("./IS.path".to_string(), "docx")?;
     generator.save_shuffled_exams("./IS.path".to_string(), "pdf")
 }
 
-fn load_students() -> Option<SBank>
+fn load_students() -> Result<SBank, Error>
 {
     use qrate::SBDB;
     SQLiteDB::open("./Students".to_string())?.read_sbank()
 }
 
-fn load_questions() -> Option<QBank>
+fn load_questions() -> Result<QBank, Error>
 {
     use qrate::QBDB;
     SQLiteDB::open("./Information_Security".to_string())?.read_qbank()
@@ -0,0 +1,829 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::collections::HashSet;
+
+use crate::{ GenerationError, QBank, Question, ShuffledQuestion };
+
+/// A type alias for a vector of `Section`s.
+pub type Sections = Vec<Section>;
+
+/// Represents a section of an exam blueprint: which questions are eligible
+/// and how many of them every student gets.
+///
+/// A question is eligible if its ID is in the range from `start` to `end`
+/// (both inclusive), its group is one of `groups` and its category is one of
+/// `categories`. Empty `groups` or `categories` mean that any group or any
+/// category is eligible.
+#[derive(Debug, Clone)]
+pub struct Section
+{
+    start: u16,
+    end: u16,
+    groups: Vec<u16>,
+    categories: Vec<u8>,
+    count: usize,
+}
+
+impl Section
+{
+    // pub fn new_with_range(start: u16, end: u16, count: usize) -> Self
+    /// Creates a new `Section` taking `count` questions whose IDs are from `start` to `end`.
+    ///
+    /// # Arguments
+    /// * `start` - The smallest question ID of the section (inclusive).
+    /// * `end` - The largest question ID of the section (inclusive).
+    /// * `count` - The number of questions to select from the section.
+    ///
+    /// # Output
+    /// `Self` - A new `Section` instance which accepts any group and any category.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// let section = Section::new_with_range(1, 30, 10);
+    /// assert_eq!(section.get_start(), 1);
+    /// assert_eq!(section.get_end(), 30);
+    /// assert_eq!(section.get_count(), 10);
+    /// assert!(section.get_groups().is_empty());
+    /// ```
+    #[inline]
+    pub fn new_with_range(start: u16, end: u16, count: usize) -> Self
+    {
+        Self { start, end, groups: Vec::new(), categories: Vec::new(), count }
+    }
+
+    // pub fn new_with_groups(groups: Vec<u16>, count: usize) -> Self
+    /// Creates a new `Section` taking `count` questions of the given groups.
+    ///
+    /// # Arguments
+    /// * `groups` - The groups whose questions are eligible.
+    /// * `count` - The number of questions to select from the section.
+    ///
+    /// # Output
+    /// `Self` - A new `Section` instance which accepts any ID and any category.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// let section = Section::new_with_groups(vec![4, 7, 9], 2);
+    /// assert_eq!(section.get_groups(), &vec![4, 7, 9]);
+    /// assert_eq!(section.get_end(), u16::MAX);
+    /// ```
+    #[inline]
+    pub fn new_with_groups(groups: Vec<u16>, count: usize) -> Self
+    {
+        Self { start: 1, end: u16::MAX, groups, categories: Vec::new(), count }
+    }
+
+    // pub fn get_start(&self) -> u16
+    /// Gets the smallest question ID of the section.
+    ///
+    /// # Output
+    /// `u16` - The smallest question ID (inclusive).
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// assert_eq!(Section::new_with_range(11, 20, 3).get_start(), 11);
+    /// ```
+    #[inline]
+    pub fn get_start(&self) -> u16
+    {
+        self.start
+    }
+
+    // pub fn get_end(&self) -> u16
+    /// Gets the largest question ID of the section.
+    ///
+    /// # Output
+    /// `u16` - The largest question ID (inclusive).
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// assert_eq!(Section::new_with_range(11, 20, 3).get_end(), 20);
+    /// ```
+    #[inline]
+    pub fn get_end(&self) -> u16
+    {
+        self.end
+    }
+
+    // pub fn set_range(&mut self, start: u16, end: u16)
+    /// Sets the range of question IDs of the section.
+    ///
+    /// # Arguments
+    /// * `start` - The smallest question ID (inclusive).
+    /// * `end` - The largest question ID (inclusive).
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// let mut section = Section::new_with_groups(vec![1, 2], 1);
+    /// section.set_range(5, 8);
+    /// assert_eq!((section.get_start(), section.get_end()), (5, 8));
+    /// ```
+    #[inline]
+    pub fn set_range(&mut self, start: u16, end: u16)
+    {
+        self.start = start;
+        self.end = end;
+    }
+
+    // pub fn get_groups(&self) -> &Vec<u16>
+    /// Gets the groups whose questions are eligible. Empty means any group.
+    ///
+    /// # Output
+    /// `&Vec<u16>` - A reference to the groups.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// assert!(Section::new_with_range(1, 10, 3).get_groups().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_groups(&self) -> &Vec<u16>
+    {
+        &self.groups
+    }
+
+    // pub fn set_groups(&mut self, groups: Vec<u16>)
+    /// Sets the groups whose questions are eligible. Empty means any group.
+    ///
+    /// # Arguments
+    /// * `groups` - The new groups.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// let mut section = Section::new_with_range(1, 10, 3);
+    /// section.set_groups(vec![2, 3]);
+    /// assert_eq!(section.get_groups(), &vec![2, 3]);
+    /// ```
+    #[inline]
+    pub fn set_groups(&mut self, groups: Vec<u16>)
+    {
+        self.groups = groups;
+    }
+
+    // pub fn get_categories(&self) -> &Vec<u8>
+    /// Gets the categories whose questions are eligible. Empty means any category.
+    ///
+    /// # Output
+    /// `&Vec<u8>` - A reference to the categories.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// assert!(Section::new_with_range(1, 10, 3).get_categories().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_categories(&self) -> &Vec<u8>
+    {
+        &self.categories
+    }
+
+    // pub fn set_categories(&mut self, categories: Vec<u8>)
+    /// Sets the categories whose questions are eligible. Empty means any category.
+    ///
+    /// # Arguments
+    /// * `categories` - The new categories.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// let mut section = Section::new_with_range(1, 10, 3);
+    /// section.set_categories(vec![1]);
+    /// assert_eq!(section.get_categories(), &vec![1]);
+    /// ```
+    #[inline]
+    pub fn set_categories(&mut self, categories: Vec<u8>)
+    {
+        self.categories = categories;
+    }
+
+    // pub fn get_count(&self) -> usize
+    /// Gets the number of questions to select from the section.
+    ///
+    /// # Output
+    /// `usize` - The number of questions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// assert_eq!(Section::new_with_range(1, 10, 3).get_count(), 3);
+    /// ```
+    #[inline]
+    pub fn get_count(&self) -> usize
+    {
+        self.count
+    }
+
+    // pub fn set_count(&mut self, count: usize)
+    /// Sets the number of questions to select from the section.
+    ///
+    /// # Arguments
+    /// * `count` - The new number of questions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Section;
+    /// let mut section = Section::new_with_range(1, 10, 3);
+    /// section.set_count(5);
+    /// assert_eq!(section.get_count(), 5);
+    /// ```
+    #[inline]
+    pub fn set_count(&mut self, count: usize)
+    {
+        self.count = count;
+    }
+
+    // pub fn contains(&self, question: &Question) -> bool
+    /// Checks whether a question is eligible for the section.
+    ///
+    /// # Arguments
+    /// * `question` - A reference to the `Question` to check.
+    ///
+    /// # Output
+    /// `bool` - `true` if the ID, the group and the category of `question` all fit the section.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Question, Section };
+    /// let mut section = Section::new_with_range(1, 10, 3);
+    /// section.set_categories(vec![2]);
+    /// assert!(section.contains(&Question::new(5, 5, 2, "Q5".to_string(), vec![])));
+    /// assert!(!section.contains(&Question::new(5, 5, 1, "Q5".to_string(), vec![])));
+    /// assert!(!section.contains(&Question::new(11, 11, 2, "Q11".to_string(), vec![])));
+    /// ```
+    pub fn contains(&self, question: &Question) -> bool
+    {
+        question.get_id() >= self.start && question.get_id() <= self.end
+            && (self.groups.is_empty() || self.groups.contains(&question.get_group()))
+            && (self.categories.is_empty() || self.categories.contains(&question.get_category()))
+    }
+}
+
+
+
+/// Represents how the questions of a paper are put in order.
+///
+/// # Examples
+/// ```
+/// use qrate::{ Blueprint, QuestionOrder };
+/// let mut blueprint = Blueprint::new_with_range(1, 51, 25);
+/// assert_eq!(blueprint.get_order(), QuestionOrder::Shuffled);
+/// blueprint.set_order(QuestionOrder::ById);
+/// assert_eq!(blueprint.get_order(), QuestionOrder::ById);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuestionOrder
+{
+    /// All the questions are shuffled together.
+    #[default]
+    Shuffled,
+
+    /// The questions are put in the order of their categories, e.g. all the
+    /// questions of Type A before those of Type B, and shuffled within each category.
+    ShuffledWithinCategory,
+
+    /// The questions are put in the order of the sections of the blueprint and
+    /// shuffled within each section. A question which falls in several sections
+    /// belongs to the first of them, and anchor questions in none of them come last.
+    ShuffledWithinSection,
+
+    /// The questions are put in the order of their IDs, e.g. the order in which
+    /// the course was taught, and are not shuffled.
+    ById,
+}
+
+/// Represents a declarative exam blueprint: the sections that every student's
+/// question set is assembled from.
+///
+/// Each selected question belongs to a unique group across the whole exam,
+/// not only within its section. Besides the questions sampled from the sections,
+/// every student gets the anchor questions, if any.
+#[derive(Debug, Clone)]
+pub struct Blueprint
+{
+    sections: Sections,
+    anchors: Vec<u16>,              // The IDs of the questions that every student gets.
+    max_overlap: Option<usize>,     // The largest number of questions that any two students may share.
+    answer_balanced: bool,          // Whether the correct answers are spread evenly over the positions of each paper.
+    order: QuestionOrder,           // How the questions of each paper are put in order.
+}
+
+impl Blueprint
+{
+    // pub fn new_empty() -> Self
+    /// Creates a new `Blueprint` without any section.
+    ///
+    /// # Output
+    /// `Self` - A new, empty `Blueprint` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// let blueprint = Blueprint::new_empty();
+    /// assert!(blueprint.get_sections().is_empty());
+    /// assert_eq!(blueprint.get_total(), 0);
+    /// ```
+    #[inline]
+    pub fn new_empty() -> Self
+    {
+        Self { sections: Sections::new(), anchors: Vec::new(), max_overlap: None, answer_balanced: false, order: QuestionOrder::Shuffled }
+    }
+
+    // pub fn new(sections: Sections) -> Self
+    /// Creates a new `Blueprint` with the given sections.
+    ///
+    /// # Arguments
+    /// * `sections` - The sections of the blueprint.
+    ///
+    /// # Output
+    /// `Self` - A new `Blueprint` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Section };
+    /// // A midterm takes 10 questions from chapters 1 to 3 and 5 from chapter 4.
+    /// let blueprint = Blueprint::new(vec![Section::new_with_range(1, 60, 10),
+    ///                                     Section::new_with_range(61, 80, 5)]);
+    /// assert_eq!(blueprint.get_total(), 15);
+    /// ```
+    #[inline]
+    pub fn new(sections: Sections) -> Self
+    {
+        Self { sections, anchors: Vec::new(), max_overlap: None, answer_balanced: false, order: QuestionOrder::Shuffled }
+    }
+
+    // pub fn new_with_range(start: u16, end: u16, selected: usize) -> Self
+    /// Creates a new `Blueprint` with a single section which takes `selected`
+    /// questions whose IDs are from `start` to `end`.
+    ///
+    /// # Arguments
+    /// * `start` - The smallest question ID to consider (inclusive).
+    /// * `end` - The largest question ID to consider (inclusive).
+    /// * `selected` - The number of questions to select.
+    ///
+    /// # Output
+    /// `Self` - A new `Blueprint` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// let blueprint = Blueprint::new_with_range(1, 51, 25);
+    /// assert_eq!(blueprint.get_sections().len(), 1);
+    /// assert_eq!(blueprint.get_total(), 25);
+    /// ```
+    #[inline]
+    pub fn new_with_range(start: u16, end: u16, selected: usize) -> Self
+    {
+        Self::new(vec![Section::new_with_range(start, end, selected)])
+    }
+
+    // pub fn new_with_quotas(start: u16, end: u16, quotas: &[(u8, usize)]) -> Self
+    /// Creates a new `Blueprint` with one section for each category, all of which
+    /// take questions whose IDs are from `start` to `end`.
+    ///
+    /// # Arguments
+    /// * `start` - The smallest question ID to consider (inclusive).
+    /// * `end` - The largest question ID to consider (inclusive).
+    /// * `quotas` - Pairs of a category and the number of questions to select from it.
+    ///
+    /// # Output
+    /// `Self` - A new `Blueprint` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// let blueprint = Blueprint::new_with_quotas(1, 51, &[(1, 20), (2, 5)]);
+    /// assert_eq!(blueprint.get_sections()[1].get_categories(), &vec![2]);
+    /// assert_eq!(blueprint.get_total(), 25);
+    /// ```
+    pub fn new_with_quotas(start: u16, end: u16, quotas: &[(u8, usize)]) -> Self
+    {
+        let mut sections = Sections::new();
+        for &(category, count) in quotas
+        {
+            let mut section = Section::new_with_range(start, end, count);
+            section.set_categories(vec![category]);
+            sections.push(section);
+        }
+        Self::new(sections)
+    }
+
+    // pub fn get_sections(&self) -> &Sections
+    /// Gets the sections of the blueprint.
+    ///
+    /// # Output
+    /// `&Sections` - A reference to the sections.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// assert_eq!(Blueprint::new_with_range(1, 10, 5).get_sections()[0].get_end(), 10);
+    /// ```
+    #[inline]
+    pub fn get_sections(&self) -> &Sections
+    {
+        &self.sections
+    }
+
+    // pub fn set_sections(&mut self, sections: Sections)
+    /// Replaces the sections of the blueprint.
+    ///
+    /// # Arguments
+    /// * `sections` - The new sections.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Section };
+    /// let mut blueprint = Blueprint::new_with_range(1, 10, 5);
+    /// blueprint.set_sections(vec![Section::new_with_range(1, 20, 8)]);
+    /// assert_eq!(blueprint.get_total(), 8);
+    /// ```
+    #[inline]
+    pub fn set_sections(&mut self, sections: Sections)
+    {
+        self.sections = sections;
+    }
+
+    // pub fn push_section(&mut self, section: Section)
+    /// Adds a section to the blueprint.
+    ///
+    /// # Arguments
+    /// * `section` - The `Section` to add.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Section };
+    /// let mut blueprint = Blueprint::new_empty();
+    /// blueprint.push_section(Section::new_with_range(1, 60, 10));
+    /// blueprint.push_section(Section::new_with_range(61, 80, 5));
+    /// assert_eq!(blueprint.get_sections().len(), 2);
+    /// ```
+    #[inline]
+    pub fn push_section(&mut self, section: Section)
+    {
+        self.sections.push(section);
+    }
+
+    // pub fn get_anchors(&self) -> &Vec<u16>
+    /// Gets the IDs of the anchor questions, which every student gets.
+    ///
+    /// # Output
+    /// `&Vec<u16>` - A reference to the IDs of the anchor questions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// assert!(Blueprint::new_with_range(1, 10, 5).get_anchors().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_anchors(&self) -> &Vec<u16>
+    {
+        &self.anchors
+    }
+
+    // pub fn set_anchors(&mut self, anchors: Vec<u16>)
+    /// Sets the IDs of the anchor questions, which every student gets.
+    ///
+    /// Anchor questions are not sampled. They are added to the questions sampled
+    /// from the sections, and no other question of their groups is sampled.
+    /// Their positions and the order of their choices are still shuffled for each student.
+    ///
+    /// # Arguments
+    /// * `anchors` - The IDs of the anchor questions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// let mut blueprint = Blueprint::new_with_range(1, 50, 20);
+    /// blueprint.set_anchors(vec![3, 17]);
+    /// assert_eq!(blueprint.get_anchors(), &vec![3, 17]);
+    /// assert_eq!(blueprint.get_total(), 22);
+    /// ```
+    #[inline]
+    pub fn set_anchors(&mut self, anchors: Vec<u16>)
+    {
+        self.anchors = anchors;
+    }
+
+    // pub fn push_anchor(&mut self, id: u16)
+    /// Adds the ID of an anchor question, which every student gets.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the anchor question.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// let mut blueprint = Blueprint::new_with_range(1, 50, 20);
+    /// blueprint.push_anchor(5);
+    /// assert_eq!(blueprint.get_anchors(), &vec![5]);
+    /// ```
+    #[inline]
+    pub fn push_anchor(&mut self, id: u16)
+    {
+        self.anchors.push(id);
+    }
+
+    // pub fn get_total(&self) -> usize
+    /// Gets the number of questions that every student gets.
+    ///
+    /// # Output
+    /// `usize` - The sum of the counts of all the sections and the number of anchor questions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// assert_eq!(Blueprint::new_with_quotas(1, 51, &[(1, 20), (2, 5)]).get_total(), 25);
+    /// ```
+    #[inline]
+    pub fn get_total(&self) -> usize
+    {
+        self.sections.iter().map(|section| section.get_count()).sum::<usize>() + self.anchors.len()
+    }
+
+    // pub fn get_max_overlap(&self) -> Option<usize>
+    /// Gets the largest number of questions that any two students may have in common.
+    ///
+    /// # Output
+    /// `Option<usize>` - The limit, or `None` if students are sampled independently.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// assert!(Blueprint::new_with_range(1, 10, 5).get_max_overlap().is_none());
+    /// ```
+    #[inline]
+    pub fn get_max_overlap(&self) -> Option<usize>
+    {
+        self.max_overlap
+    }
+
+    // pub fn set_max_overlap(&mut self, max_overlap: Option<usize>)
+    /// Sets the largest number of questions that any two students may have in common.
+    ///
+    /// With a limit, the question sets are assembled one student after another,
+    /// preferring the questions which fewer students have got so far, so that
+    /// the usage of questions is spread across the cohort.
+    ///
+    /// # Arguments
+    /// * `max_overlap` - The limit, or `None` to sample students independently.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// let mut blueprint = Blueprint::new_with_range(1, 100, 20);
+    /// blueprint.set_max_overlap(Some(8));
+    /// assert_eq!(blueprint.get_max_overlap(), Some(8));
+    /// ```
+    #[inline]
+    pub fn set_max_overlap(&mut self, max_overlap: Option<usize>)
+    {
+        self.max_overlap = max_overlap;
+    }
+
+    // pub fn is_answer_balanced(&self) -> bool
+    /// Checks whether the correct answers are spread evenly over the positions of each paper.
+    ///
+    /// # Output
+    /// `bool` - `true` if the choices are shuffled with `ShuffledQuestion::shuffle_balanced()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// assert!(!Blueprint::new_with_range(1, 10, 5).is_answer_balanced());
+    /// ```
+    #[inline]
+    pub fn is_answer_balanced(&self) -> bool
+    {
+        self.answer_balanced
+    }
+
+    // pub fn set_answer_balanced(&mut self, on: bool)
+    /// Sets whether the correct answers are spread evenly over the positions of each paper.
+    ///
+    /// When it is off, the choices of every question are shuffled independently,
+    /// so one student's answer key may end up mostly at the same position by chance.
+    ///
+    /// # Arguments
+    /// * `on` - `true` to balance the positions of the correct answers.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Question, Student, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false), ("D".to_string(), false), ("E".to_string(), false)])); }
+    ///
+    /// let mut blueprint = Blueprint::new_with_range(1, 20, 15);
+    /// blueprint.set_answer_balanced(true);
+    /// let qset = ShuffledQSet::new_with_blueprint(&qbank, &blueprint, &Student::new_empty(), 2026).unwrap();
+    /// assert_eq!(qset.count_answer_positions(&[qbank]).unwrap(), vec![3, 3, 3, 3, 3]);
+    /// ```
+    #[inline]
+    pub fn set_answer_balanced(&mut self, on: bool)
+    {
+        self.answer_balanced = on;
+    }
+
+    // pub fn get_order(&self) -> QuestionOrder
+    /// Gets how the questions of each paper are put in order.
+    ///
+    /// # Output
+    /// `QuestionOrder` - The order of the questions, `QuestionOrder::Shuffled` by default.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QuestionOrder };
+    /// assert_eq!(Blueprint::new_with_range(1, 10, 5).get_order(), QuestionOrder::Shuffled);
+    /// ```
+    #[inline]
+    pub fn get_order(&self) -> QuestionOrder
+    {
+        self.order
+    }
+
+    // pub fn set_order(&mut self, order: QuestionOrder)
+    /// Sets how the questions of each paper are put in order.
+    ///
+    /// Papers of students sitting next to each other are kept apart only by
+    /// moving questions within the places that `order` leaves them, so
+    /// `QuestionOrder::ById` cannot move any question.
+    ///
+    /// # Arguments
+    /// * `order` - The new order of the questions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Question, QuestionOrder, Student, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20    // Odd questions are of Type A, and even ones of Type B.
+    ///     { qbank.push_question(Question::new(id, id, (id % 2 == 0) as u8 + 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let student = Student::new_empty();
+    ///
+    /// let mut blueprint = Blueprint::new_with_range(1, 20, 8);
+    /// blueprint.set_order(QuestionOrder::ById);
+    /// let qset = ShuffledQSet::new_with_blueprint(&qbank, &blueprint, &student, 2026).unwrap();
+    /// let ids: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    ///
+    /// blueprint.set_order(QuestionOrder::ShuffledWithinCategory);
+    /// let qset = ShuffledQSet::new_with_blueprint(&qbank, &blueprint, &student, 2026).unwrap();
+    /// let categories: Vec<u8> = qset.get_shuffled_questions().iter().map(|q| qbank.get_question_by_id(q.get_question()).unwrap().get_category()).collect();
+    /// assert!(categories.windows(2).all(|pair| pair[0] <= pair[1]));
+    ///
+    /// let mut blueprint = Blueprint::new(vec![qrate::Section::new_with_range(11, 20, 3), qrate::Section::new_with_range(1, 10, 3)]);
+    /// blueprint.set_order(QuestionOrder::ShuffledWithinSection);
+    /// let qset = ShuffledQSet::new_with_blueprint(&qbank, &blueprint, &student, 2026).unwrap();
+    /// let ids: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// assert!(ids[..3].iter().all(|&id| id > 10) && ids[3..].iter().all(|&id| id <= 10));
+    /// ```
+    #[inline]
+    pub fn set_order(&mut self, order: QuestionOrder)
+    {
+        self.order = order;
+    }
+
+    // pub(crate) fn get_order_key(&self, qbank: &QBank, shuffled_question: &ShuffledQuestion) -> usize
+    /// Gets the place of `shuffled_question` in the order of the questions.
+    /// Questions of the same key may be shuffled among themselves,
+    /// and questions of a smaller key come first.
+    pub(crate) fn get_order_key(&self, qbank: &QBank, shuffled_question: &ShuffledQuestion) -> usize
+    {
+        let id = shuffled_question.get_question();
+        match self.order
+        {
+            QuestionOrder::Shuffled => 0,
+            QuestionOrder::ShuffledWithinCategory => qbank.get_question_by_id(id).map_or(0, |question| question.get_category() as usize),
+            QuestionOrder::ShuffledWithinSection => qbank.get_question_by_id(id)
+                .and_then(|question| self.sections.iter().position(|section| section.contains(question)))
+                .unwrap_or(self.sections.len()),
+            QuestionOrder::ById => id as usize,
+        }
+    }
+
+    // pub fn get_overlap_lower_bound(&self, qbank: &QBank, number_of_students: usize) -> usize
+    /// Gets a number of common questions which some two students are bound to share,
+    /// however the question sets are assembled.
+    ///
+    /// The bound comes from spreading all the selected questions of all the students
+    /// over the eligible questions of `qbank` as evenly as possible. A limit set by
+    /// `set_max_overlap()` below this bound cannot be met with `qbank`.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `number_of_students` - The number of students.
+    ///
+    /// # Output
+    /// `usize` - The lower bound of the largest overlap between two students.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    ///
+    /// // Two students taking 5 of 10 questions each may share none.
+    /// assert_eq!(Blueprint::new_with_range(1, 10, 5).get_overlap_lower_bound(&qbank, 2), 0);
+    /// // Three students taking 8 of 10 questions each cannot share fewer than 6.
+    /// assert_eq!(Blueprint::new_with_range(1, 10, 8).get_overlap_lower_bound(&qbank, 3), 6);
+    /// // With an anchor question, two students taking 5 of the other 9 share it and one more.
+    /// let mut blueprint = Blueprint::new_with_range(1, 10, 5);
+    /// blueprint.push_anchor(1);
+    /// assert_eq!(blueprint.get_overlap_lower_bound(&qbank, 2), 2);
+    /// ```
+    pub fn get_overlap_lower_bound(&self, qbank: &QBank, number_of_students: usize) -> usize
+    {
+        if number_of_students < 2
+            { return 0; }
+        let anchor_groups: HashSet<u16> = self.anchors.iter()
+            .filter_map(|&id| qbank.get_question_by_id(id))
+            .map(|q| q.get_group())
+            .collect();
+        let eligible = qbank.get_questions().iter()
+            .filter(|q| !anchor_groups.contains(&q.get_group()) && self.sections.iter().any(|section| section.contains(q)))
+            .map(|q| q.get_id())
+            .collect::<HashSet<u16>>()
+            .len();
+        if eligible == 0
+            { return self.anchors.len(); }
+
+        // Every student shares the anchor questions. Each of the other questions
+        // is given to `base` or `base + 1` students.
+        let picks = number_of_students * (self.get_total() - self.anchors.len());
+        let (base, extra) = (picks / eligible, picks % eligible);
+        let pairs_sharing = extra * (base + 1) * base / 2 + (eligible - extra) * base * base.saturating_sub(1) / 2;
+        let pairs_of_students = number_of_students * (number_of_students - 1) / 2;
+        self.anchors.len() + pairs_sharing.div_ceil(pairs_of_students)
+    }
+
+    // pub fn is_valid(&self) -> bool
+    /// Checks whether the blueprint is well-formed, regardless of any question bank.
+    ///
+    /// # Output
+    /// `bool` - `true` if at least one question is to be selected, no section
+    /// has a range which starts from 0 or whose start is greater than its end,
+    /// and no anchor question is listed twice.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Blueprint;
+    /// assert!(Blueprint::new_with_range(1, 10, 5).is_valid());
+    /// assert!(!Blueprint::new_with_range(10, 1, 5).is_valid());
+    /// assert!(!Blueprint::new_with_range(0, 10, 5).is_valid());
+    /// assert!(!Blueprint::new_with_range(1, 10, 0).is_valid());
+    ///
+    /// let mut blueprint = Blueprint::new_empty();
+    /// blueprint.set_anchors(vec![1, 2]);
+    /// assert!(blueprint.is_valid());
+    /// blueprint.push_anchor(1);
+    /// assert!(!blueprint.is_valid());
+    /// ```
+    #[inline]
+    pub fn is_valid(&self) -> bool
+    {
+        self.validate().is_ok()
+    }
+
+    // pub fn validate(&self) -> Result<(), GenerationError>
+    /// Checks whether the blueprint is well-formed as `is_valid()` does,
+    /// telling what is wrong with it.
+    ///
+    /// # Output
+    /// `Result<(), GenerationError>` - `Ok(())` if the blueprint is well-formed,
+    /// or the first problem found otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationError, Section };
+    /// assert_eq!(Blueprint::new_with_range(1, 10, 5).validate(), Ok(()));
+    /// assert_eq!(Blueprint::new_with_range(1, 10, 0).validate(), Err(GenerationError::NothingSelected));
+    ///
+    /// let blueprint = Blueprint::new(vec![Section::new_with_range(1, 10, 5), Section::new_with_range(30, 20, 5)]);
+    /// assert_eq!(blueprint.validate(), Err(GenerationError::InvalidRange { section: 1, start: 30, end: 20 }));
+    /// ```
+    pub fn validate(&self) -> Result<(), GenerationError>
+    {
+        if self.get_total() == 0
+            { return Err(GenerationError::NothingSelected); }
+        if let Some((i, section)) = self.sections.iter().enumerate().find(|(_, section)| section.get_start() == 0 || section.get_start() > section.get_end())
+            { return Err(GenerationError::InvalidRange { section: i, start: section.get_start(), end: section.get_end() }); }
+        let mut anchors = HashSet::new();
+        if let Some(&id) = self.anchors.iter().find(|&&id| !anchors.insert(id))
+            { return Err(GenerationError::DuplicateAnchor { id }); }
+        Ok(())
+    }
+}
@@ -0,0 +1,485 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::collections::{ BTreeMap, HashSet };
+
+use crate::{ Blueprint, GenerationError, QBank, ShuffledQSet };
+
+/// Represents how many distinct question sets a question bank can support
+/// for a blueprint, worked out before any set is generated.
+///
+/// It tells the number and the sizes of the groups that the questions are drawn
+/// from, the number of possible distinct sets, the overlap which a pair of students
+/// is expected to have, the overlap which the most similar pair of the class is
+/// likely to reach and is bound to reach, and whether the blueprint can be met at all,
+/// so that one can see how many new questions are worth writing.
+///
+/// Questions of the same group are never given together, so a set takes one
+/// question from each of as many groups as it has questions. Anchor questions
+/// and the other questions of their groups are not counted among the groups,
+/// since every student is given the anchors.
+///
+/// # Examples
+/// ```
+/// use qrate::{ QBank, Question };
+/// let mut qbank = QBank::new_with_default();
+/// for id in 1..=12    // Four groups of three questions each
+///     { qbank.push_question(Question::new(id, id % 4 + 1, 1, format!("Q{}", id), vec![])); }
+///
+/// let capacity = qbank.analyze(1, 12, 2, 30);
+/// assert_eq!(capacity.get_groups(), 4);
+/// assert_eq!(capacity.get_group_sizes(), &vec![(1, 3), (2, 3), (3, 3), (4, 3)]);
+/// assert_eq!(capacity.get_possible_sets(), 54);   // 6 pairs of groups, 3 * 3 questions for each
+/// assert!((capacity.get_expected_overlap() - 1.0 / 3.0).abs() < 1e-9);
+/// assert!(capacity.is_feasible());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capacity
+{
+    number_of_students: usize,
+    group_sizes: Vec<(u16, usize)>,
+    possible_sets: u128,
+    expected_overlap: f64,
+    likely_max_overlap: usize,
+    overlap_lower_bound: usize,
+    exact: bool,        // Whether the sections draw from different groups.
+    feasibility: Result<(), GenerationError>,
+}
+
+impl Capacity
+{
+    // pub fn new_with_blueprint(qbank: &QBank, blueprint: &Blueprint, number_of_students: usize) -> Self
+    /// Analyses what `qbank` can support for `blueprint` and the given number of students.
+    ///
+    /// The number of possible sets and the overlaps are exact for a blueprint
+    /// whose sections draw from different groups, such as one made by
+    /// `Blueprint::new_with_range()`, or by `Blueprint::new_with_quotas()` for groups
+    /// of one category each. When some sections share groups, they count the sections
+    /// as if they were independent, so the number of possible sets is only an upper bound
+    /// and the overlaps are estimates, as `is_exact()` tells. Whether the blueprint can
+    /// be met is always checked exactly, without drawing any random number.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `blueprint` - A reference to the `Blueprint` that every student's set is assembled from.
+    /// * `number_of_students` - The number of students who take the examination.
+    ///
+    /// # Output
+    /// `Self` - The result of the analysis.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Capacity, QBank, Question, Section };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    ///
+    /// let mut blueprint = Blueprint::new_with_range(1, 10, 3);
+    /// blueprint.push_anchor(10);
+    /// let capacity = Capacity::new_with_blueprint(&qbank, &blueprint, 2);
+    /// assert_eq!(capacity.get_groups(), 9);
+    /// assert_eq!(capacity.get_possible_sets(), 84);   // 3 of the other 9 questions
+    /// assert!((capacity.get_expected_overlap() - 2.0).abs() < 1e-9);  // The anchor and 3 * 3 / 9 more
+    /// assert_eq!(capacity.get_overlap_lower_bound(), 1);
+    /// assert!(capacity.is_exact());
+    ///
+    /// // Both sections draw from groups 1 to 3, so only 3 of the 9 counted sets are distinct.
+    /// let shared = Blueprint::new(vec![Section::new_with_range(1, 3, 1), Section::new_with_range(1, 3, 1)]);
+    /// let capacity = Capacity::new_with_blueprint(&qbank, &shared, 2);
+    /// assert_eq!(capacity.get_possible_sets(), 9);
+    /// assert!(!capacity.is_exact());
+    /// ```
+    pub fn new_with_blueprint(qbank: &QBank, blueprint: &Blueprint, number_of_students: usize) -> Self
+    {
+        let anchor_groups: HashSet<u16> = blueprint.get_anchors().iter()
+            .filter_map(|&id| qbank.get_question_by_id(id))
+            .map(|q| q.get_group())
+            .collect();
+
+        // For each section, the number of its eligible questions in each group
+        let sections = blueprint.get_sections();
+        let mut section_sizes: Vec<BTreeMap<u16, usize>> = vec![BTreeMap::new(); sections.len()];
+        let mut group_sizes: BTreeMap<u16, usize> = BTreeMap::new();
+        for question in qbank.get_questions()
+        {
+            if anchor_groups.contains(&question.get_group())
+                { continue; }
+            let mut eligible = false;
+            for (i, section) in sections.iter().enumerate()
+            {
+                if section.contains(question)
+                {
+                    *section_sizes[i].entry(question.get_group()).or_insert(0) += 1;
+                    eligible = true;
+                }
+            }
+            if eligible
+                { *group_sizes.entry(question.get_group()).or_insert(0) += 1; }
+        }
+
+        // A section puts its groups in a random order and takes the first ones,
+        // and then one question of each of them at random.
+        let mut possible_sets = 1_u128;
+        let mut expected_overlap = blueprint.get_anchors().len() as f64;
+        // distribution[k] is the probability that two students share k questions.
+        let mut distribution = vec![0.0; blueprint.get_anchors().len()];
+        distribution.push(1.0);
+        for (section, sizes) in sections.iter().zip(&section_sizes)
+        {
+            possible_sets = possible_sets.saturating_mul(Self::count_selections(sizes.values().copied(), section.get_count()));
+            if !sizes.is_empty()
+            {
+                let share = section.get_count().min(sizes.len()) as f64 / sizes.len() as f64;
+                expected_overlap += share * share * sizes.values().map(|&size| 1.0 / size as f64).sum::<f64>();
+                let section_distribution = Self::overlap_distribution(sizes.values().copied().collect(), section.get_count());
+                let mut sum = vec![0.0; distribution.len() + section_distribution.len() - 1];
+                for (i, p) in distribution.iter().enumerate()
+                {
+                    for (j, q) in section_distribution.iter().enumerate()
+                        { sum[i + j] += p * q; }
+                }
+                distribution = sum;
+            }
+        }
+        let exact = group_sizes.keys().all(|group| section_sizes.iter().filter(|sizes| sizes.contains_key(group)).count() <= 1);
+        let overlap_lower_bound = blueprint.get_overlap_lower_bound(qbank, number_of_students);
+
+        // Each pair shares at least k questions with the probability `tail`, so pairs * tail pairs are expected to,
+        // and some pair does with about even odds when it reaches one half.
+        let pairs = number_of_students.saturating_sub(1) as f64 * number_of_students as f64 / 2.0;
+        let mut tail = 0.0;
+        let mut likely_max_overlap = 0;
+        for (k, p) in distribution.iter().enumerate().rev()
+        {
+            tail += p;
+            if pairs * tail >= 0.5
+            {
+                likely_max_overlap = k;
+                break;
+            }
+        }
+        let likely_max_overlap = likely_max_overlap.max(overlap_lower_bound);
+
+        // The check matches groups to the wanted questions as the generation does, but without random numbers.
+        let feasibility = ShuffledQSet::check_blueprint(qbank, blueprint);
+        if feasibility.is_err()
+            { possible_sets = 0; }
+        Self
+        {
+            number_of_students,
+            group_sizes: group_sizes.into_iter().collect(),
+            possible_sets,
+            expected_overlap,
+            likely_max_overlap,
+            overlap_lower_bound,
+            exact,
+            feasibility,
+        }
+    }
+
+    // fn overlap_distribution(sizes: Vec<usize>, count: usize) -> Vec<f64>
+    /// Works out the probability that two students share k questions of a section
+    /// which takes `count` of the groups of `sizes` questions each, for every k.
+    ///
+    /// The two students have j groups in common with the hypergeometric probability,
+    /// and the j common groups are any j of the groups alike. Each common group
+    /// gives both of them the same question with the probability 1 / its size.
+    fn overlap_distribution(sizes: Vec<usize>, count: usize) -> Vec<f64>
+    {
+        let m = sizes.len();
+        let c = count.min(m);
+        // common[j][k] is the probability of k same questions in j common groups out of the groups so far,
+        // averaged over the j-subsets so that it stays within the range of f64.
+        let mut common = vec![vec![0.0; c + 1]; c + 1];
+        common[0][0] = 1.0;
+        for (t, &size) in sizes.iter().enumerate()
+        {
+            let p = 1.0 / size as f64;
+            for j in (1..=c.min(t + 1)).rev()
+            {
+                let kept = (t + 1 - j) as f64 / (t + 1) as f64;  // C(t, j) / C(t + 1, j)
+                let added = j as f64 / (t + 1) as f64;          // C(t, j - 1) / C(t + 1, j)
+                for k in (0..=j).rev()
+                {
+                    let same = if k > 0 { common[j - 1][k - 1] * p } else { 0.0 };
+                    common[j][k] = kept * common[j][k] + added * (common[j - 1][k] * (1.0 - p) + same);
+                }
+            }
+        }
+
+        let ln_choose = |n: usize, k: usize| (1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum::<f64>();
+        let mut distribution = vec![0.0; c + 1];
+        for (j, same) in common.iter().enumerate()
+        {
+            if c - j > m - c
+                { continue; }
+            let hypergeometric = (ln_choose(c, j) + ln_choose(m - c, c - j) - ln_choose(m, c)).exp();
+            for (k, probability) in distribution.iter_mut().enumerate()
+                { *probability += hypergeometric * same[k]; }
+        }
+        distribution
+    }
+
+    // fn count_selections(sizes: impl Iterator<Item = usize>, count: usize) -> u128
+    /// Counts the ways of choosing `count` groups and one question of each of them,
+    /// which is the elementary symmetric polynomial of degree `count` in the group sizes.
+    /// The count stops growing at `u128::MAX`.
+    fn count_selections(sizes: impl Iterator<Item = usize>, count: usize) -> u128
+    {
+        // ways[j] is the number of ways of taking j questions from the groups so far.
+        let mut ways = vec![0_u128; count + 1];
+        ways[0] = 1;
+        for size in sizes
+        {
+            for j in (1..=count).rev()
+                { ways[j] = ways[j].saturating_add(ways[j - 1].saturating_mul(size as u128)); }
+        }
+        ways[count]
+    }
+
+    // pub fn get_number_of_students(&self) -> usize
+    /// Gets the number of students that the analysis was made for.
+    ///
+    /// # Output
+    /// `usize` - The number of students.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![]));
+    /// assert_eq!(qbank.analyze(1, 1, 1, 40).get_number_of_students(), 40);
+    /// ```
+    #[inline]
+    pub fn get_number_of_students(&self) -> usize
+    {
+        self.number_of_students
+    }
+
+    // pub fn get_groups(&self) -> usize
+    /// Gets the number of groups that have questions to draw from.
+    ///
+    /// # Output
+    /// `usize` - The number of groups, which is the largest number of questions
+    /// that a set can take if the sections have no other limits.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=51    // Questions 1 to 51 fall into 8 groups.
+    ///     { qbank.push_question(Question::new(id, id % 8 + 1, 1, format!("Q{}", id), vec![])); }
+    /// assert_eq!(qbank.analyze(1, 51, 5, 30).get_groups(), 8);
+    /// assert_eq!(qbank.analyze(1, 4, 2, 30).get_groups(), 4);
+    /// ```
+    #[inline]
+    pub fn get_groups(&self) -> usize
+    {
+        self.group_sizes.len()
+    }
+
+    // pub fn get_group_sizes(&self) -> &Vec<(u16, usize)>
+    /// Gets the number of questions to draw from in each group.
+    ///
+    /// # Output
+    /// `&Vec<(u16, usize)>` - Pairs of a group and its number of questions, in the order of the groups.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for (id, group) in [(1, 1), (2, 1), (3, 2), (4, 3), (5, 3), (6, 3)]
+    ///     { qbank.push_question(Question::new(id, group, 1, format!("Q{}", id), vec![])); }
+    /// assert_eq!(qbank.analyze(1, 6, 2, 30).get_group_sizes(), &vec![(1, 2), (2, 1), (3, 3)]);
+    /// ```
+    #[inline]
+    pub fn get_group_sizes(&self) -> &Vec<(u16, usize)>
+    {
+        &self.group_sizes
+    }
+
+    // pub fn get_possible_sets(&self) -> u128
+    /// Gets the number of distinct question sets, regardless of the order
+    /// of the questions and of their choices.
+    ///
+    /// # Output
+    /// `u128` - The number of possible sets, 0 if the blueprint cannot be met,
+    /// or `u128::MAX` if there are at least as many. If some sections share groups,
+    /// the sets of one section are counted again in the other, so it is only an
+    /// upper bound and `is_exact()` gives `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=12    // Groups 1 to 4 of category 1, groups 5 and 6 of category 2
+    ///     { qbank.push_question(Question::new(id, (id + 1) / 2, if id <= 8 { 1 } else { 2 }, format!("Q{}", id), vec![])); }
+    ///
+    /// // 6 pairs of groups 1 to 4, one group of groups 5 and 6, and 2 questions for each group
+    /// assert_eq!(qbank.analyze_with_quotas(1, 12, &[(1, 2), (2, 1)], 30).get_possible_sets(), 6 * 2 * 2 * 2 * 2);
+    /// assert_eq!(qbank.analyze_with_quotas(1, 12, &[(2, 3)], 30).get_possible_sets(), 0);
+    /// ```
+    #[inline]
+    pub fn get_possible_sets(&self) -> u128
+    {
+        self.possible_sets
+    }
+
+    // pub fn get_expected_overlap(&self) -> f64
+    /// Gets the number of common questions that a pair of students is expected to share
+    /// when their sets are drawn independently, as `Generator::new()` does.
+    ///
+    /// It is the average over the pairs, so it does not depend on the number of students,
+    /// while the most similar pair of a larger class shares more, as
+    /// `get_likely_max_overlap()` tells. A limit set by `Blueprint::set_max_overlap()`
+    /// makes the sets depend on each other.
+    ///
+    /// # Output
+    /// `f64` - The expected overlap of a pair of students, anchors included.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    /// // Each student takes 10 of 20 questions, half of the other student's 10.
+    /// assert!((qbank.analyze(1, 20, 10, 30).get_expected_overlap() - 5.0).abs() < 1e-9);
+    /// ```
+    #[inline]
+    pub fn get_expected_overlap(&self) -> f64
+    {
+        self.expected_overlap
+    }
+
+    // pub fn get_likely_max_overlap(&self) -> usize
+    /// Gets the number of common questions which the most similar pair of
+    /// all the students is likely to share when their sets are drawn independently,
+    /// as `Generator::new()` does.
+    ///
+    /// It is the largest number of questions that some pair shares with about even odds,
+    /// worked out from the probability that a pair shares them and the number of pairs,
+    /// so it grows with the number of students. It is never less than
+    /// `get_overlap_lower_bound()`.
+    ///
+    /// # Output
+    /// `usize` - The likely largest overlap between two students, anchors included,
+    /// or 0 for fewer than two students.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    /// // A pair shares 5 of 10 questions on average, but some pair of many students shares more.
+    /// let few = qbank.analyze(1, 20, 10, 2).get_likely_max_overlap();
+    /// let many = qbank.analyze(1, 20, 10, 200).get_likely_max_overlap();
+    /// assert!(few <= 6);
+    /// assert!(many > few);
+    /// assert!(many < 10);
+    /// ```
+    #[inline]
+    pub fn get_likely_max_overlap(&self) -> usize
+    {
+        self.likely_max_overlap
+    }
+
+    // pub fn get_overlap_lower_bound(&self) -> usize
+    /// Gets the number of common questions which some two of the students are bound
+    /// to share, however the sets are assembled, as `Blueprint::get_overlap_lower_bound()` does.
+    ///
+    /// # Output
+    /// `usize` - The lower bound of the largest overlap between two students.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    /// assert_eq!(qbank.analyze(1, 10, 5, 2).get_overlap_lower_bound(), 0);
+    /// assert_eq!(qbank.analyze(1, 10, 8, 3).get_overlap_lower_bound(), 6);
+    /// ```
+    #[inline]
+    pub fn get_overlap_lower_bound(&self) -> usize
+    {
+        self.overlap_lower_bound
+    }
+
+    // pub fn is_exact(&self) -> bool
+    /// Checks whether the number of possible sets and the overlaps are exact,
+    /// which they are unless some sections of the blueprint share groups.
+    ///
+    /// # Output
+    /// `bool` - `true` if they are exact, or `false` if the number of possible sets
+    /// is an upper bound and the overlaps are estimates.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    /// assert!(qbank.analyze(1, 6, 3, 30).is_exact());
+    /// ```
+    #[inline]
+    pub fn is_exact(&self) -> bool
+    {
+        self.exact
+    }
+
+    // pub fn is_feasible(&self) -> bool
+    /// Checks whether a question set can be assembled at all,
+    /// meeting the selection count or every quota.
+    ///
+    /// # Output
+    /// `bool` - `true` if it can, or `false` if `get_error()` tells why not.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, if id <= 4 { 1 } else { 2 }, format!("Q{}", id), vec![])); }
+    /// assert!(qbank.analyze_with_quotas(1, 6, &[(1, 4), (2, 2)], 30).is_feasible());
+    /// assert!(!qbank.analyze_with_quotas(1, 6, &[(1, 4), (2, 3)], 30).is_feasible());
+    /// ```
+    #[inline]
+    pub fn is_feasible(&self) -> bool
+    {
+        self.feasibility.is_ok()
+    }
+
+    // pub fn get_error(&self) -> Option<&GenerationError>
+    /// Gets the reason why no question set can be assembled.
+    ///
+    /// # Output
+    /// `Option<&GenerationError>` - The error that the generation would fail with,
+    /// or `None` if it is feasible.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ GenerationError, QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=12    // Groups 1 to 4 of category 1, groups 5 and 6 of category 2
+    ///     { qbank.push_question(Question::new(id, (id + 1) / 2, if id <= 8 { 1 } else { 2 }, format!("Q{}", id), vec![])); }
+    ///
+    /// let capacity = qbank.analyze_with_quotas(1, 12, &[(1, 2), (2, 3)], 30);
+    /// // One more group of category 2 is needed.
+    /// assert_eq!(capacity.get_error(), Some(&GenerationError::TooFewGroups { section: 1, start: 1, end: 12, groups: 2, requested: 3 }));
+    /// assert!(qbank.analyze(1, 12, 6, 30).get_error().is_none());
+    /// ```
+    #[inline]
+    pub fn get_error(&self) -> Option<&GenerationError>
+    {
+        self.feasibility.as_ref().err()
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::collections::HashSet;
+
+/// Represents a rule between two questions of a question bank, given by their IDs,
+/// that every question set has to keep beyond the groups of the questions.
+///
+/// A group only tells that at most one of its questions is selected.
+/// A `Constraint` can also tell that two questions of different groups
+/// must not be selected together, or that a question is selected only
+/// together with another one.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use qrate::Constraint;
+///
+/// // Question 12 reveals the answer of question 30.
+/// let excludes = Constraint::Excludes { question: 12, other: 30 };
+/// assert!(excludes.is_kept_by(&HashSet::from([12, 31])));
+/// assert!(!excludes.is_kept_by(&HashSet::from([30, 12])));
+///
+/// // Question 41 is a follow-up of question 40.
+/// let requires = Constraint::Requires { question: 41, required: 40 };
+/// assert!(requires.is_kept_by(&HashSet::from([40])));
+/// assert!(requires.is_kept_by(&HashSet::from([40, 41])));
+/// assert!(!requires.is_kept_by(&HashSet::from([41])));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Constraint
+{
+    /// `question` and `other` are not selected together.
+    /// The rule is symmetric, so which of the two is `question` does not matter.
+    Excludes { question: u16, other: u16 },
+
+    /// `question` is selected only if `required` is selected too.
+    Requires { question: u16, required: u16 },
+}
+
+impl Constraint
+{
+    // pub fn new_with_kind(kind: &str, question: u16, other: u16) -> Option<Self>
+    /// Creates a new `Constraint` from the name of its kind, as it is stored.
+    ///
+    /// # Arguments
+    /// * `kind` - `"Excludes"` or `"Requires"`, in any case.
+    /// * `question` - The ID of the question that the rule is about.
+    /// * `other` - The ID of the question that `question` excludes or requires.
+    ///
+    /// # Output
+    /// `Option<Self>` - The `Constraint`, or `None` if `kind` is not known.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Constraint;
+    /// assert_eq!(Constraint::new_with_kind("requires", 41, 40), Some(Constraint::Requires { question: 41, required: 40 }));
+    /// assert_eq!(Constraint::new_with_kind("Excludes", 12, 30), Some(Constraint::Excludes { question: 12, other: 30 }));
+    /// assert!(Constraint::new_with_kind("Follows", 41, 40).is_none());
+    /// ```
+    pub fn new_with_kind(kind: &str, question: u16, other: u16) -> Option<Self>
+    {
+        if kind.trim().eq_ignore_ascii_case("Excludes")
+            { Some(Self::Excludes { question, other }) }
+        else if kind.trim().eq_ignore_ascii_case("Requires")
+            { Some(Self::Requires { question, required: other }) }
+        else
+            { None }
+    }
+
+    // pub fn get_kind(&self) -> &'static str
+    /// Gets the name of the kind of the rule, as it is stored.
+    ///
+    /// # Output
+    /// `&'static str` - `"Excludes"` or `"Requires"`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Constraint;
+    /// assert_eq!(Constraint::Requires { question: 41, required: 40 }.get_kind(), "Requires");
+    /// ```
+    pub fn get_kind(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Excludes { .. } => "Excludes",
+            Self::Requires { .. } => "Requires",
+        }
+    }
+
+    // pub fn get_question(&self) -> u16
+    /// Gets the ID of the question that the rule is about.
+    ///
+    /// # Output
+    /// `u16` - The ID of the question.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Constraint;
+    /// assert_eq!(Constraint::Requires { question: 41, required: 40 }.get_question(), 41);
+    /// ```
+    pub fn get_question(&self) -> u16
+    {
+        match *self
+        {
+            Self::Excludes { question, .. } | Self::Requires { question, .. } => question,
+        }
+    }
+
+    // pub fn get_other(&self) -> u16
+    /// Gets the ID of the question that the question of the rule excludes or requires.
+    ///
+    /// # Output
+    /// `u16` - The ID of the other question.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Constraint;
+    /// assert_eq!(Constraint::Requires { question: 41, required: 40 }.get_other(), 40);
+    /// ```
+    pub fn get_other(&self) -> u16
+    {
+        match *self
+        {
+            Self::Excludes { other, .. } | Self::Requires { required: other, .. } => other,
+        }
+    }
+
+    // pub fn excludes(&self, id: u16, selected: &HashSet<u16>) -> bool
+    /// Tells whether the rule forbids adding the question `id` to the questions already `selected`.
+    /// Only `Excludes` forbids it, since the question that `Requires` asks for may be selected later.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the question to add.
+    /// * `selected` - The IDs of the questions already selected.
+    ///
+    /// # Output
+    /// `bool` - `true` if the question must not be added.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use qrate::Constraint;
+    /// let excludes = Constraint::Excludes { question: 12, other: 30 };
+    /// assert!(excludes.excludes(30, &HashSet::from([12])));
+    /// assert!(excludes.excludes(12, &HashSet::from([30])));
+    /// assert!(!excludes.excludes(31, &HashSet::from([12])));
+    /// ```
+    pub fn excludes(&self, id: u16, selected: &HashSet<u16>) -> bool
+    {
+        match *self
+        {
+            Self::Excludes { question, other } =>
+                (question == id && selected.contains(&other)) || (other == id && selected.contains(&question)),
+            Self::Requires { .. } => false,
+        }
+    }
+
+    // pub fn is_kept_by(&self, selected: &HashSet<u16>) -> bool
+    /// Tells whether the questions `selected` keep the rule.
+    ///
+    /// # Arguments
+    /// * `selected` - The IDs of the questions of a question set.
+    ///
+    /// # Output
+    /// `bool` - `true` if the rule is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use qrate::Constraint;
+    /// assert!(!Constraint::Requires { question: 41, required: 40 }.is_kept_by(&HashSet::from([41, 42])));
+    /// ```
+    pub fn is_kept_by(&self, selected: &HashSet<u16>) -> bool
+    {
+        match *self
+        {
+            Self::Excludes { question, other } => !(selected.contains(&question) && selected.contains(&other)),
+            Self::Requires { question, required } => !selected.contains(&question) || selected.contains(&required),
+        }
+    }
+}
+
+/// A type alias for a vector of `Constraint`s.
+pub type Constraints = Vec<Constraint>;
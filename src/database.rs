@@ -8,9 +8,9 @@
 ///////////////////////////////////////////////////////////////////////////////
 
 
-use rusqlite::{ Connection, Error };
+use rusqlite::Connection;
 
-use crate::check_path;
+use crate::{ Error, check_path };
 
 
 /// Represents an SQLite database connection.
@@ -28,7 +28,7 @@ pub struct SQLiteDB
 
 impl SQLiteDB
 {
-    // pub fn open_with_ext(path: String, extention: &str) -> Option<Self>
+    // pub fn open_with_ext(path: String, extention: &str) -> Result<Self, Error>
     /// Opens a new connection to an SQLite database.
     ///
     /// # Arguments
@@ -36,7 +36,8 @@ impl SQLiteDB
     /// * `extention` - The file extension to append if the path does not have one.
     ///
     /// # Output
-    /// An `Option<Self>` which is `Some(SQLiteDB)` on successful connection, or `None` on failure.
+    /// A `Result<Self, Error>` which is `Ok(SQLiteDB)` on successful connection,
+    /// or `Err(Error::Sqlite)` on failure.
     ///
     /// # Examples
     /// ```
@@ -45,22 +46,20 @@ impl SQLiteDB
     /// // Using an in-memory database for the example.
     /// // In a real scenario, you would provide a file path.
     /// let db = SQLiteDB::open(":memory:".to_string(), "db");
-    /// assert!(db.is_some());
+    /// assert!(db.is_ok());
     /// ```
-    pub fn open_with_ext(path: String, extention: &str) -> Option<Self>
+    pub fn open_with_ext(path: String, extention: &str) -> Result<Self, Error>
     {
         let extended_path = check_path(path, extention);
-        if let Ok(con) = Connection::open(&extended_path)
-            { Some(Self { path: extended_path, conn: con }) }
-        else
-            { None }
+        let con = Connection::open(&extended_path)?;
+        Ok(Self { path: extended_path, conn: con })
     }
 
-    // pub fn close(self) -> Result<(), (Connection, Error)>
+    // pub fn close(self) -> Result<(), Error>
     /// Closes the database connection.
     ///
     /// # Output
-    /// `Ok(())` if the connection is closed successfully, `Err(())` otherwise.
+    /// `Ok(())` if the connection is closed successfully, `Err(Error::Sqlite)` otherwise.
     ///
     /// # Examples
     /// ```
@@ -70,13 +69,9 @@ impl SQLiteDB
     /// let result = db.close();
     /// assert!(result.is_ok());
     /// ```
-    pub fn close(self) -> Result<(), (Connection, Error)>
+    pub fn close(self) -> Result<(), Error>
     {
-        match self.conn.close()
-        {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
-        }
+        self.conn.close().map_err(|(_, e)| Error::Sqlite(e))
     }
 
     // pub fn set_path(&mut self, path: String)
@@ -157,4 +152,51 @@ impl SQLiteDB
     {
         &self.conn
     }
+
+    // pub(crate) fn has_column(&self, table: &str, column: &str) -> Result<bool, Error>
+    /// Checks whether `table` has the column `column`.
+    ///
+    /// # Output
+    /// `Result<bool, Error>` - `Ok(true)` if the column exists, `Ok(false)` if it does not
+    /// or the table does not exist, or `Err(Error::Sqlite)` if the schema cannot be read.
+    pub(crate) fn has_column(&self, table: &str, column: &str) -> Result<bool, Error>
+    {
+        let sql = "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2;";
+        Ok(self.conn.query_row(sql, [table, column], |row| row.get(0))?)
+    }
+
+    // pub(crate) fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<(), Error>
+    /// Adds the column `column` with the type and the constraints in `definition`
+    /// to `table` unless the table already has it, so that a table made by
+    /// an earlier version of `qrate` gets the columns that later versions store.
+    /// The names and `definition` are put into the SQL as they are, so they
+    /// must come from `qrate` itself, never from a user.
+    ///
+    /// # Output
+    /// `Result<(), Error>` - `Ok(())` if the column exists afterwards,
+    /// or `Err(Error::Sqlite)` if it cannot be added, e.g. because the database is read-only.
+    pub(crate) fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<(), Error>
+    {
+        if !self.has_column(table, column)?
+            { self.conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {};", table, column, definition), [])?; }
+        Ok(())
+    }
+
+    // pub(crate) fn check_columns(&self, table: &str, expected: &[&str]) -> Result<(), Error>
+    /// Checks that the columns of `table` begin with the columns in `expected`, in that order.
+    ///
+    /// # Output
+    /// `Result<(), Error>` - `Ok(())` if they do, `Err(Error::Schema)` if they do not,
+    /// or `Err(Error::Sqlite)` if the table cannot be queried.
+    pub(crate) fn check_columns(&self, table: &str, expected: &[&str]) -> Result<(), Error>
+    {
+        let stmt = self.conn.prepare(&format!("SELECT * FROM {} LIMIT 0;", table))?;
+        let columns = stmt.column_names();
+        if columns.len() < expected.len() || columns.iter().zip(expected).any(|(column, name)| !column.eq_ignore_ascii_case(name))
+        {
+            let message = format!("the columns are {}, but {} are expected first", columns.join(", "), expected.join(", "));
+            return Err(Error::Schema { table: table.to_string(), message });
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,307 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::fmt;
+
+use crate::Constraint;
+
+/// Represents every error that `qrate` reports, so that callers can tell
+/// what went wrong rather than only print a message.
+///
+/// Errors from the libraries that `qrate` relies on are kept as they are
+/// in the variants which wrap them, and `source()` gives them back.
+///
+/// # Examples
+/// ```
+/// use qrate::{ Error, Excel, QBDB };
+///
+/// // A file which does not exist cannot be read.
+/// let excel = Excel::open("no_such_bank".to_string()).unwrap();
+/// match excel.read_qbank()
+/// {
+///     Err(Error::ExcelRead(_)) => {},
+///     _ => panic!("An Excel error is expected."),
+/// }
+///
+/// // A row of the "Questions" sheet without its question text is reported with its row.
+/// let mut workbook = rust_xlsxwriter::Workbook::new();
+/// workbook.add_worksheet().set_name("Header").unwrap().write_string(0, 0, "Title").unwrap();
+/// let sheet = workbook.add_worksheet().set_name("Questions").unwrap();
+/// for (col, title) in ["ID", "Group", "Category", "Question"].iter().enumerate()
+///     { sheet.write_string(0, col as u16, *title).unwrap(); }
+/// for col in 0..3
+///     { sheet.write_number(1, col, 1.0).unwrap(); }
+/// let excel = Excel::open("test_error".to_string()).unwrap();
+/// workbook.save(excel.get_path()).unwrap();
+///
+/// match excel.read_qbank()
+/// {
+///     Err(Error::ExcelParse { sheet, row, .. }) => assert_eq!((sheet.as_str(), row), ("Questions", 2)),
+///     _ => panic!("A parse error is expected."),
+/// }
+/// std::fs::remove_file(excel.get_path()).unwrap(); // Clean up
+/// ```
+#[derive(Debug)]
+pub enum Error
+{
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+
+    /// An SQLite operation failed.
+    Sqlite(rusqlite::Error),
+
+    /// An Excel workbook could not be opened or read.
+    ExcelRead(calamine::Error),
+
+    /// An Excel workbook could not be written.
+    ExcelWrite(rust_xlsxwriter::XlsxError),
+
+    /// A row of an Excel sheet could not be parsed.
+    /// Rows are numbered from 1 as the spreadsheet shows them.
+    ExcelParse { sheet: String, row: usize, message: String },
+
+    /// A table or a sheet does not have the layout that `qrate` expects,
+    /// such as a missing sheet or unexpected columns.
+    Schema { table: String, message: String },
+
+    /// The data breaks a rule of `qrate`, such as two questions with the same ID.
+    Validation(String),
+
+    /// Question sets could not be generated.
+    Generation(GenerationError),
+
+    /// An examination paper could not be rendered into a document.
+    Render(String),
+}
+
+impl fmt::Display for Error
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Sqlite(e) => write!(f, "SQLite error: {}", e),
+            Self::ExcelRead(e) => write!(f, "Excel read error: {}", e),
+            Self::ExcelWrite(e) => write!(f, "Excel write error: {}", e),
+            Self::ExcelParse { sheet, row, message } => write!(f, "row {} of the sheet {}: {}", row, sheet, message),
+            Self::Schema { table, message } => write!(f, "{}: {}", table, message),
+            Self::Validation(message) => write!(f, "{}", message),
+            Self::Generation(e) => write!(f, "{}", e),
+            Self::Render(message) => write!(f, "rendering error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self
+        {
+            Self::Io(e) => Some(e),
+            Self::Sqlite(e) => Some(e),
+            Self::ExcelRead(e) => Some(e),
+            Self::ExcelWrite(e) => Some(e),
+            Self::Generation(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error
+{
+    fn from(e: std::io::Error) -> Self
+    {
+        Self::Io(e)
+    }
+}
+
+impl From<rusqlite::Error> for Error
+{
+    fn from(e: rusqlite::Error) -> Self
+    {
+        Self::Sqlite(e)
+    }
+}
+
+impl From<calamine::Error> for Error
+{
+    fn from(e: calamine::Error) -> Self
+    {
+        Self::ExcelRead(e)
+    }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for Error
+{
+    fn from(e: rust_xlsxwriter::XlsxError) -> Self
+    {
+        Self::ExcelWrite(e)
+    }
+}
+
+impl From<GenerationError> for Error
+{
+    fn from(e: GenerationError) -> Self
+    {
+        Self::Generation(e)
+    }
+}
+
+/// Represents the reason why question sets could not be generated.
+///
+/// Each variant names the constraint that failed together with the numbers
+/// involved, and its `Display` output is a sentence which tells them, such as
+/// `range 1..=51 has 8 distinct groups, 10 requested`.
+/// Sections are numbered from 0 in the order of `Blueprint::get_sections()`.
+///
+/// # Examples
+/// ```
+/// use qrate::{ GenerationError, QBank, Question, Student, shuffler::ShuffledQSet };
+/// let mut qbank = QBank::new_with_default();
+/// for id in 1..=51    // Questions 1 to 51 fall into 8 groups.
+///     { qbank.push_question(Question::new(id, id % 8 + 1, 1, format!("Q{}", id), vec![])); }
+///
+/// let student = Student::new("Test".to_string(), "123".to_string());
+/// let error = ShuffledQSet::new(&qbank, 1, 51, 10, &student).unwrap_err();
+/// assert_eq!(error, GenerationError::TooFewGroups { section: 0, start: 1, end: 51, groups: 8, requested: 10 });
+/// assert_eq!(error.to_string(), "range 1..=51 has 8 distinct groups, 10 requested");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationError
+{
+    /// No question is to be selected, e.g. `selected` is 0.
+    NothingSelected,
+
+    /// The range of a section starts from 0 or its start is greater than its end.
+    InvalidRange { section: usize, start: u16, end: u16 },
+
+    /// An anchor question is listed more than once.
+    DuplicateAnchor { id: u16 },
+
+    /// Two or more questions of the question bank have the same ID.
+    DuplicateId { id: u16 },
+
+    /// An anchor question is not in the question bank.
+    MissingAnchor { id: u16 },
+
+    /// A section asks for a category of which its range has no question.
+    MissingCategory { section: usize, start: u16, end: u16, category: u8 },
+
+    /// A section asks for more questions than its range has distinct groups.
+    TooFewGroups { section: usize, start: u16, end: u16, groups: usize, requested: usize },
+
+    /// Each section can be filled on its own, but not all of them together
+    /// with questions of distinct groups, since some sections share groups.
+    /// `groups` is the largest number of questions that can be so selected.
+    TooFewGroupsInTotal { groups: usize, requested: usize },
+
+    /// The overlap limit is below the overlap that some two students are bound to have.
+    OverlapUnreachable { max_overlap: usize, lower_bound: usize, students: usize },
+
+    /// No question set for the student keeps the overlap limit and
+    /// the seating within the given number of attempts.
+    PlacementFailed { student: String, attempts: u64 },
+
+    /// The number of forms is zero.
+    NoForms,
+
+    /// The question set of the student repeats more questions seen before than allowed.
+    TooManyRepeats { student: String, repeats: usize, max_repeats: usize },
+
+    /// A question bank is referred to by its 0-based index, but not given.
+    MissingBank { bank: usize },
+
+    /// The numbers of question banks and blueprints differ.
+    BlueprintCount { banks: usize, blueprints: usize },
+
+    /// The question banks have more categories together than a question can be numbered in.
+    TooManyCategories { categories: usize },
+
+    /// The question `id` of the question bank of the 0-based index `bank` is in
+    /// the category `category`, which the header of its question bank does not name,
+    /// so the category cannot be put together with those of the other question banks.
+    UnnamedCategory { bank: usize, id: u16, category: u8 },
+
+    /// The blueprint of the question bank of the 0-based index `bank` puts
+    /// the questions in another `QuestionOrder` than the first blueprint does.
+    OrderMismatch { bank: usize },
+
+    /// The question set could not be drawn from the question bank of the 0-based index `bank`.
+    InBank { bank: usize, error: Box<GenerationError> },
+
+    /// No question set for the student keeps the constraints of the question bank
+    /// within the given number of attempts.
+    ConstraintsUnmet { student: String, attempts: u64 },
+
+    /// The anchor questions and the questions that they require, which every set has,
+    /// cannot keep `constraint` together, e.g. a required question is not in the question bank
+    /// or has no slot left in the sections it belongs to.
+    AnchorConstraint { constraint: Constraint },
+
+    /// No values of the parameters of the templated question make its text
+    /// and distinct choices within the given number of attempts.
+    ValuesUnmet { id: u16, attempts: u64 },
+}
+
+impl fmt::Display for GenerationError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Self::NothingSelected =>
+                write!(f, "no question is to be selected"),
+            Self::InvalidRange { section, start, end } =>
+                write!(f, "section {} has the invalid range {}..={}", section, start, end),
+            Self::DuplicateAnchor { id } =>
+                write!(f, "anchor question {} is listed more than once", id),
+            Self::DuplicateId { id } =>
+                write!(f, "question ID {} is used by two or more questions", id),
+            Self::MissingAnchor { id } =>
+                write!(f, "anchor question {} is not in the question bank", id),
+            Self::MissingCategory { start, end, category, .. } =>
+                write!(f, "range {}..={} has no question of category {}", start, end, category),
+            Self::TooFewGroups { start, end, groups, requested, .. } =>
+                write!(f, "range {}..={} has {} distinct groups, {} requested", start, end, groups, requested),
+            Self::TooFewGroupsInTotal { groups, requested } =>
+                write!(f, "the sections together can take {} questions of distinct groups, {} requested", groups, requested),
+            Self::OverlapUnreachable { max_overlap, lower_bound, students } =>
+                write!(f, "an overlap of at most {} is requested, but some two of {} students share at least {} questions", max_overlap, students, lower_bound),
+            Self::PlacementFailed { student, attempts } =>
+                write!(f, "no question set for the student {} keeps the overlap limit and the seating in {} attempts", student, attempts),
+            Self::NoForms =>
+                write!(f, "the number of forms is zero"),
+            Self::TooManyRepeats { student, repeats, max_repeats } =>
+                write!(f, "the question set of the student {} repeats {} of the questions seen before, but at most {} may be repeated", student, repeats, max_repeats),
+            Self::MissingBank { bank } =>
+                write!(f, "question bank {} is not given", bank),
+            Self::BlueprintCount { banks, blueprints } =>
+                write!(f, "{} question banks are given with {} blueprints", banks, blueprints),
+            Self::TooManyCategories { categories } =>
+                write!(f, "the question banks have {} categories together, but at most {} are allowed", categories, u8::MAX),
+            Self::UnnamedCategory { bank, id, category } =>
+                write!(f, "question {} of question bank {} is in category {}, which the header of the question bank does not name", id, bank, category),
+            Self::OrderMismatch { bank } =>
+                write!(f, "the blueprint of question bank {} puts the questions in another order than the first blueprint", bank),
+            Self::InBank { bank, error } =>
+                write!(f, "question bank {}: {}", bank, error),
+            Self::ConstraintsUnmet { student, attempts } =>
+                write!(f, "no question set for the student {} keeps the constraints of the question bank in {} attempts", student, attempts),
+            Self::AnchorConstraint { constraint } =>
+                write!(f, "the anchor questions cannot keep the constraint that question {} {} question {}", constraint.get_question(), constraint.get_kind().to_lowercase(), constraint.get_other()),
+            Self::ValuesUnmet { id, attempts } =>
+                write!(f, "no values of the parameters of question {} make its text and distinct choices in {} attempts", id, attempts),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
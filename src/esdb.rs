@@ -0,0 +1,695 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::path::Path;
+
+use calamine::DataType;
+use rust_xlsxwriter::{ Format, FormatBorder, Workbook };
+
+use crate::Error;
+use crate::Exam;
+use crate::Excel;
+use crate::SQLiteDB;
+use crate::Student;
+use crate::{ choices_to_text, text_to_choices };
+use crate::template::{ text_to_values, values_to_text };
+use crate::{ ShuffledQuestion, ShuffledQuestions, ShuffledQSet, ShuffledQSets };
+
+/// A trait defining the database operations for a generated examination (`Exam`).
+///
+/// This abstracts the storage mechanism for the shuffled question sets of all students,
+/// so that the mapping from each student's paper to the original questions and
+/// their choice order survives after the `Generator` is dropped.
+pub trait ESDB
+{
+    // fn open(path: String) -> Result<Self, Error> where Self: Sized;
+    /// Opens a connection to the exam set database.
+    /// If the path has no extension, proper extention is appended.
+    ///
+    /// # Arguments
+    /// * `path` - The file path for the database.
+    ///
+    /// # Output
+    /// `Result<Self, Error>` - A `Self` instance if the connection is successful,
+    /// or an `Error` telling why it is not.
+    ///
+    /// # Example 1 for SQLiteDB
+    /// ```
+    /// use qrate::{ SQLiteDB, ESDB };
+    ///
+    /// let db = SQLiteDB::open("test_open".to_string()).unwrap();
+    /// assert_eq!(db.get_path(), "test_open.esdb");
+    /// std::fs::remove_file(db.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 2 for Excel
+    /// ```
+    /// use qrate::{ Excel, ESDB };
+    ///
+    /// let excel = Excel::open("midterm".to_string()).unwrap();
+    /// assert_eq!(excel.get_path(), "midterm.es.xlsx");
+    /// ```
+    fn open(path: String) -> Result<Self, Error> where Self: Sized;
+
+    // fn make_tables(&self) -> Result<(), Error>
+    /// Creates the necessary tables in the database.
+    ///
+    /// # Output
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    ///
+    /// # Example 1 for SQLiteDB
+    /// ```
+    /// use qrate::{ SQLiteDB, ESDB };
+    ///
+    /// let db = SQLiteDB::open("test_make_tables".to_string()).unwrap();
+    /// assert!(db.make_tables().is_ok());
+    /// std::fs::remove_file(db.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 2 for Excel
+    /// ```
+    /// use qrate::{ Excel, ESDB, Exam, ShuffledQSets };
+    /// use std::path::Path;
+    ///
+    /// let excel = Excel::open("test_make_tables".to_string()).unwrap();
+    /// assert!(excel.make_tables().is_ok());
+    /// assert!(Path::new(excel.get_path()).exists());
+    ///
+    /// // Existing exams are left as they are.
+    /// let mut excel = Excel::open("test_make_tables_kept".to_string()).unwrap();
+    /// let exam = Exam::new("Quiz".to_string(), "2026-10-16".to_string(), "bank".to_string(), Some(7), ShuffledQSets::new());
+    /// excel.write_exam(&exam).unwrap();
+    /// assert!(excel.make_tables().is_ok());
+    /// assert_eq!(excel.read_exam().unwrap().get_title(), "Quiz");
+    /// std::fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// std::fs::remove_file("test_make_tables.es.xlsx").unwrap(); // Clean up
+    /// ```
+    fn make_tables(&self) -> Result<(), Error>;
+
+    // fn read_exams(&self) -> Result<Vec<Exam>, Error>
+    /// Reads every `Exam` (its information and all shuffled question sets)
+    /// from the database, oldest first.
+    ///
+    /// # Output
+    /// `Result<Vec<Exam>, Error>` - The `Exam`s in the order they were written
+    /// on success, or an `Error` on failure.
+    ///
+    /// # Example 1 for SQLiteDB
+    /// ```
+    /// use qrate::{ SQLiteDB, ESDB, Exam, History, ShuffledQSets };
+    ///
+    /// let mut db = SQLiteDB::open("test_read_exams".to_string()).unwrap();
+    /// db.write_exam(&Exam::new("Midterm".to_string(), "2026-10-16".to_string(), "bank".to_string(), Some(1), ShuffledQSets::new())).unwrap();
+    /// let mut exam = Exam::new("Final".to_string(), "2026-12-18".to_string(), "bank".to_string(), Some(2), ShuffledQSets::new());
+    /// exam.set_bank_sources(vec!["bank".to_string(), "other bank".to_string()]);
+    /// db.write_exam(&exam).unwrap();
+    ///
+    /// let history = History::new_with_exams(db.read_exams().unwrap());
+    /// assert_eq!(history.get_exams().len(), 2);
+    /// assert_eq!(history.get_exams()[0].get_title(), "Midterm");
+    /// assert_eq!(history.get_exams()[1].get_title(), "Final");
+    /// assert!(history.get_exams()[0].get_bank_sources().is_empty());
+    /// assert_eq!(history.get_exams()[1].get_bank_sources(), exam.get_bank_sources());
+    /// std::fs::remove_file(db.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 2 for Excel
+    /// ```
+    /// use qrate::{ Excel, ESDB, Exam, ShuffledQSets };
+    ///
+    /// let mut excel = Excel::open("test_read_exams".to_string()).unwrap();
+    /// excel.write_exam(&Exam::new("Midterm".to_string(), "2026-10-16".to_string(), "bank".to_string(), Some(1), ShuffledQSets::new())).unwrap();
+    /// excel.write_exam(&Exam::new("Final".to_string(), "2026-12-18".to_string(), "bank".to_string(), Some(2), ShuffledQSets::new())).unwrap();
+    ///
+    /// let exams = excel.read_exams().unwrap();
+    /// assert_eq!(exams.len(), 2);
+    /// assert_eq!(exams[1].get_seed(), Some(2));
+    /// std::fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    fn read_exams(&self) -> Result<Vec<Exam>, Error>;
+
+    // fn read_exam(&self) -> Result<Exam, Error>
+    /// Reads the latest `Exam` (its information and all shuffled question sets) from the database.
+    ///
+    /// # Output
+    /// `Result<Exam, Error>` - The `Exam` written last on success,
+    /// `Err(Error::Validation)` if the database has no `Exam`, or another `Error` on failure.
+    ///
+    /// # Example 1 for SQLiteDB
+    /// ```
+    /// use qrate::{ SQLiteDB, ESDB, Exam, QBank, Question, Student, Students, Generator };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=5
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string())];
+    /// let generator = Generator::new(&qbank, 1, 5, 3, &students).unwrap();
+    /// let exam = generator.get_exam("2026-10-16".to_string(), "./Test.qbdb".to_string());
+    ///
+    /// let mut db = SQLiteDB::open("test_read_exam".to_string()).unwrap();
+    /// db.write_exam(&exam).unwrap();
+    ///
+    /// let read_exam = db.read_exam().unwrap();
+    /// assert_eq!(read_exam.get_title(), "Examination");
+    /// assert_eq!(read_exam.get_seed(), generator.get_seed());
+    /// let read_qset = &read_exam.get_shuffled_qsets()[0];
+    /// assert_eq!(read_qset.get_student().get_name(), "Alice");
+    /// for (read, written) in read_qset.get_shuffled_questions().iter().zip(exam.get_shuffled_qsets()[0].get_shuffled_questions())
+    /// {
+    ///     assert_eq!(read.get_question(), written.get_question());
+    ///     assert_eq!(read.get_choices(), written.get_choices());
+    /// }
+    /// std::fs::remove_file(db.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 2 for Excel
+    /// ```
+    /// use qrate::{ Excel, ESDB, Exam, QBank, Question, Student, Students, Generator };
+    /// use std::fs;
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=5
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Bob".to_string(), "2".to_string())];
+    /// let generator = Generator::new(&qbank, 1, 5, 3, &students).unwrap();
+    /// let exam = generator.get_exam("2026-10-16".to_string(), "./Test.qbdb".to_string());
+    ///
+    /// let mut excel = Excel::open("test_read_exam".to_string()).unwrap();
+    /// excel.write_exam(&exam).unwrap();
+    ///
+    /// let read_exam = excel.read_exam().unwrap();
+    /// assert_eq!(read_exam.get_date(), "2026-10-16");
+    /// assert_eq!(read_exam.get_seed(), generator.get_seed());
+    /// let read_qset = &read_exam.get_shuffled_qsets()[0];
+    /// assert_eq!(read_qset.get_student().get_id(), "2");
+    /// for (read, written) in read_qset.get_shuffled_questions().iter().zip(exam.get_shuffled_qsets()[0].get_shuffled_questions())
+    /// {
+    ///     assert_eq!(read.get_question(), written.get_question());
+    ///     assert_eq!(read.get_choices(), written.get_choices());
+    /// }
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    fn read_exam(&self) -> Result<Exam, Error>
+    {
+        self.read_exams()?.pop()
+            .ok_or_else(|| Error::Validation("the exam set database has no exam".to_string()))
+    }
+
+    // fn write_exam(&mut self, exam: &Exam) -> Result<(), Error>
+    /// Writes an entire `Exam` (its information and all shuffled question sets) to the database.
+    ///
+    /// The `Exam` is appended after those already in the database, so that
+    /// one database keeps the history of all the examinations held from a question bank.
+    ///
+    /// # Arguments
+    /// * `exam` - A reference to the `Exam` to be written to the database.
+    ///
+    /// # Output
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    ///
+    /// # Example 1 for SQLiteDB
+    /// ```
+    /// use qrate::{ SQLiteDB, ESDB, Exam, ShuffledQSet, ShuffledQuestion, Student };
+    ///
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_student(&Student::new("Alice".to_string(), "1".to_string()));
+    /// let mut sq = ShuffledQuestion::new(3, 4);
+    /// sq.set_choices(vec![2, 4, 1, 3]);
+    /// sq.set_bank(1);     // Question 3 of the second question bank
+    /// sq.set_values(vec![("a".to_string(), 3), ("b".to_string(), -42)]);  // Values of its parameters
+    /// qset.set_shuffled_questions(vec![sq]);
+    /// let exam = Exam::new("Quiz".to_string(), "2026-10-16".to_string(), "bank".to_string(), Some(1), vec![qset]);
+    ///
+    /// let mut db = SQLiteDB::open("test_write_exam".to_string()).unwrap();
+    /// assert!(db.write_exam(&exam).is_ok());
+    /// assert!(db.write_exam(&exam).is_ok()); // Appended after the first one
+    /// assert_eq!(db.read_exams().unwrap().len(), 2);
+    /// let read_exam = db.read_exam().unwrap();
+    /// assert_eq!(read_exam.get_shuffled_qsets().len(), 1);
+    /// let read_question = &read_exam.get_shuffled_qsets()[0].get_shuffled_questions()[0];
+    /// assert_eq!(read_question.get_bank(), 1);
+    /// assert_eq!(read_question.get_values(), &vec![("a".to_string(), 3), ("b".to_string(), -42)]);
+    /// std::fs::remove_file(db.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 2 for Excel
+    /// ```
+    /// use qrate::{ Excel, ESDB, Exam, ShuffledQSet, ShuffledQuestion, Student };
+    /// use std::path::Path;
+    /// use std::fs;
+    ///
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_student(&Student::new("Alice".to_string(), "1".to_string()));
+    /// let mut sq = ShuffledQuestion::new(3, 4);
+    /// sq.set_values(vec![("a".to_string(), 3), ("b".to_string(), -42)]);  // Values of its parameters
+    /// qset.set_shuffled_questions(vec![sq]);
+    /// let mut empty = ShuffledQSet::new_empty();     // A student who has no question
+    /// empty.set_student(&Student::new("Bob".to_string(), "2".to_string()));
+    /// let exam = Exam::new("Quiz".to_string(), "2026-10-16".to_string(), "bank".to_string(), Some(1), vec![qset, empty]);
+    ///
+    /// let mut excel = Excel::open("test_write_exam".to_string()).unwrap();
+    /// assert!(excel.write_exam(&exam).is_ok());
+    /// assert!(Path::new(excel.get_path()).exists());
+    /// assert!(excel.write_exam(&exam).is_ok()); // Appended after the first one
+    /// assert_eq!(excel.read_exams().unwrap().len(), 2);
+    /// let read_exam = excel.read_exam().unwrap();
+    /// assert_eq!(read_exam.get_shuffled_qsets()[0].get_shuffled_questions()[0].get_values()[1], ("b".to_string(), -42));
+    /// assert_eq!(read_exam.get_shuffled_qsets().len(), 2);
+    /// assert_eq!(read_exam.get_shuffled_qsets()[1].get_student().get_name(), "Bob");
+    /// assert!(read_exam.get_shuffled_qsets()[1].get_shuffled_questions().is_empty());
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    fn write_exam(&mut self, exam: &Exam) -> Result<(), Error>;
+}
+
+/// The tables of an exam set database, in which every row belongs to the exam numbered `exam`.
+const EXAM_TABLES: &str = r#"CREATE TABLE IF NOT EXISTS tblExam (
+    exam    INTEGER NOT NULL UNIQUE,
+    title   TEXT NOT NULL,
+    date    TEXT NOT NULL,
+    source  TEXT NOT NULL,
+    seed    INTEGER,
+    sources TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY(exam)
+);
+CREATE TABLE IF NOT EXISTS tblSets (
+    exam    INTEGER NOT NULL,
+    student INTEGER NOT NULL,
+    name    TEXT NOT NULL,
+    id      TEXT NOT NULL,
+    form    INTEGER,
+    PRIMARY KEY(exam, student)
+);
+CREATE TABLE IF NOT EXISTS tblSetQuestions (
+    exam        INTEGER NOT NULL,
+    student     INTEGER NOT NULL,
+    position    INTEGER NOT NULL,
+    question    INTEGER NOT NULL,
+    choices     TEXT NOT NULL,
+    bank        INTEGER NOT NULL DEFAULT 0,
+    parameter_values    TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY(exam, student, position)
+);"#;
+
+impl ESDB for SQLiteDB
+{
+    // fn open(path: String) -> Result<Self, Error> where Self: Sized
+    /// Implements `open` for `SQLiteDB`.
+    /// Appends `.esdb` to the path if no extension is present and opens a connection.
+    #[inline]
+    fn open(path: String) -> Result<Self, Error>
+    where Self: Sized
+    {
+        SQLiteDB::open_with_ext(path, "esdb")
+    }
+
+    // fn make_tables(&self) -> Result<(), Error>
+    /// Creates `tblExam`, `tblSets` and `tblSetQuestions` for `SQLiteDB`.
+    ///
+    /// Tables made by earlier versions hold one exam without the `exam` column.
+    /// They are rebuilt with the `exam` column, and their exam becomes the exam numbered 1.
+    /// A `tblExam` without the `sources` column gets it, empty for every exam,
+    /// and a `tblSets` without the `form` column gets it, empty for every student.
+    fn make_tables(&self) -> Result<(), Error>
+    {
+        if !self.has_column("tblExam", "title")?
+            { return Ok(self.conn.execute_batch(EXAM_TABLES)?); }
+        if self.has_column("tblExam", "exam")?
+        {
+            self.conn.execute_batch(EXAM_TABLES)?;
+            self.add_column_if_missing("tblExam", "sources", "TEXT NOT NULL DEFAULT ''")?;
+            return self.add_column_if_missing("tblSets", "form", "INTEGER");
+        }
+
+        // Tables made by earlier versions lack the columns added since.
+        self.add_column_if_missing("tblSetQuestions", "bank", "INTEGER NOT NULL DEFAULT 0")?;
+        self.add_column_if_missing("tblSetQuestions", "parameter_values", "TEXT NOT NULL DEFAULT ''")?;
+        let sql = format!(r#"BEGIN;
+ALTER TABLE tblExam RENAME TO tblExamOld;
+ALTER TABLE tblSets RENAME TO tblSetsOld;
+ALTER TABLE tblSetQuestions RENAME TO tblSetQuestionsOld;
+{}
+INSERT INTO tblExam (exam, title, date, source, seed) SELECT 1, title, date, source, seed FROM tblExamOld LIMIT 1;
+INSERT INTO tblSets (exam, student, name, id) SELECT 1, student, name, id FROM tblSetsOld;
+INSERT INTO tblSetQuestions (exam, student, position, question, choices, bank, parameter_values)
+    SELECT 1, student, position, question, choices, bank, parameter_values FROM tblSetQuestionsOld;
+DROP TABLE tblExamOld;
+DROP TABLE tblSetsOld;
+DROP TABLE tblSetQuestionsOld;
+COMMIT;"#, EXAM_TABLES);
+        self.conn.execute_batch(&sql)?;
+        Ok(())
+    }
+
+    // fn read_exams(&self) -> Result<Vec<Exam>, Error>
+    /// Implements `read_exams` for `SQLiteDB`.
+    ///
+    /// Reads the rows of `tblExam` in the order of their exam numbers, then for each exam
+    /// the students from `tblSets` and their questions from `tblSetQuestions`
+    /// in the order of their positions.
+    /// Tables without the `exam` column, made by earlier versions, hold the exam numbered 1,
+    /// and a `tblExam` without the `sources` column has no sources of question banks.
+    /// A student whose `form` is empty has no version, and every student or none of an exam has one.
+    /// A `tblSetQuestions` without the `bank` column has every question from bank 0,
+    /// and one without the `parameter_values` column has no values of parameters.
+    fn read_exams(&self) -> Result<Vec<Exam>, Error>
+    {
+        let exam_no = if self.has_column("tblExam", "exam")? { "exam" } else { "1" };
+        let sources = if self.has_column("tblExam", "sources")? { "sources" } else { "''" };
+        let sql = format!("SELECT {}, title, date, source, seed, {} FROM tblExam ORDER BY {};", exam_no, sources, exam_no);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let exams = stmt.query_map([], |row| {
+            let no: i64 = row.get(0)?;
+            let seed: Option<i64> = row.get(4)?;
+            let sources: String = row.get(5)?;
+            let mut exam = Exam::new(row.get(1)?, row.get(2)?, row.get(3)?, seed.map(|seed| seed as u64), ShuffledQSets::new());
+            exam.set_bank_sources(text_to_sources(&sources));
+            Ok((no, exam))
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        let form = if self.has_column("tblSets", "form")? { "form" } else { "NULL" };
+        let sql = format!("SELECT student, name, id, {} FROM tblSets WHERE {} = ?1 ORDER BY student;", form, exam_no);
+        let mut set_stmt = self.conn.prepare(&sql)?;
+        let bank = if self.has_column("tblSetQuestions", "bank")? { "bank" } else { "0" };
+        let values = if self.has_column("tblSetQuestions", "parameter_values")? { "parameter_values" } else { "''" };
+        let sql = format!("SELECT question, choices, {}, {} FROM tblSetQuestions WHERE {} = ?1 AND student = ?2 ORDER BY position;", bank, values, exam_no);
+        let mut question_stmt = self.conn.prepare(&sql)?;
+        let mut read_exams = Vec::new();
+        for (no, mut exam) in exams
+        {
+            let sets = set_stmt.query_map([no], |row| {
+                let student: i64 = row.get(0)?;
+                let form: Option<i64> = row.get(3)?;
+                Ok((student, Student::new(row.get(1)?, row.get(2)?), form))
+            })?.collect::<Result<Vec<_>, _>>()?;
+
+            let mut shuffled_qsets = ShuffledQSets::new();
+            let mut assigned_forms = Vec::new();
+            for (student_no, student, form) in sets
+            {
+                if let Some(form) = form
+                    { assigned_forms.push(form as usize); }
+                let rows = question_stmt.query_map([no, student_no], |row| {
+                    let question: u16 = row.get(0)?;
+                    let choices: String = row.get(1)?;
+                    let bank: u16 = row.get(2)?;
+                    let values: String = row.get(3)?;
+                    Ok((question, choices, bank, values))
+                })?.collect::<Result<Vec<_>, _>>()?;
+
+                let mut questions = ShuffledQuestions::new();
+                for (question, choices, bank, values) in rows
+                {
+                    let mut shuffled_question = ShuffledQuestion::new(question, 0);
+                    shuffled_question.set_bank(bank);
+                    let order = text_to_choices(&choices)
+                        .ok_or_else(|| Error::Validation(format!("question {} of student {} in exam {} has the invalid choice order \"{}\"", question, student.get_id(), no, choices)))?;
+                    shuffled_question.set_choices(order);
+                    let values = text_to_values(&values)
+                        .ok_or_else(|| Error::Validation(format!("question {} of student {} in exam {} has the invalid values \"{}\"", question, student.get_id(), no, values)))?;
+                    shuffled_question.set_values(values);
+                    questions.push(shuffled_question);
+                }
+                let mut shuffled_qset = ShuffledQSet::new_empty();
+                shuffled_qset.set_student(&student);
+                shuffled_qset.set_shuffled_questions(questions);
+                shuffled_qsets.push(shuffled_qset);
+            }
+            if !assigned_forms.is_empty() && assigned_forms.len() != shuffled_qsets.len()
+                { return Err(Error::Validation(format!("only {} of the {} students in exam {} have a version", assigned_forms.len(), shuffled_qsets.len(), no))); }
+            exam.set_shuffled_qsets(shuffled_qsets);
+            exam.set_assigned_forms(assigned_forms);
+            read_exams.push(exam);
+        }
+        Ok(read_exams)
+    }
+
+    // fn write_exam(&mut self, exam: &Exam) -> Result<(), Error>
+    /// Implements `write_exam` for `SQLiteDB`.
+    ///
+    /// Inserts the `Exam` in a single transaction under the exam number
+    /// following the largest one in `tblExam`.
+    /// Students are numbered from 1 in the order of the shuffled question sets.
+    fn write_exam(&mut self, exam: &Exam) -> Result<(), Error>
+    {
+        self.make_tables()?;
+        let tx = self.conn.transaction()?;
+        let no: i64 = tx.query_row("SELECT COALESCE(MAX(exam), 0) + 1 FROM tblExam;", [], |row| row.get(0))?;
+        tx.execute("INSERT INTO tblExam (exam, title, date, source, seed, sources) VALUES (?1, ?2, ?3, ?4, ?5, ?6);",
+                    (no, exam.get_title(), exam.get_date(), exam.get_source(), exam.get_seed().map(|seed| seed as i64), sources_to_text(exam.get_bank_sources())))?;
+        {
+            let mut set_stmt = tx.prepare("INSERT INTO tblSets (exam, student, name, id, form) VALUES (?1, ?2, ?3, ?4, ?5);")?;
+            let mut question_stmt = tx.prepare("INSERT INTO tblSetQuestions (exam, student, position, question, choices, bank, parameter_values) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);")?;
+            for (i, shuffled_qset) in exam.get_shuffled_qsets().iter().enumerate()
+            {
+                let student = shuffled_qset.get_student();
+                let form = exam.get_assigned_forms().get(i).map(|&form| form as i64);
+                set_stmt.execute((no, (i + 1) as i64, student.get_name(), student.get_id(), form))?;
+                for (j, shuffled_question) in shuffled_qset.get_shuffled_questions().iter().enumerate()
+                {
+                    question_stmt.execute((no, (i + 1) as i64, (j + 1) as i64, shuffled_question.get_question(), choices_to_text(shuffled_question.get_choices()), shuffled_question.get_bank(), values_to_text(shuffled_question.get_values())))?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+
+
+impl ESDB for Excel
+{
+    // fn open(path: String) -> Result<Self, Error> where Self: Sized
+    /// Implements `open` for `Excel`.
+    /// Appends `.es.xlsx` to the path if no extension is present.
+    #[inline]
+    fn open(path: String) -> Result<Self, Error>
+    where Self: Sized
+    {
+        Excel::open_with_ext(path, "es.xlsx")
+    }
+
+    // fn make_tables(&self) -> Result<(), Error>
+    /// Creates the "Exam" and "Sets" sheets for `Excel`.
+    /// A workbook which already exists is left untouched, so that its exams are kept.
+    fn make_tables(&self) -> Result<(), Error>
+    {
+        if Path::new(&self.path).exists()
+            { return Ok(()); }
+        write_exam_sheets(&self.path, &[])
+    }
+
+    // fn read_exams(&self) -> Result<Vec<Exam>, Error>
+    /// Implements `read_exams` for `Excel`.
+    ///
+    /// The "Exam" sheet has one row for each exam, and every row of the "Sets" sheet
+    /// begins with the number of the exam it belongs to.
+    /// A row of either sheet which cannot be parsed is an `Error::ExcelParse`.
+    /// A row without a position and a question is a student who has no question.
+    /// A row without the "Bank" column has its question from bank 0,
+    /// and one without the "Values" column has no values of parameters.
+    /// A student whose first row has no "Form" has no version, and every student or none of an exam has one.
+    /// An "Exam" sheet without the "Sources" column has no sources of question banks.
+    /// A workbook written by earlier versions, whose "Exam" sheet lists
+    /// the title, the date, the source and the seed downwards, holds one exam.
+    fn read_exams(&self) -> Result<Vec<Exam>, Error>
+    {
+        let range = self.read_sheet("Exam")?;
+        let legacy = range.get((0, 0)).and_then(|c| c.as_string()).is_some_and(|s| s == "Title");
+        let mut numbers = Vec::new();
+        let mut exams = Vec::new();
+        if legacy
+        {
+            let cell = |row: usize| range.get((row, 1)).and_then(|c| c.as_string()).unwrap_or_default();
+            let seed = cell(3).parse::<u64>().ok();
+            numbers.push(1);
+            exams.push(Exam::new(cell(0), cell(1), cell(2), seed, ShuffledQSets::new()));
+        }
+        else
+        {
+            for (i, row) in range.rows().enumerate().skip(1) // Skip header row
+            {
+                let cell = |col: usize| row.get(col).and_then(|c| c.as_string()).unwrap_or_default();
+                let no = row.first().and_then(|d| d.as_f64()).ok_or_else(|| Excel::parse_error(&range, "Exam", i, "the exam number is missing or not a number"))? as u64;
+                // Written as text because a spreadsheet number cannot hold every u64 exactly.
+                // An exam drawn from an external source has no seed.
+                let seed = match cell(4)
+                {
+                    text if text.is_empty() => None,
+                    text => Some(text.parse::<u64>().map_err(|_| Excel::parse_error(&range, "Exam", i, "the seed is not a number"))?),
+                };
+                let mut exam = Exam::new(cell(1), cell(2), cell(3), seed, ShuffledQSets::new());
+                exam.set_bank_sources(text_to_sources(&cell(5)));
+                numbers.push(no);
+                exams.push(exam);
+            }
+        }
+
+        let range = self.read_sheet("Sets")?;
+        let parse_error = |i: usize, message: &str| Excel::parse_error(&range, "Sets", i, message);
+        let c = if legacy { 0 } else { 1 };     // The column of the student number
+        let mut current = (0_u64, 0_u64);
+        let mut current_exam = 0_usize;
+        let mut assigned_forms: Vec<Vec<usize>> = vec![Vec::new(); exams.len()];
+        for (i, row) in range.rows().enumerate().skip(1) // Skip header row
+        {
+            let exam_no = if legacy { 1 } else { row.first().and_then(|d| d.as_f64()).ok_or_else(|| parse_error(i, "the exam number is missing or not a number"))? as u64 };
+            let student_no = row.get(c).and_then(|d| d.as_f64()).ok_or_else(|| parse_error(i, "the student number is missing or not a number"))? as u64;
+            if (exam_no, student_no) != current
+            {
+                current_exam = numbers.iter().position(|no| *no == exam_no).ok_or_else(|| parse_error(i, "the row belongs to no exam"))?;
+                let name = row.get(c + 1).and_then(|d| d.as_string()).ok_or_else(|| parse_error(i, "the name is missing"))?;
+                let id = row.get(c + 2).and_then(|d| d.as_string()).ok_or_else(|| parse_error(i, "the ID is missing"))?;
+                let mut shuffled_qset = ShuffledQSet::new_empty();
+                shuffled_qset.set_student(&Student::new(name, id));
+                let mut shuffled_qsets = exams[current_exam].get_shuffled_qsets().clone();
+                shuffled_qsets.push(shuffled_qset);
+                exams[current_exam].set_shuffled_qsets(shuffled_qsets);
+                if let Some(cell) = row.get(c + 8).filter(|d| !d.is_empty())
+                    { assigned_forms[current_exam].push(cell.as_f64().ok_or_else(|| parse_error(i, "the form is not a number"))? as usize); }
+                current = (exam_no, student_no);
+            }
+            // A student who has no question has a row without a position and a question.
+            if row.get(c + 3).is_none_or(|d| d.is_empty()) && row.get(c + 4).is_none_or(|d| d.is_empty())
+                { continue; }
+            let question = row.get(c + 4).and_then(|d| d.as_f64()).ok_or_else(|| parse_error(i, "the question is missing or not a number"))? as u16;
+            let mut shuffled_question = ShuffledQuestion::new(question, 0);
+            let order = row.get(c + 5).and_then(|d| d.as_string()).and_then(|text| text_to_choices(&text))
+                            .ok_or_else(|| parse_error(i, "the choices are missing or not numbers"))?;
+            shuffled_question.set_choices(order);
+            if let Some(cell) = row.get(c + 6).filter(|d| !d.is_empty())
+                { shuffled_question.set_bank(cell.as_f64().ok_or_else(|| parse_error(i, "the bank is not a number"))? as u16); }
+            if let Some(text) = row.get(c + 7).and_then(|d| d.as_string())
+                { shuffled_question.set_values(text_to_values(&text).ok_or_else(|| parse_error(i, "the values are not names with numbers"))?); }
+
+            let mut shuffled_qsets = exams[current_exam].get_shuffled_qsets().clone();
+            let shuffled_qset = shuffled_qsets.last_mut().ok_or_else(|| parse_error(i, "the row belongs to no student"))?;
+            let mut questions = shuffled_qset.get_shuffled_questions().clone();
+            questions.push(shuffled_question);
+            shuffled_qset.set_shuffled_questions(questions);
+            exams[current_exam].set_shuffled_qsets(shuffled_qsets);
+        }
+
+        for (k, (exam, assigned_forms)) in exams.iter_mut().zip(assigned_forms).enumerate()
+        {
+            if !assigned_forms.is_empty() && assigned_forms.len() != exam.get_shuffled_qsets().len()
+                { return Err(Error::Validation(format!("only {} of the {} students in exam {} have a version", assigned_forms.len(), exam.get_shuffled_qsets().len(), numbers[k]))); }
+            exam.set_assigned_forms(assigned_forms);
+        }
+
+        // A workbook made empty by `make_tables()` of earlier versions holds no exam.
+        if legacy && exams[0].get_title().is_empty() && exams[0].get_shuffled_qsets().is_empty()
+            { exams.clear(); }
+        Ok(exams)
+    }
+
+    // fn write_exam(&mut self, exam: &Exam) -> Result<(), Error>
+    /// Implements `write_exam` for `Excel`.
+    ///
+    /// Reads the exams already in the workbook and writes them again
+    /// followed by `exam`, all numbered from 1 in that order.
+    fn write_exam(&mut self, exam: &Exam) -> Result<(), Error>
+    {
+        let mut exams = if Path::new(&self.path).exists() { self.read_exams()? } else { Vec::new() };
+        exams.push(exam.clone());
+        write_exam_sheets(&self.path, &exams)
+    }
+}
+
+// fn write_exam_sheets(path: &str, exams: &[Exam]) -> Result<(), Error>
+/// Writes `exams` to the "Exam" and "Sets" sheets of a new workbook at `path`,
+/// numbering them from 1.
+///
+/// The "Sets" sheet has one row for each question of each student, and
+/// one row without a position and a question for a student who has no question,
+/// so that every student is kept as `SQLiteDB` keeps them.
+fn write_exam_sheets(path: &str, exams: &[Exam]) -> Result<(), Error>
+{
+    let mut workbook = Workbook::new();
+    let border_format = Format::new().set_border(FormatBorder::Thin);
+    let bold_border_format = Format::new().set_bold().set_border(FormatBorder::Thin);
+
+    // 1. Write "Exam" sheet
+    let exam_sheet = workbook.add_worksheet().set_name("Exam")?;
+    for (col, title) in ["Exam", "Title", "Date", "Source", "Seed", "Sources"].iter().enumerate()
+        { exam_sheet.write_string_with_format(0, col as u16, *title, &bold_border_format)?; }
+    for (k, exam) in exams.iter().enumerate()
+    {
+        let row = (k + 1) as u32;
+        exam_sheet.write_number_with_format(row, 0, (k + 1) as f64, &border_format)?;
+        exam_sheet.write_string_with_format(row, 1, exam.get_title(), &border_format)?;
+        exam_sheet.write_string_with_format(row, 2, exam.get_date(), &border_format)?;
+        exam_sheet.write_string_with_format(row, 3, exam.get_source(), &border_format)?;
+        // Written as text because a spreadsheet number cannot hold every u64 exactly.
+        let seed = exam.get_seed().map(|seed| seed.to_string()).unwrap_or_default();
+        exam_sheet.write_string_with_format(row, 4, seed, &border_format)?;
+        exam_sheet.write_string_with_format(row, 5, sources_to_text(exam.get_bank_sources()), &border_format)?;
+    }
+
+    // 2. Write "Sets" sheet
+    let sets_sheet = workbook.add_worksheet().set_name("Sets")?;
+    for (col, title) in ["Exam", "No", "Name", "ID", "Position", "Question", "Choices", "Bank", "Values", "Form"].iter().enumerate()
+        { sets_sheet.write_string_with_format(0, col as u16, *title, &bold_border_format)?; }
+
+    let mut current_row = 1_u32;
+    for (k, exam) in exams.iter().enumerate()
+    {
+        for (i, shuffled_qset) in exam.get_shuffled_qsets().iter().enumerate()
+        {
+            let student = shuffled_qset.get_student();
+            let form = exam.get_assigned_forms().get(i);
+            if shuffled_qset.get_shuffled_questions().is_empty()
+            {
+                sets_sheet.write_number_with_format(current_row, 0, (k + 1) as f64, &border_format)?;
+                sets_sheet.write_number_with_format(current_row, 1, (i + 1) as f64, &border_format)?;
+                sets_sheet.write_string_with_format(current_row, 2, student.get_name(), &border_format)?;
+                sets_sheet.write_string_with_format(current_row, 3, student.get_id(), &border_format)?;
+                if let Some(&form) = form
+                    { sets_sheet.write_number_with_format(current_row, 9, form as f64, &border_format)?; }
+                current_row += 1;
+            }
+            for (j, shuffled_question) in shuffled_qset.get_shuffled_questions().iter().enumerate()
+            {
+                sets_sheet.write_number_with_format(current_row, 0, (k + 1) as f64, &border_format)?;
+                sets_sheet.write_number_with_format(current_row, 1, (i + 1) as f64, &border_format)?;
+                sets_sheet.write_string_with_format(current_row, 2, student.get_name(), &border_format)?;
+                sets_sheet.write_string_with_format(current_row, 3, student.get_id(), &border_format)?;
+                sets_sheet.write_number_with_format(current_row, 4, (j + 1) as f64, &border_format)?;
+                sets_sheet.write_number_with_format(current_row, 5, shuffled_question.get_question() as f64, &border_format)?;
+                sets_sheet.write_string_with_format(current_row, 6, choices_to_text(shuffled_question.get_choices()), &border_format)?;
+                sets_sheet.write_number_with_format(current_row, 7, shuffled_question.get_bank() as f64, &border_format)?;
+                sets_sheet.write_string_with_format(current_row, 8, values_to_text(shuffled_question.get_values()), &border_format)?;
+                if let Some(&form) = form
+                    { sets_sheet.write_number_with_format(current_row, 9, form as f64, &border_format)?; }
+                current_row += 1;
+            }
+        }
+    }
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+// fn sources_to_text(sources: &[String]) -> String
+/// Writes the sources of the question banks of an exam one per line.
+#[inline]
+fn sources_to_text(sources: &[String]) -> String
+{
+    sources.join("\n")
+}
+
+// fn text_to_sources(text: &str) -> Vec<String>
+/// Reads the sources of the question banks of an exam written by `sources_to_text()`.
+#[inline]
+fn text_to_sources(text: &str) -> Vec<String>
+{
+    text.lines().map(str::to_string).collect()
+}
@@ -0,0 +1,352 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use crate::ShuffledQSets;
+
+/// Represents a generated examination: the shuffled question sets of all students
+/// together with the information needed to grade, reprint and analyse it later.
+#[derive(Debug, Clone)]
+pub struct Exam
+{
+    title: String,      // The title of the examination.
+    date: String,       // The date of the examination, e.g. "2026-10-16".
+    source: String,     // The question bank that the questions were drawn from, e.g. its path.
+    seed: Option<u64>,  // The master seed that the shuffled sets were generated from, if any.
+    bank_sources: Vec<String>,  // The question banks of an exam drawn from several, e.g. their paths, in the order of their indices.
+    shuffled_qsets: ShuffledQSets,
+    assigned_forms: Vec<usize>, // The 0-based version of each shuffled question set, empty unless the students share versions.
+}
+
+impl Exam
+{
+    // pub fn new_empty() -> Self
+    /// Creates a new, empty `Exam`.
+    ///
+    /// # Output
+    /// `Self` - A new, empty `Exam` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let exam = Exam::new_empty();
+    /// assert_eq!(exam.get_title(), "");
+    /// assert!(exam.get_shuffled_qsets().is_empty());
+    /// ```
+    #[inline]
+    pub fn new_empty() -> Self
+    {
+        Self
+        {
+            title: String::new(),
+            date: String::new(),
+            source: String::new(),
+            seed: None,
+            bank_sources: Vec::new(),
+            shuffled_qsets: ShuffledQSets::new(),
+            assigned_forms: Vec::new(),
+        }
+    }
+
+    // pub fn new(title: String, date: String, source: String, seed: Option<u64>, shuffled_qsets: ShuffledQSets) -> Self
+    /// Creates a new `Exam` with the given values.
+    ///
+    /// # Arguments
+    /// * `title` - The title of the examination.
+    /// * `date` - The date of the examination.
+    /// * `source` - The question bank that the questions were drawn from.
+    /// * `seed` - The master seed that the shuffled sets were generated from,
+    ///   or `None` if they were drawn from an external `RandomSource`.
+    /// * `shuffled_qsets` - The shuffled question sets of all students.
+    ///
+    /// # Output
+    /// `Self` - A new `Exam` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSets };
+    /// let exam = Exam::new("Midterm".to_string(), "2026-10-16".to_string(), "./Information_Security.qbdb".to_string(), Some(7), ShuffledQSets::new());
+    /// assert_eq!(exam.get_title(), "Midterm");
+    /// assert_eq!(exam.get_seed(), Some(7));
+    /// ```
+    #[inline]
+    pub fn new(title: String, date: String, source: String, seed: Option<u64>, shuffled_qsets: ShuffledQSets) -> Self
+    {
+        Self { title, date, source, seed, bank_sources: Vec::new(), shuffled_qsets, assigned_forms: Vec::new() }
+    }
+
+    // pub fn get_title(&self) -> &String
+    /// Gets the title of the examination.
+    ///
+    /// # Output
+    /// `&String` - A reference to the title.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSets };
+    /// let exam = Exam::new("Final".to_string(), String::new(), String::new(), None, ShuffledQSets::new());
+    /// assert_eq!(exam.get_title(), "Final");
+    /// ```
+    #[inline]
+    pub fn get_title(&self) -> &String
+    {
+        &self.title
+    }
+
+    // pub fn set_title(&mut self, title: String)
+    /// Sets the title of the examination.
+    ///
+    /// # Arguments
+    /// * `title` - The new title.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_title("Make-up".to_string());
+    /// assert_eq!(exam.get_title(), "Make-up");
+    /// ```
+    #[inline]
+    pub fn set_title(&mut self, title: String)
+    {
+        self.title = title;
+    }
+
+    // pub fn get_date(&self) -> &String
+    /// Gets the date of the examination.
+    ///
+    /// # Output
+    /// `&String` - A reference to the date.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSets };
+    /// let exam = Exam::new(String::new(), "2026-10-16".to_string(), String::new(), None, ShuffledQSets::new());
+    /// assert_eq!(exam.get_date(), "2026-10-16");
+    /// ```
+    #[inline]
+    pub fn get_date(&self) -> &String
+    {
+        &self.date
+    }
+
+    // pub fn set_date(&mut self, date: String)
+    /// Sets the date of the examination.
+    ///
+    /// # Arguments
+    /// * `date` - The new date.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_date("2026-12-20".to_string());
+    /// assert_eq!(exam.get_date(), "2026-12-20");
+    /// ```
+    #[inline]
+    pub fn set_date(&mut self, date: String)
+    {
+        self.date = date;
+    }
+
+    // pub fn get_source(&self) -> &String
+    /// Gets the question bank that the questions were drawn from.
+    ///
+    /// # Output
+    /// `&String` - A reference to the source question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSets };
+    /// let exam = Exam::new(String::new(), String::new(), "./C_Programming.qbdb".to_string(), None, ShuffledQSets::new());
+    /// assert_eq!(exam.get_source(), "./C_Programming.qbdb");
+    /// ```
+    #[inline]
+    pub fn get_source(&self) -> &String
+    {
+        &self.source
+    }
+
+    // pub fn set_source(&mut self, source: String)
+    /// Sets the question bank that the questions were drawn from.
+    ///
+    /// # Arguments
+    /// * `source` - The new source question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_source("./Software_Engineering.qbdb".to_string());
+    /// assert_eq!(exam.get_source(), "./Software_Engineering.qbdb");
+    /// ```
+    #[inline]
+    pub fn set_source(&mut self, source: String)
+    {
+        self.source = source;
+    }
+
+    // pub fn get_seed(&self) -> Option<u64>
+    /// Gets the master seed that the shuffled sets were generated from.
+    ///
+    /// # Output
+    /// `Option<u64>` - The master seed, or `None` if the shuffled sets were
+    /// drawn from an external `RandomSource` and cannot be regenerated from a seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSets };
+    /// let exam = Exam::new(String::new(), String::new(), String::new(), Some(42), ShuffledQSets::new());
+    /// assert_eq!(exam.get_seed(), Some(42));
+    /// assert_eq!(Exam::new_empty().get_seed(), None);
+    /// ```
+    #[inline]
+    pub fn get_seed(&self) -> Option<u64>
+    {
+        self.seed
+    }
+
+    // pub fn set_seed(&mut self, seed: Option<u64>)
+    /// Sets the master seed that the shuffled sets were generated from.
+    ///
+    /// # Arguments
+    /// * `seed` - The new master seed, or `None` for sets drawn from an external `RandomSource`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_seed(Some(u64::MAX));
+    /// assert_eq!(exam.get_seed(), Some(u64::MAX));
+    /// ```
+    #[inline]
+    pub fn set_seed(&mut self, seed: Option<u64>)
+    {
+        self.seed = seed;
+    }
+
+    // pub fn get_bank_sources(&self) -> &Vec<String>
+    /// Gets the question banks that the questions of an exam drawn from several
+    /// question banks were drawn from, in the order of their indices.
+    ///
+    /// # Output
+    /// `&Vec<String>` - A reference to the source of each question bank,
+    /// which is empty for an exam drawn from one question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let exam = Exam::new_empty();
+    /// assert!(exam.get_bank_sources().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_bank_sources(&self) -> &Vec<String>
+    {
+        &self.bank_sources
+    }
+
+    // pub fn set_bank_sources(&mut self, bank_sources: Vec<String>)
+    /// Sets the question banks that the questions of an exam drawn from several
+    /// question banks were drawn from, so that `Generator::new_with_exam_from_banks()`
+    /// can check that it is given the same question banks in the same order.
+    ///
+    /// # Arguments
+    /// * `bank_sources` - The source of each question bank, e.g. its path, in the order of their indices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_bank_sources(vec!["./Networks.qbdb".to_string(), "./Security.qbdb".to_string()]);
+    /// assert_eq!(exam.get_bank_sources()[1], "./Security.qbdb");
+    /// ```
+    #[inline]
+    pub fn set_bank_sources(&mut self, bank_sources: Vec<String>)
+    {
+        self.bank_sources = bank_sources;
+    }
+
+    // pub fn get_shuffled_qsets(&self) -> &ShuffledQSets
+    /// Gets the shuffled question sets of all students.
+    ///
+    /// # Output
+    /// `&ShuffledQSets` - A reference to the shuffled question sets.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let exam = Exam::new_empty();
+    /// assert!(exam.get_shuffled_qsets().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_shuffled_qsets(&self) -> &ShuffledQSets
+    {
+        &self.shuffled_qsets
+    }
+
+    // pub fn set_shuffled_qsets(&mut self, shuffled_qsets: ShuffledQSets)
+    /// Replaces the shuffled question sets of all students.
+    ///
+    /// # Arguments
+    /// * `shuffled_qsets` - The new shuffled question sets.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSet, ShuffledQSets };
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_shuffled_qsets(vec![ShuffledQSet::new_empty()]);
+    /// assert_eq!(exam.get_shuffled_qsets().len(), 1);
+    /// ```
+    #[inline]
+    pub fn set_shuffled_qsets(&mut self, shuffled_qsets: ShuffledQSets)
+    {
+        self.shuffled_qsets = shuffled_qsets;
+    }
+
+    // pub fn get_assigned_forms(&self) -> &Vec<usize>
+    /// Gets the version of the paper that each student was given, when the
+    /// students share the versions that `GenerationOptions::set_forms()` asks for.
+    ///
+    /// # Output
+    /// `&Vec<usize>` - A reference to the 0-based version of each shuffled question set,
+    /// in the same order, which is empty if every student has a paper of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Exam;
+    /// let exam = Exam::new_empty();
+    /// assert!(exam.get_assigned_forms().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_assigned_forms(&self) -> &Vec<usize>
+    {
+        &self.assigned_forms
+    }
+
+    // pub fn set_assigned_forms(&mut self, assigned_forms: Vec<usize>)
+    /// Sets the version of the paper that each student was given.
+    ///
+    /// # Arguments
+    /// * `assigned_forms` - The 0-based version of each shuffled question set, in the same order,
+    ///   or an empty vector if every student has a paper of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, ShuffledQSet };
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_shuffled_qsets(vec![ShuffledQSet::new_empty(), ShuffledQSet::new_empty()]);
+    /// exam.set_assigned_forms(vec![0, 1]);
+    /// assert_eq!(exam.get_assigned_forms(), &vec![0, 1]);
+    /// ```
+    #[inline]
+    pub fn set_assigned_forms(&mut self, assigned_forms: Vec<usize>)
+    {
+        self.assigned_forms = assigned_forms;
+    }
+}
@@ -9,9 +9,10 @@
 
 
 
-use calamine::DataType;
+use calamine::{ DataType, Reader, open_workbook_auto };
 
-use crate::{ Question, Choices, check_path };
+use crate::{ Error, Question, Questions, Choices, Constraint, Constraints, check_path, text_to_choices };
+use crate::template::text_to_parameters;
 
 
 /// Represents an Excel file for question bank operations.
@@ -27,7 +28,7 @@ pub struct Excel
 
 impl Excel
 {
-    // pub fn open(path: String, extention: &str) -> Option<Self>
+    // pub fn open_with_ext(path: String, extention: &str) -> Result<Self, Error>
     /// Creates a new `Excel` instance with a given path.
     ///
     /// # Arguments
@@ -35,7 +36,8 @@ impl Excel
     /// * `extention` - The file extension to append if the path does not have one.
     ///
     /// # Output
-    /// An `Option<Self>` containing the `Excel` instance.
+    /// A `Result<Self, Error>` containing the `Excel` instance. Since the workbook
+    /// is not opened until it is read or written, this does not fail.
     /// 
     /// # Features
     /// If the path does not have an extension, `extention` is appended.
@@ -45,13 +47,13 @@ impl Excel
     /// use qrate::Excel;
     ///
     /// let excel_handler = Excel::open("my_quiz".to_string(), ".qb.xlsx");
-    /// assert!(excel_handler.is_some());
+    /// assert!(excel_handler.is_ok());
     /// assert_eq!(excel_handler.unwrap().get_path(), "my_quiz.qb.xlsx");
     /// ```
     #[inline]
-    pub fn open_with_ext(path: String, extention: &str) -> Option<Self>
+    pub fn open_with_ext(path: String, extention: &str) -> Result<Self, Error>
     {
-        Some(Self { path: check_path(path, extention) })
+        Ok(Self { path: check_path(path, extention) })
     }
 
     // pub fn get_path(&self) -> &String
@@ -72,7 +74,22 @@ impl Excel
         &self.path
     }
     
-    // pub(crate) fn parse_question_row(row: &[calamine::Data]) -> Option<Question>
+    // pub(crate) fn read_sheet(&self, sheet: &str) -> Result<calamine::Range<calamine::Data>, Error>
+    /// Opens the workbook and reads the sheet named `sheet`.
+    ///
+    /// # Output
+    /// `Result<calamine::Range<calamine::Data>, Error>` - The cells of the sheet,
+    /// `Err(Error::ExcelRead)` if the workbook cannot be read, or
+    /// `Err(Error::Schema)` if the workbook has no such sheet.
+    pub(crate) fn read_sheet(&self, sheet: &str) -> Result<calamine::Range<calamine::Data>, Error>
+    {
+        let mut workbook = open_workbook_auto(&self.path)?;
+        if !workbook.sheet_names().iter().any(|name| name == sheet)
+            { return Err(Error::Schema { table: sheet.to_string(), message: format!("the workbook {} has no such sheet", self.path) }); }
+        Ok(workbook.worksheet_range(sheet)?)
+    }
+
+    // pub(crate) fn parse_question_row(row: &[calamine::Data]) -> Result<Question, String>
     /// Parses a single row from an Excel sheet into a `Question` struct.
     ///
     /// This function takes a slice of `calamine::Data` representing a single row
@@ -84,9 +101,9 @@ impl Excel
     ///   followed by pairs of Choice Text and IsAnswer.
     ///
     /// # Output
-    /// * `Some(Question)` if the row is successfully parsed.
-    /// * `None` if essential data (ID, Category, Question Text) is missing or has
-    ///   an incorrect data type.
+    /// * `Ok(Question)` if the row is successfully parsed.
+    /// * `Err(String)` telling which of the essential data (ID, Group, Category,
+    ///   Question Text) is missing or has an incorrect data type.
     ///
     /// # Examples
     /// ```ignore
@@ -120,20 +137,20 @@ impl Excel
     /// ]);
     /// assert_eq!(question.get_choices(), &expected_choices);
     /// ```
-    pub(crate) fn parse_question_row(row: &[calamine::Data]) -> Option<Question>
+    pub(crate) fn parse_question_row(row: &[calamine::Data]) -> Result<Question, String>
     {
-        let id = row.get(0).and_then(|d| d.as_f64()).map(|f| f as u16)?;
-        let group = row.get(1).and_then(|d| d.as_f64()).map(|f| f as u16)?;
-        let category = row.get(2).and_then(|d| d.as_f64()).map(|f| f as u8)?;
-        let question_text = row.get(3).and_then(|d| d.as_string())?;
+        let id = row.first().and_then(|d| d.as_f64()).map(|f| f as u16).ok_or("the ID is missing or not a number")?;
+        let group = row.get(1).and_then(|d| d.as_f64()).map(|f| f as u16).ok_or("the group is missing or not a number")?;
+        let category = row.get(2).and_then(|d| d.as_f64()).map(|f| f as u8).ok_or("the category is missing or not a number")?;
+        let question_text = row.get(3).and_then(|d| d.as_string()).ok_or("the question text is missing")?;
         
         let mut choices = Choices::new();
         for choice_pair in row.get(4..).unwrap_or(&[]).chunks(2)
         {
-            let choice_text = choice_pair.get(0).and_then(|d| d.get_string()).map(|s| s.to_string()).unwrap_or_default();
+            let choice_text = choice_pair.first().and_then(|d| d.get_string()).map(|s| s.to_string()).unwrap_or_default();
             let is_answer = choice_pair.get(1).and_then(|d| d.get_bool()).unwrap_or_else(|| {
                 choice_pair.get(1).and_then(|d| d.get_string())
-                    .map_or(false, |s| s.eq_ignore_ascii_case("TRUE"))
+                    .is_some_and(|s| s.eq_ignore_ascii_case("TRUE"))
             });
 
             if !choice_text.is_empty() || is_answer
@@ -145,6 +162,92 @@ impl Excel
                 break;
             }
         }
-        Some(Question::new(id, group, category, question_text, choices))
+        Ok(Question::new(id, group, category, question_text, choices))
     }
-}
\ No newline at end of file
+
+    // pub(crate) fn parse_question_rows(range: &calamine::Range<calamine::Data>) -> Result<Questions, Error>
+    /// Parses all the rows of the "Questions" sheet into `Question`s, skipping its header row.
+    ///
+    /// If the header row has a "Pinned" column, it follows the choices and holds
+    /// the pinned choices of each question as text such as `"4"` or `"1,2,5"`.
+    /// If it has an "Ordered" column, that holds the choices which keep their
+    /// relative order when shuffled, as text such as `"1,2,3"`.
+    /// If it has a "Distractors" column, that holds the number of incorrect choices
+    /// shown to each student, and is left blank for a question which shows them all.
+    /// If it has a "Parameters" column, that holds the parameters of a templated
+    /// question as text such as `"a=1..9,b=10..99"`.
+    /// Blank rows are skipped, but any other row that cannot be parsed is an
+    /// `Error::ExcelParse` with its row number.
+    pub(crate) fn parse_question_rows(range: &calamine::Range<calamine::Data>) -> Result<Questions, Error>
+    {
+        let mut rows = range.rows();
+        let header = rows.next().unwrap_or(&[]);
+        let find_col = |title: &str| header.iter().position(|cell| cell.get_string() == Some(title));
+        let (pinned_col, ordered_col, distractors_col, parameters_col) = (find_col("Pinned"), find_col("Ordered"), find_col("Distractors"), find_col("Parameters"));
+        let mut questions = Questions::new();
+        for (i, row) in rows.enumerate()
+        {
+            if row.iter().all(|cell| cell.is_empty())
+                { continue; }
+            let choices_end = pinned_col.into_iter().chain(ordered_col).chain(distractors_col).chain(parameters_col).fold(row.len(), usize::min);
+            let mut question = Excel::parse_question_row(&row[..choices_end])
+                .map_err(|message| Self::parse_error(range, "Questions", i + 1, message))?;
+            if let Some(text) = pinned_col.and_then(|col| row.get(col)).and_then(|cell| cell.as_string())
+            {
+                let pinned = text_to_choices(&text).ok_or_else(|| Self::parse_error(range, "Questions", i + 1, format!("the pinned choices \"{}\" are not numbers", text)))?;
+                question.set_pinned(pinned);
+            }
+            if let Some(text) = ordered_col.and_then(|col| row.get(col)).and_then(|cell| cell.as_string())
+            {
+                let ordered = text_to_choices(&text).ok_or_else(|| Self::parse_error(range, "Questions", i + 1, format!("the ordered choices \"{}\" are not numbers", text)))?;
+                question.set_ordered(ordered);
+            }
+            if let Some(cell) = distractors_col.and_then(|col| row.get(col)).filter(|cell| !cell.is_empty())
+            {
+                let shown_distractors = cell.as_f64().ok_or_else(|| Self::parse_error(range, "Questions", i + 1, "the number of distractors is not a number"))?;
+                question.set_shown_distractors(Some(shown_distractors as u8));
+            }
+            if let Some(text) = parameters_col.and_then(|col| row.get(col)).and_then(|cell| cell.as_string())
+            {
+                let parameters = text_to_parameters(&text).ok_or_else(|| Self::parse_error(range, "Questions", i + 1, format!("the parameters \"{}\" are not names with ranges", text)))?;
+                question.set_parameters(parameters);
+            }
+            questions.push(question);
+        }
+        Ok(questions)
+    }
+
+    // pub(crate) fn parse_constraint_rows(range: &calamine::Range<calamine::Data>) -> Result<Constraints, Error>
+    /// Parses all the rows of the "Constraints" sheet into `Constraint`s, skipping its header row.
+    ///
+    /// Each row holds the ID of a question, the kind of the rule, `"Excludes"` or
+    /// `"Requires"`, and the ID of the other question. Blank rows are skipped,
+    /// but any other row that cannot be parsed is an `Error::ExcelParse` with its row number.
+    pub(crate) fn parse_constraint_rows(range: &calamine::Range<calamine::Data>) -> Result<Constraints, Error>
+    {
+        let mut constraints = Constraints::new();
+        for (i, row) in range.rows().enumerate().skip(1)
+        {
+            if row.iter().all(|cell| cell.is_empty())
+                { continue; }
+            let question = row.first().and_then(|d| d.as_f64()).map(|f| f as u16)
+                .ok_or_else(|| Self::parse_error(range, "Constraints", i, "the question is missing or not a number"))?;
+            let kind = row.get(1).and_then(|d| d.as_string()).unwrap_or_default();
+            let other = row.get(2).and_then(|d| d.as_f64()).map(|f| f as u16)
+                .ok_or_else(|| Self::parse_error(range, "Constraints", i, "the other question is missing or not a number"))?;
+            let constraint = Constraint::new_with_kind(&kind, question, other)
+                .ok_or_else(|| Self::parse_error(range, "Constraints", i, format!("the kind \"{}\" is neither Excludes nor Requires", kind)))?;
+            constraints.push(constraint);
+        }
+        Ok(constraints)
+    }
+
+    // pub(crate) fn parse_error(range: &calamine::Range<calamine::Data>, sheet: &str, idx: usize, message: impl Into<String>) -> Error
+    /// Makes an `Error::ExcelParse` for the `idx`-th row of `range`, counted from 0,
+    /// numbering the row as the spreadsheet shows it.
+    pub(crate) fn parse_error(range: &calamine::Range<calamine::Data>, sheet: &str, idx: usize, message: impl Into<String>) -> Error
+    {
+        let first_row = range.start().map(|(row, _)| row as usize).unwrap_or(0);
+        Error::ExcelParse { sheet: sheet.to_string(), row: first_row + idx + 1, message: message.into() }
+    }
+}
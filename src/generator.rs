@@ -10,21 +10,46 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::collections::{ HashMap, HashSet };
 
 use docx_rs::{ Docx, Paragraph, Run, BreakType, PageMargin, AlignmentType,
                 Footer, InstrText, InstrPAGE, InstrNUMPAGES, FieldCharType };
 use genpdf::{ Document, elements, fonts, style, Element, SimplePageDecorator, Alignment };
+use cryptocol::random::Random as PRNG;
 
-use crate::{ Choices, QBank, Questions, check_path };
+use crate::{ Choices, Error, GenerationError, Header, QBank, Question, Questions, check_path, choices_to_text };
+use crate::template::values_to_text;
 use crate::{ Students, Student };
-use crate::{ ShuffledQSet, ShuffledQSets };
+use crate::{ ShuffledQuestion, ShuffledQSet, ShuffledQSets };
+use crate::shuffler::{ QuestionPool, SetOptions };
+use crate::{ Blueprint, Exam, GenerationOptions, History, RandomSource, Seating };
+
+/// The earlier examinations whose questions the sets avoid: those of the latest
+/// `sittings` for every student, and, if `seen` is set, those that each student has seen,
+/// of which at most `max_repeats` may be in the student's set if it is given.
+struct Avoidance<'a>
+{
+    history: &'a History,
+    sittings: usize,
+    max_repeats: Option<usize>,
+    seen: bool,
+}
+
+/// Makes a candidate set for a student, given the weights of the questions and the attempt number.
+type QSetMaker<'a> = dyn FnMut(&Student, &HashMap<(u16, u16), usize>, u64) -> Result<ShuffledQSet, GenerationError> + 'a;
 
+/// A paper to be saved: the student and the version printed on it, its questions and the heading of its answer key.
+type Paper = (Student, Option<usize>, QBank, String);
 
 #[derive(Debug, Clone)]
 pub struct Generator
 {
     origin: QBank,
+    banks: Vec<QBank>,          // The question banks of an exam drawn from several, empty otherwise.
     shuffled_qsets: ShuffledQSets,
+    forms: ShuffledQSets,       // The versions of the paper, empty unless the students share versions.
+    assigned_forms: Vec<usize>, // The 0-based version of each shuffled question set, if there are versions.
+    seed: Option<u64>,         // The master seed, or None if the sets were drawn from an external source.
     current_question_number: u16,
     body_font_size: f32,
     title_font_size: f32,
@@ -61,7 +86,10 @@ impl Generator
     const FOOTER_UNDERLINE: u16 = 0b_100_0000_0000_0000;
     const FOOTER_STRIKE: u16 = 0b_1000_0000_0000_0000;
 
-    // pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, students: &Students) -> Option<Self>
+    /// The number of question sets tried for a student before giving up the overlap limit or the seating.
+    const PLACEMENT_ATTEMPTS: u64 = 64;
+
+    // pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, students: &Students) -> Result<Self, Error>
     /// Creates a new `Generator` instance for multiple shuffled sets, one for each student.
     ///
     /// This function generates shuffled question sets for each student based on the
@@ -69,13 +97,13 @@ impl Generator
     ///
     /// # Arguments
     /// * `qbank` - A reference to the `QBank` containing the original questions.
-    /// * `start` - The 1-based starting index of questions to consider (inclusive).
-    /// * `end` - The 1-based ending index of questions to consider (inclusive).
+    /// * `start` - The smallest question ID to consider (inclusive).
+    /// * `end` - The largest question ID to consider (inclusive).
     /// * `selected` - The number of questions to be randomly selected for each student.
     /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
     ///
     /// # Output
-    /// An `Option<Self>` which is `Some(Generator)` if successful, or `None` if
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
     /// the generation fails (e.g., invalid question range, insufficient questions, or selected count).
     ///
     /// # Examples
@@ -92,36 +120,670 @@ impl Generator
     ///
     /// // Generate exams with 2 questions selected for each student
     /// let generator = Generator::new(&qbank, 1, 2, 2, &students);
-    /// assert!(generator.is_some());
+    /// assert!(generator.is_ok());
+    /// ```
+    #[inline]
+    pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, students: &Students) -> Result<Self, Error>
+    {
+        Self::new_with_seed(qbank, start, end, selected, students, PRNG::new().random_u64())
+    }
+
+    // pub fn new_with_seed(qbank: &QBank, start: u16, end: u16, selected: usize, students: &Students, seed: u64) -> Result<Self, Error>
+    /// Creates a new `Generator` instance reproducibly from a master seed.
+    ///
+    /// Each student's question set is generated from a seed derived from `seed` and
+    /// the student's ID, so the same inputs always give the same question sets,
+    /// choice orders and answer keys, whatever the order of `students` is.
+    /// This makes it possible to reprint a lost paper or to rebuild the answer key
+    /// later from the master seed alone.
+    ///
+    /// The questions are grouped once for all the students, and the question sets
+    /// are generated on as many threads as the machine runs at once. Since no set
+    /// depends on another, the sets are the same as those generated one by one.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` containing the original questions.
+    /// * `start` - The smallest question ID to consider (inclusive).
+    /// * `end` - The largest question ID to consider (inclusive).
+    /// * `selected` - The number of questions to be randomly selected for each student.
+    /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
+    /// * `seed` - The master seed of the examination.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
+    /// the generation fails for the same reasons as `new()`.
+    ///
+    /// # Caution
+    /// Saved `.txt` and `.docx` files are byte-identical for the same inputs.
+    /// `.pdf` files have the same content, but their metadata contains the time of creation.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Generator, Question, ShuffledQSet, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string()),
+    ///                               Student::new("Bob".to_string(), "2".to_string())];
+    ///
+    /// let generator1 = Generator::new_with_seed(&qbank, 1, 10, 5, &students, 20261016).unwrap();
+    /// let generator2 = Generator::new_with_seed(&qbank, 1, 10, 5, &students, 20261016).unwrap();
+    /// assert_eq!(generator1.get_seed(), Some(20261016));
+    /// for ((_, qbank1), (_, qbank2)) in generator1.get_shuffled_qbanks().unwrap().iter().zip(generator2.get_shuffled_qbanks().unwrap().iter())
+    /// {
+    ///     for (q1, q2) in qbank1.get_questions().iter().zip(qbank2.get_questions())
+    ///     {
+    ///         assert_eq!(q1.get_id(), q2.get_id());
+    ///         assert_eq!(q1.get_choices(), q2.get_choices());
+    ///     }
+    /// }
+    ///
+    /// // A large cohort gets the same sets as the students would get one by one.
+    /// let cohort: Students = (1..=2000).map(|i| Student::new(format!("Candidate {}", i), i.to_string())).collect();
+    /// let generator = Generator::new_with_seed(&qbank, 1, 10, 5, &cohort, 7).unwrap();
+    /// for (student, shuffled_qset) in cohort.iter().zip(generator.get_shuffled_qsets())
+    /// {
+    ///     let alone = ShuffledQSet::new_with_seed(&qbank, 1, 10, 5, student, ShuffledQSet::derive_seed(7, student)).unwrap();
+    ///     assert_eq!(shuffled_qset.get_student().get_id(), student.get_id());
+    ///     assert_eq!(shuffled_qset.count_same_position(&alone), 5);
+    ///     for (q1, q2) in shuffled_qset.get_shuffled_questions().iter().zip(alone.get_shuffled_questions())
+    ///         { assert_eq!(q1.get_choices(), q2.get_choices()); }
+    /// }
     /// ```
-    pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, students: &Students) -> Option<Self>
+    #[inline]
+    pub fn new_with_seed(qbank: &QBank, start: u16, end: u16, selected: usize, students: &Students, seed: u64) -> Result<Self, Error>
+    {
+        Self::new_with_blueprint(qbank, &Blueprint::new_with_range(start, end, selected), students, seed)
+    }
+
+    // pub fn new_with_quotas(qbank: &QBank, start: u16, end: u16, quotas: &[(u8, usize)], students: &Students, seed: u64) -> Result<Self, Error>
+    /// Creates a new `Generator` instance reproducibly from a master seed,
+    /// giving every student the same number of questions from each category.
+    ///
+    /// Each student's question set is generated by `ShuffledQSet::new_with_quotas()`
+    /// from a seed derived from `seed` and the student's ID, as in `new_with_seed()`.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` containing the original questions.
+    /// * `start` - The smallest question ID to consider (inclusive).
+    /// * `end` - The largest question ID to consider (inclusive).
+    /// * `quotas` - Pairs of a category and the number of questions to select from it for each student.
+    /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
+    /// * `seed` - The master seed of the examination.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
+    /// the generation fails for the same reasons as `ShuffledQSet::new_with_quotas()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20    // Odd questions are of Type A, and even ones are of Type B.
+    ///     { qbank.push_question(Question::new(id, id, (id % 2 == 0) as u8 + 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string()),
+    ///                               Student::new("Bob".to_string(), "2".to_string())];
+    ///
+    /// let generator = Generator::new_with_quotas(&qbank, 1, 20, &[(1, 4), (2, 2)], &students, 7).unwrap();
+    /// for (_, shuffled_qbank) in generator.get_shuffled_qbanks().unwrap()
+    /// {
+    ///     let type_a = shuffled_qbank.get_questions().iter().filter(|q| q.get_category() == 1).count();
+    ///     let type_b = shuffled_qbank.get_questions().iter().filter(|q| q.get_category() == 2).count();
+    ///     assert_eq!((type_a, type_b), (4, 2));
+    /// }
+    /// ```
+    #[inline]
+    pub fn new_with_quotas(qbank: &QBank, start: u16, end: u16, quotas: &[(u8, usize)], students: &Students, seed: u64) -> Result<Self, Error>
     {
+        Self::new_with_blueprint(qbank, &Blueprint::new_with_quotas(start, end, quotas), students, seed)
+    }
+
+    // pub fn new_with_blueprint(qbank: &QBank, blueprint: &Blueprint, students: &Students, seed: u64) -> Result<Self, Error>
+    /// Creates a new `Generator` instance reproducibly from a master seed,
+    /// assembling every student's question set from the sections of `blueprint`.
+    ///
+    /// Each student's question set is generated by `ShuffledQSet::new_with_blueprint()`
+    /// from a seed derived from `seed` and the student's ID, as in `new_with_seed()`.
+    /// `new_with_seed()` and `new_with_quotas()` are shorthands for a blueprint
+    /// with a single section and a blueprint with a section for each category.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` containing the original questions.
+    /// * `blueprint` - A reference to the `Blueprint` that every student's set is assembled from.
+    /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
+    /// * `seed` - The master seed of the examination.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
+    /// the generation fails for the same reasons as `ShuffledQSet::new_with_blueprint()`,
+    /// or if the overlap limit of `blueprint` cannot be kept.
+    ///
+    /// # Features
+    /// If `blueprint` has an overlap limit (see `Blueprint::set_max_overlap()`),
+    /// the question sets are assembled in the order of `students`, each preferring
+    /// the questions which fewer of the earlier students have got. A set which shares
+    /// more questions than the limit with an earlier set is drawn again, up to a fixed
+    /// number of times. A limit below `Blueprint::get_overlap_lower_bound()` fails at once.
+    /// In this mode, the sets depend on the order of `students` as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Generator, Question, Section, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=80    // Chapters 1 to 3 have questions 1 to 60, and chapter 4 has 61 to 80.
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string()),
+    ///                               Student::new("Bob".to_string(), "2".to_string())];
+    ///
+    /// // A midterm takes 10 questions from chapters 1 to 3 and 5 from chapter 4.
+    /// let blueprint = Blueprint::new(vec![Section::new_with_range(1, 60, 10), Section::new_with_range(61, 80, 5)]);
+    /// let generator = Generator::new_with_blueprint(&qbank, &blueprint, &students, 2026).unwrap();
+    /// for (_, shuffled_qbank) in generator.get_shuffled_qbanks().unwrap()
+    /// {
+    ///     assert_eq!(shuffled_qbank.get_questions().len(), 15);
+    ///     assert_eq!(shuffled_qbank.get_questions().iter().filter(|q| q.get_id() > 60).count(), 5);
+    /// }
+    ///
+    /// // Every student also answers question 7 in order to compare scores across the sets.
+    /// let mut blueprint = blueprint.clone();
+    /// blueprint.push_anchor(7);
+    /// let generator = Generator::new_with_blueprint(&qbank, &blueprint, &students, 2026).unwrap();
+    /// for (_, shuffled_qbank) in generator.get_shuffled_qbanks().unwrap()
+    /// {
+    ///     assert_eq!(shuffled_qbank.get_questions().len(), 16);
+    ///     assert_eq!(shuffled_qbank.get_questions().iter().filter(|q| q.get_id() == 7).count(), 1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn new_with_blueprint(qbank: &QBank, blueprint: &Blueprint, students: &Students, seed: u64) -> Result<Self, Error>
+    {
+        Self::new_with_options(qbank, blueprint, students, &GenerationOptions::new_empty(), seed)
+    }
+
+    // pub fn new_with_options(qbank: &QBank, blueprint: &Blueprint, students: &Students, options: &GenerationOptions, seed: u64) -> Result<Self, Error>
+    /// Creates a new `Generator` instance reproducibly from a master seed as
+    /// `new_with_blueprint()` does, with the seating, the earlier examinations to avoid,
+    /// the limit of questions seen before and the versions of the paper that `options` gives.
+    ///
+    /// The options work together in any combination, as `GenerationOptions` explains
+    /// for each of them. Each candidate set of a student, or of a version, is generated
+    /// from a seed derived from `seed`, the number of the attempt and the student's ID.
+    ///
+    /// Without any option and without an overlap limit in `blueprint`, the sets are
+    /// generated in parallel as `new_with_seed()` explains. With a seating, a history,
+    /// versions or an overlap limit, each set depends on the sets made before it or on
+    /// the earlier examinations, so the sets are generated one by one on the calling thread.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` containing the original questions.
+    /// * `blueprint` - A reference to the `Blueprint` that every student's set is assembled from.
+    /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
+    /// * `options` - A reference to the `GenerationOptions` of the generation.
+    /// * `seed` - The master seed of the examination.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
+    /// the generation fails for the same reasons as `new_with_blueprint()`, if no set is found
+    /// which keeps the overlap limit and a shared question off the positions of the neighbours,
+    /// if the number of versions is zero, or `GenerationError::TooManyRepeats` for the first
+    /// student whose set cannot avoid more than the allowed questions seen before.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Error, GenerationError, GenerationOptions, Generator, History, QBank, Question, Seating, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=4).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// let blueprint = Blueprint::new_with_range(1, 20, 5);
+    ///
+    /// // The midterm used questions 1 to 5 for everybody.
+    /// let midterm = Generator::new_with_blueprint(&qbank, &Blueprint::new_with_range(1, 5, 5), &students, 1).unwrap();
+    ///
+    /// // Two versions for a row of four students, avoiding the midterm questions
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_seating(Seating::new_with_grid(&[(1..=4).map(|i| i.to_string()).collect()]));
+    /// options.set_history(History::new_with_exams(vec![midterm.get_exam(String::new(), String::new())]));
+    /// options.set_sittings(1);
+    /// options.set_forms(Some(2));
+    /// let generator = Generator::new_with_options(&qbank, &blueprint, &students, &options, 2026).unwrap();
+    /// let assigned: Vec<usize> = (0..4).map(|i| generator.get_assigned_form(i).unwrap()).collect();
+    /// assert_eq!(assigned, vec![0, 1, 0, 1]);
+    /// for form in generator.get_forms()
+    ///     { assert!(form.get_shuffled_questions().iter().all(|q| q.get_question() > 5)); }
+    ///
+    /// options.set_forms(Some(0));
+    /// let error = Generator::new_with_options(&qbank, &blueprint, &students, &options, 2026).unwrap_err();
+    /// assert!(matches!(error, Error::Generation(GenerationError::NoForms)));
+    /// ```
+    pub fn new_with_options(qbank: &QBank, blueprint: &Blueprint, students: &Students, options: &GenerationOptions, seed: u64) -> Result<Self, Error>
+    {
+        // The questions are grouped once for all the students and all the attempts.
+        let pools = [QuestionPool::new(qbank, blueprint)?];
+        let make_qset = |student: &Student, weights: &HashMap<(u16, u16), usize>, attempt: u64| {
+            let student_seed = ShuffledQSet::derive_seed(seed.wrapping_add(attempt), student);
+            ShuffledQSet::new_with_pools_source(&pools, student, &SetOptions { usage: weights, arranged: true }, &mut PRNG::new_with_seeds(student_seed, student_seed.rotate_left(32)))
+        };
+        let mut generator = Self::generate_seeded(&pools, students, options, make_qset)?;
+        generator.origin = qbank.clone();
+        generator.seed = Some(seed);
+        Ok(generator)
+    }
+
+    // pub fn new_with_source(qbank: &QBank, blueprint: &Blueprint, students: &Students, options: &GenerationOptions, source: &mut dyn RandomSource) -> Result<Self, Error>
+    /// Creates a new `Generator` instance as `new_with_options()` does,
+    /// drawing every random number from the given `source` instead of
+    /// generators seeded from a master seed.
+    ///
+    /// Since there is no master seed, `get_seed()` of the returned `Generator`
+    /// gives `None`, and the examination cannot be regenerated from its `Exam`
+    /// unless `source` gives the same numbers again.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` containing the original questions.
+    /// * `blueprint` - A reference to the `Blueprint` that every student's set is assembled from.
+    /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
+    /// * `options` - A reference to the `GenerationOptions` of the generation.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
+    /// the generation fails for the same reasons as `new_with_options()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ Blueprint, GenerationOptions, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=3).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// let blueprint = Blueprint::new_with_range(1, 10, 4);
+    /// let options = GenerationOptions::new_empty();
+    ///
+    /// let generator1 = Generator::new_with_source(&qbank, &blueprint, &students, &options, &mut Random::new_with_seeds(7, 7)).unwrap();
+    /// let generator2 = Generator::new_with_source(&qbank, &blueprint, &students, &options, &mut Random::new_with_seeds(7, 7)).unwrap();
+    /// assert_eq!(generator1.get_seed(), None);
+    /// for (qset1, qset2) in generator1.get_shuffled_qsets().iter().zip(generator2.get_shuffled_qsets())
+    ///     { assert_eq!(qset1.count_same_position(qset2), 4); }
+    /// ```
+    pub fn new_with_source(qbank: &QBank, blueprint: &Blueprint, students: &Students, options: &GenerationOptions, source: &mut dyn RandomSource) -> Result<Self, Error>
+    {
+        let pools = [QuestionPool::new(qbank, blueprint)?];
+        let mut make_qset = |student: &Student, weights: &HashMap<(u16, u16), usize>, _attempt: u64|
+            ShuffledQSet::new_with_pools_source(&pools, student, &SetOptions { usage: weights, arranged: true }, source);
+        let mut generator = Self::generate(&pools, students, options, &mut make_qset)?;
+        generator.origin = qbank.clone();
+        Ok(generator)
+    }
+
+    // fn map_in_parallel<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+    /// Applies `f` to every item of `items` on as many threads as the machine runs
+    /// at once, and returns the results in the order of `items`.
+    /// A panic in any thread is resumed in the calling thread.
+    fn map_in_parallel<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+    {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get()).min(items.len());
+        if threads <= 1
+            { return items.iter().map(f).collect(); }
+        let f = &f;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = items.chunks(items.len().div_ceil(threads))
+                .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+                .collect();
+            handles.into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        })
+    }
+
+    // fn depends_on_others(pools: &[QuestionPool], options: &GenerationOptions) -> bool
+    /// Checks whether a student's set depends on the sets made before it or on the
+    /// earlier examinations, which it does with an overlap limit, a seating or a history.
+    #[inline]
+    fn depends_on_others(pools: &[QuestionPool], options: &GenerationOptions) -> bool
+    {
+        pools.iter().any(|pool| pool.get_blueprint().get_max_overlap().is_some())
+            || !options.get_seating().get_pairs().is_empty() || !options.get_history().get_exams().is_empty()
+    }
+
+    // fn generate_seeded(pools: &[QuestionPool], students: &Students, options: &GenerationOptions, mut make_qset: impl Fn(&Student, &HashMap<(u16, u16), usize>, u64) -> Result<ShuffledQSet, GenerationError> + Sync) -> Result<Self, Error>
+    /// Does the work of `new_with_options()` and `new_with_banks()`, leaving the question banks and the seed to them.
+    ///
+    /// If there are no versions and no student's set depends on the others, every set depends
+    /// only on the seed and the student, so the sets are made in parallel by `make_qset`.
+    /// Otherwise they are made one by one by `generate()`.
+    fn generate_seeded(pools: &[QuestionPool], students: &Students, options: &GenerationOptions, mut make_qset: impl Fn(&Student, &HashMap<(u16, u16), usize>, u64) -> Result<ShuffledQSet, GenerationError> + Sync) -> Result<Self, Error>
+    {
+        if options.get_forms().is_some() || Self::depends_on_others(pools, options)
+            { return Self::generate(pools, students, options, &mut make_qset); }
+        let mut generator = Self::new_empty();
+        generator.shuffled_qsets = Self::map_in_parallel(students, |student| make_qset(student, &HashMap::new(), 0))
+            .into_iter()
+            .collect::<Result<ShuffledQSets, GenerationError>>()?;
+        Ok(generator)
+    }
+
+    // fn generate(pools: &[QuestionPool], students: &Students, options: &GenerationOptions, make_qset: &mut QSetMaker) -> Result<Self, Error>
+    /// Does the work of `new_with_options()`, `new_with_source()` and `new_with_banks()`,
+    /// drawing from the pool of each question bank and leaving the question banks and the seed to them.
+    ///
+    /// `make_qset` makes a candidate set for a student, preferring the questions
+    /// of less weight, in the given attempt from zero on.
+    fn generate(pools: &[QuestionPool], students: &Students, options: &GenerationOptions, make_qset: &mut QSetMaker) -> Result<Self, Error>
+    {
+        let mut generator = Self::new_empty();
+        let history = options.get_history();
+        let Some(forms) = options.get_forms()
+        else
+        {
+            let avoidance = Avoidance { history, sittings: options.get_sittings(), max_repeats: options.get_max_repeats(), seen: true };
+            generator.shuffled_qsets = Self::generate_sets(pools, students, options.get_seating(), &avoidance, make_qset)?;
+            return Ok(generator);
+        };
+        if forms == 0
+            { return Err(Error::Generation(GenerationError::NoForms)); }
+
+        // Every version is the neighbour of every other so that they are kept apart.
+        // The versions avoid the recent sittings, and each student is given a version below.
+        let form_students: Students = (0..forms).map(Self::get_form_student).collect();
+        let mut form_seating = Seating::new_empty();
+        for i in 0..forms
+        {
+            for j in (i + 1)..forms
+                { form_seating.push_pair(form_students[i].get_id().clone(), form_students[j].get_id().clone()); }
+        }
+        let avoidance = Avoidance { history, sittings: options.get_sittings(), max_repeats: None, seen: false };
+        let form_qsets = Self::generate_sets(pools, &form_students, &form_seating, &avoidance, make_qset)?;
+
+        let mut counts = vec![0_usize; forms];
+        let mut assigned_forms = Vec::new();
         let mut shuffled_qsets = ShuffledQSets::new();
-        for i in 0..students.len()
+        let mut placed: HashMap<&String, Vec<usize>> = HashMap::new();   // ID -> forms of the students assigned so far
+        for student in students
         {
-            let mut shuffled_qset = ShuffledQSet::new(qbank, start, end, selected, &students[i])?;
-            shuffled_qset.shuffle();
+            let mut conflicts = vec![0_usize; forms];
+            for neighbour in options.get_seating().get_neighbours(student.get_id()).into_iter().flatten()
+            {
+                for &form in placed.get(neighbour).into_iter().flatten()
+                    { conflicts[form] += 1; }
+            }
+            // Anchor questions are given to every student and are not counted.
+            let seen = history.get_seen_questions(student.get_id());
+            let repeats: Vec<usize> = form_qsets.iter()
+                .map(|form_qset| form_qset.get_shuffled_questions().iter()
+                    .filter(|shuffled_question| seen.contains(&(shuffled_question.get_bank(), shuffled_question.get_question())) && !Self::is_anchor(pools, shuffled_question))
+                    .count())
+                .collect();
+            let form = (0..forms).filter(|&form| options.get_max_repeats().is_none_or(|max_repeats| repeats[form] <= max_repeats))
+                .min_by_key(|&form| (conflicts[form], repeats[form], counts[form]));
+            let Some(form) = form
+            else
+            {
+                let repeats = repeats.iter().copied().min().unwrap_or(0);
+                let max_repeats = options.get_max_repeats().unwrap_or(0);
+                return Err(Error::Generation(GenerationError::TooManyRepeats { student: student.get_id().clone(), repeats, max_repeats }));
+            };
+            counts[form] += 1;
+            assigned_forms.push(form);
+            placed.entry(student.get_id()).or_default().push(form);
+            let mut shuffled_qset = form_qsets[form].clone();
+            shuffled_qset.set_student(student);
             shuffled_qsets.push(shuffled_qset);
         }
-        Some(
-            Self
+        generator.shuffled_qsets = shuffled_qsets;
+        generator.forms = form_qsets;
+        generator.assigned_forms = assigned_forms;
+        Ok(generator)
+    }
+
+    // fn generate_sets(pools: &[QuestionPool], students: &Students, seating: &Seating, avoidance: &Avoidance, make_qset: &mut QSetMaker) -> Result<ShuffledQSets, Error>
+    /// Makes the sets of `students` one by one for `generate()`, keeping them apart
+    /// from those of their neighbours in `seating` and from the questions of `avoidance`.
+    /// The overlap limit of the blueprint of each pool counts only the questions of its question bank.
+    ///
+    /// `make_qset` makes a candidate set for a student, preferring the questions
+    /// of less weight, in the given attempt from zero on.
+    fn generate_sets(pools: &[QuestionPool], students: &Students, seating: &Seating, avoidance: &Avoidance, make_qset: &mut QSetMaker) -> Result<ShuffledQSets, Error>
+    {
+        let history = avoidance.history;
+        let mut shuffled_qsets = ShuffledQSets::new();
+        let max_overlaps: Vec<Option<usize>> = pools.iter().map(|pool| pool.get_blueprint().get_max_overlap()).collect();
+        let limited = max_overlaps.iter().any(Option::is_some);
+        if !limited && seating.get_pairs().is_empty() && history.get_exams().is_empty()
+        {
+            for student in students
+                { shuffled_qsets.push(make_qset(student, &HashMap::new(), 0)?); }
+        }
+        else
+        {
+            for (bank, pool) in pools.iter().enumerate()
+            {
+                let lower_bound = pool.get_blueprint().get_overlap_lower_bound(pool.get_qbank(), students.len());
+                let Some(max_overlap) = max_overlaps[bank].filter(|&max_overlap| max_overlap < lower_bound)
+                    else { continue; };
+                let error = GenerationError::OverlapUnreachable { max_overlap, lower_bound, students: students.len() };
+                // A set of one question bank reports the error as it is.
+                return Err(Error::Generation(if pools.len() == 1 { error } else { GenerationError::InBank { bank, error: Box::new(error) } }));
+            }
+            let exceeds_overlap = |candidate: &ShuffledQSet, earlier: &ShuffledQSet| max_overlaps.iter().enumerate()
+                .any(|(bank, max_overlap)| max_overlap.is_some_and(|max_overlap| earlier.count_overlap_in_bank(candidate, bank as u16) > max_overlap));
+            let order_key = |shuffled_question: &ShuffledQuestion| {
+                let pool = &pools[shuffled_question.get_bank() as usize];
+                (shuffled_question.get_bank(), pool.get_blueprint().get_order_key(pool.get_qbank(), shuffled_question))
+            };
+            let mut usage: HashMap<(u16, u16), usize> = HashMap::new();
+            let mut placed: HashMap<String, Vec<usize>> = HashMap::new();  // ID -> indices of the sets made so far
+            let recent_usage = history.get_recent_usage(avoidance.sittings);
+            for student in students
+            {
+                let mut neighbour_indices: Vec<usize> = seating.get_neighbours(student.get_id()).into_iter().flatten()
+                    .filter_map(|id| placed.get(id)).flatten().copied().collect();
+                neighbour_indices.sort_unstable();
+                let neighbours: Vec<&ShuffledQSet> = neighbour_indices.iter().map(|&i| &shuffled_qsets[i]).collect();
+                // The questions of a neighbour weigh more than those of all the other students together.
+                let mut weights = if limited { usage.clone() } else { HashMap::new() };
+                for neighbour in &neighbours
+                {
+                    for shuffled_question in neighbour.get_shuffled_questions()
+                        { *weights.entry((shuffled_question.get_bank(), shuffled_question.get_question())).or_insert(0) += students.len(); }
+                }
+                for (&question, &sittings_used) in &recent_usage
+                    { *weights.entry(question).or_insert(0) += sittings_used * students.len(); }
+                // The questions that the student has seen weigh more than all the others.
+                let heaviest = weights.values().copied().max().unwrap_or(0) + 1;
+                let seen = if avoidance.seen { history.get_seen_questions(student.get_id()) } else { HashSet::new() };
+                for &question in &seen
+                    { *weights.entry(question).or_insert(0) += heaviest; }
+
+                let mut accepted = None;
+                let mut fewest_repeats = None;
+                for attempt in 0..Self::PLACEMENT_ATTEMPTS
+                {
+                    let mut candidate = make_qset(student, &weights, attempt)?;
+                    if !candidate.arrange_apart(&neighbours, order_key)
+                        || shuffled_qsets.iter().any(|earlier| exceeds_overlap(&candidate, earlier))
+                        { continue; }
+                    // Anchor questions are given to every student and are not counted.
+                    let repeats = candidate.get_shuffled_questions().iter()
+                        .filter(|shuffled_question| seen.contains(&(shuffled_question.get_bank(), shuffled_question.get_question())) && !Self::is_anchor(pools, shuffled_question))
+                        .count();
+                    if avoidance.max_repeats.is_some_and(|max_repeats| repeats > max_repeats)
+                    {
+                        fewest_repeats = Some(fewest_repeats.map_or(repeats, |fewest: usize| fewest.min(repeats)));
+                        continue;
+                    }
+                    accepted = Some(candidate);
+                    break;
+                }
+                let shuffled_qset = match (accepted, fewest_repeats, avoidance.max_repeats)
+                {
+                    (Some(shuffled_qset), _, _) => shuffled_qset,
+                    (None, Some(repeats), Some(max_repeats)) =>
+                        return Err(Error::Generation(GenerationError::TooManyRepeats { student: student.get_id().clone(), repeats, max_repeats })),
+                    _ => return Err(Error::Generation(GenerationError::PlacementFailed { student: student.get_id().clone(), attempts: Self::PLACEMENT_ATTEMPTS })),
+                };
+                for shuffled_question in shuffled_qset.get_shuffled_questions()
+                    { *usage.entry((shuffled_question.get_bank(), shuffled_question.get_question())).or_insert(0) += 1; }
+                placed.entry(student.get_id().clone()).or_default().push(shuffled_qsets.len());
+                shuffled_qsets.push(shuffled_qset);
+            }
+        }
+        Ok(shuffled_qsets)
+    }
+
+    // fn is_anchor(pools: &[QuestionPool], shuffled_question: &ShuffledQuestion) -> bool
+    /// Checks whether `shuffled_question` is an anchor question of the blueprint of its question bank.
+    #[inline]
+    fn is_anchor(pools: &[QuestionPool], shuffled_question: &ShuffledQuestion) -> bool
+    {
+        pools.get(shuffled_question.get_bank() as usize)
+            .is_some_and(|pool| pool.get_blueprint().get_anchors().contains(&shuffled_question.get_question()))
+    }
+
+    // pub fn new_with_banks(qbanks: &[QBank], blueprints: &[Blueprint], header: usize, students: &Students, options: &GenerationOptions, seed: u64) -> Result<Self, Error>
+    /// Creates a new `Generator` instance reproducibly from a master seed, drawing
+    /// every student's set from several question banks, each with its own blueprint,
+    /// with the seating, the earlier examinations to avoid, the limit of questions
+    /// seen before and the versions of the paper that `options` gives.
+    ///
+    /// Each student's set is made as `ShuffledQSet::new_with_banks()` makes it, and the
+    /// options work as `new_with_options()` explains, so the sets are generated in parallel
+    /// only without any option and without an overlap limit in any blueprint. The overlap limit of each blueprint
+    /// counts only the questions of its question bank, and the questions of the history
+    /// are told apart by their question banks. The title, the notice and the other
+    /// fields of the papers come from the header of the question bank of index `header`.
+    /// The categories of all the question banks are put together, those of
+    /// the same name being one category, so each question keeps its category's name.
+    ///
+    /// # Arguments
+    /// * `qbanks` - The `QBank`s containing the original questions.
+    /// * `blueprints` - The `Blueprint` for each question bank of `qbanks`, in the same order.
+    /// * `header` - The 0-based index of the question bank whose header the papers have.
+    /// * `students` - A slice of `Student` instances for whom shuffled sets will be generated.
+    /// * `options` - A reference to the `GenerationOptions` of the generation.
+    /// * `seed` - The master seed of the examination.
+    ///
+    /// # Output
+    /// `Result<Self, Error>` - A new `Generator` instance, or an `Error::Generation` if
+    /// `header` is not the index of a question bank, if the question banks have more
+    /// than 255 categories together, if a question is in a category which the header
+    /// of its question bank does not name, if a student's set cannot be drawn for the
+    /// reasons that `ShuffledQSet::new_with_banks()` explains, or if the options cannot
+    /// be met for the reasons that `new_with_options()` explains. An overlap limit which
+    /// cannot be kept is wrapped in `GenerationError::InBank`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Error, GenerationError, GenerationOptions, Generator, Header, History, QBank, Question, Seating, Student, Students };
+    ///
+    /// let mut networks = QBank::new_with_header(Header::new("Final Exam".to_string(), "Name".to_string(), "ID".to_string(), vec!["Networks".to_string()], String::new()));
+    /// let mut security = QBank::new_with_header(Header::new("Security".to_string(), "Name".to_string(), "ID".to_string(), vec!["Security".to_string()], String::new()));
+    /// for id in 1..=10
+    /// {
+    ///     networks.push_question(Question::new(id, id, 1, format!("Network Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    ///     security.push_question(Question::new(id, id, 1, format!("Security Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    /// }
+    /// let qbanks = vec![networks, security];
+    /// let blueprints = vec![Blueprint::new_with_range(1, 10, 3), Blueprint::new_with_range(1, 10, 2)];
+    /// let students: Students = (1..=3).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// let options = GenerationOptions::new_empty();
+    ///
+    /// let mut generator = Generator::new_with_banks(&qbanks, &blueprints, 0, &students, &options, 2026).unwrap();
+    /// assert_eq!(generator.get_exam(String::new(), String::new()).get_title(), "Final Exam");
+    /// let (_, paper) = generator.get_shuffled_qbank(0).unwrap();
+    /// assert_eq!(paper.get_questions().iter().filter(|q| q.get_question().starts_with("Security")).count(), 2);
+    /// assert_eq!(paper.get_header().get_categories(), &vec!["Networks".to_string(), "Security".to_string()]);
+    /// while let Some((_, category, question, _)) = generator.next()
+    ///     { assert_eq!(question.starts_with("Security"), category == "Security"); }
+    ///
+    /// // Neighbours share no question of either bank at the same position, and the
+    /// // security questions of the midterm are avoided.
+    /// let midterm = Generator::new_with_banks(&qbanks, &[Blueprint::new_with_range(1, 3, 3), Blueprint::new_with_range(1, 3, 3)], 0, &students, &options, 1).unwrap();
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_seating(Seating::new_with_grid(&[(1..=3).map(|i| i.to_string()).collect()]));
+    /// options.set_history(History::new_with_exams(vec![midterm.get_exam(String::new(), String::new())]));
+    /// options.set_sittings(1);
+    /// let generator = Generator::new_with_banks(&qbanks, &blueprints, 0, &students, &options, 2026).unwrap();
+    /// let qsets = generator.get_shuffled_qsets();
+    /// for qset in qsets
+    ///     { assert!(qset.get_shuffled_questions().iter().all(|q| q.get_question() > 3)); }
+    /// assert_eq!(qsets[0].count_same_position(&qsets[1]), 0);
+    ///
+    /// let error = Generator::new_with_banks(&qbanks, &blueprints, 2, &students, &options, 2026).unwrap_err();
+    /// assert!(matches!(error, Error::Generation(GenerationError::MissingBank { bank: 2 })));
+    ///
+    /// // 200 categories in each question bank, all of them different
+    /// let many: Vec<QBank> = (0..2).map(|bank| QBank::new_with_header(Header::new(String::new(), String::new(), String::new(),
+    ///             (1..=200).map(|c| format!("Category {}-{}", bank, c)).collect(), String::new()))).collect();
+    /// let error = Generator::new_with_banks(&many, &blueprints, 0, &students, &options, 2026).unwrap_err();
+    /// assert!(matches!(error, Error::Generation(GenerationError::TooManyCategories { categories: 400 })));
+    ///
+    /// // Category 2 of the security questions has no name.
+    /// let mut unnamed = qbanks.clone();
+    /// unnamed[1].push_question(Question::new(11, 11, 2, "Security Q11".to_string(), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    /// let error = Generator::new_with_banks(&unnamed, &blueprints, 0, &students, &options, 2026).unwrap_err();
+    /// assert!(matches!(error, Error::Generation(GenerationError::UnnamedCategory { bank: 1, id: 11, category: 2 })));
+    /// ```
+    pub fn new_with_banks(qbanks: &[QBank], blueprints: &[Blueprint], header: usize, students: &Students, options: &GenerationOptions, seed: u64) -> Result<Self, Error>
+    {
+        let (origin, banks) = Self::merge_banks(qbanks, header)?;
+        // The questions of each bank are grouped once for all the students and all the attempts.
+        let pools = QuestionPool::new_with_banks(qbanks, blueprints)?;
+        let make_qset = |student: &Student, weights: &HashMap<(u16, u16), usize>, attempt: u64| {
+            let student_seed = ShuffledQSet::derive_seed(seed.wrapping_add(attempt), student);
+            ShuffledQSet::new_with_pools_source(&pools, student, &SetOptions { usage: weights, arranged: true }, &mut PRNG::new_with_seeds(student_seed, student_seed.rotate_left(32)))
+        };
+        let mut generator = Self::generate_seeded(&pools, students, options, make_qset)?;
+        (generator.origin, generator.banks) = (origin, banks);
+        generator.seed = Some(seed);
+        Ok(generator)
+    }
+
+    // fn merge_banks(qbanks: &[QBank], header: usize) -> Result<(QBank, Vec<QBank>), GenerationError>
+    /// Puts the categories of all the question banks together, those of the header's
+    /// question bank first, and renumbers the categories of the questions accordingly.
+    /// Returns the question bank of index `header` with the merged categories in its header,
+    /// and all the question banks so renumbered, or an error if there is no such question bank,
+    /// if there are more categories than a `u8` can number, or if a question is in a category without a name.
+    fn merge_banks(qbanks: &[QBank], header: usize) -> Result<(QBank, Vec<QBank>), GenerationError>
+    {
+        let mut merged_header = qbanks.get(header).ok_or(GenerationError::MissingBank { bank: header })?.get_header().clone();
+        let mut categories = merged_header.get_categories().clone();
+        for qbank in qbanks
+        {
+            for category in qbank.get_header().get_categories()
+            {
+                if !categories.contains(category)
+                    { categories.push(category.clone()); }
+            }
+        }
+        if categories.len() > u8::MAX as usize
+            { return Err(GenerationError::TooManyCategories { categories: categories.len() }); }
+        merged_header.set_categories(categories.clone());
+
+        let mut banks = Vec::new();
+        for (index, qbank) in qbanks.iter().enumerate()
+        {
+            let mut bank = QBank::new_with_header(merged_header.clone());
+            bank.set_constraints(qbank.get_constraints().clone());
+            for question in qbank.get_questions()
             {
-                origin: qbank.clone(),
-                shuffled_qsets,
-                current_question_number: 0,
-                title_font_size: 14.0,
-                body_font_size: 11.0,
-                answer_sheet_font_size: 12.0,
-                footer_font_size: 9.0,
-                attributes: Self::TITLE_BOLD,
-                margin_left_in_mm: 10.0,
-                margin_right_in_mm: 10.0,
-                margin_top_in_mm: 10.0,
-                margin_buttom_in_mm: 10.0,
-                line_spacing: 1.0,
-                answer_sheet_title: "Answer Sheet        정답지        Ответы".to_string()
-             }
-        )
+                let mut question = question.clone();
+                let position = qbank.get_header().get_category(question.get_category())
+                    .and_then(|name| categories.iter().position(|c| c == name))
+                    .ok_or(GenerationError::UnnamedCategory { bank: index, id: question.get_id(), category: question.get_category() })?;
+                question.set_category((position + 1) as u8);
+                bank.push_question(question);
+            }
+            banks.push(bank);
+        }
+        Ok((banks[header].clone(), banks))
     }
 
     // pub fn new_empty() -> Self
@@ -147,7 +809,11 @@ impl Generator
         Self
         {
             origin: QBank::new_empty(),
+            banks: Vec::new(),
             shuffled_qsets: ShuffledQSets::new(),
+            forms: ShuffledQSets::new(),
+            assigned_forms: Vec::new(),
+            seed: None,
             current_question_number: 0,
             title_font_size: 14.0,
             body_font_size: 11.0,
@@ -163,7 +829,7 @@ impl Generator
         }
     }
 
-    // pub fn new_one_set(qbank: &QBank, start: u16, end: u16, selected: usize) -> Option<Self>
+    // pub fn new_one_set(qbank: &QBank, start: u16, end: u16, selected: usize) -> Result<Self, Error>
     /// Creates a new `Generator` instance for a single shuffled set.
     ///
     /// This function generates a single shuffled question set based on the provided
@@ -171,12 +837,12 @@ impl Generator
     ///
     /// # Arguments
     /// * `qbank` - A reference to the `QBank` containing the original questions.
-    /// * `start` - The starting number of the questions to include (inclusive).
-    /// * `end` - The ending number of the questions to include (inclusive).
+    /// * `start` - The smallest question ID to include (inclusive).
+    /// * `end` - The largest question ID to include (inclusive).
     /// * `selected` - The number of questions to be randomly selected.
     ///
     /// # Output
-    /// An `Option<Self>` which is `Some(Generator)` if successful, or `None` if
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or an `Error::Generation` if
     /// the generation fails (e.g., invalid question range).
     ///
     /// # Examples
@@ -188,15 +854,557 @@ impl Generator
     /// qbank.add_question("Question 2".to_string(), "Answer 2".to_string());
     ///
     /// let generator = Generator::new_one_set(&qbank, 1, 2, 2);
-    /// assert!(generator.is_some());
+    /// assert!(generator.is_ok());
     /// ```
-    pub fn new_one_set(qbank: &QBank, start: u16, end: u16, selected: usize) -> Option<Self>
+    pub fn new_one_set(qbank: &QBank, start: u16, end: u16, selected: usize) -> Result<Self, Error>
     {
         let student = Student::new_empty();
         let students = vec![student];
         Self::new(qbank, start, end, selected, &students)
     }
 
+    // pub fn new_with_exam(qbank: &QBank, exam: &Exam) -> Result<Self, Error>
+    /// Creates a new `Generator` instance from an `Exam` saved earlier.
+    ///
+    /// No question set is generated. The shuffled question sets, the versions of
+    /// the paper that the students were given and the master seed are taken from
+    /// `exam` as they are, so that a lost paper can be reprinted and the answer keys
+    /// can be rebuilt exactly as they were. A version which no student was given
+    /// cannot be restored, so it has no paper.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` that `exam` was generated from.
+    /// * `exam` - A reference to the `Exam`, usually read from an `ESDB`.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, or
+    /// `Err(Error::Validation)` naming the student and the question if `exam` refers
+    /// to a question which `qbank` does not have, if a choice order does not
+    /// arrange the choices which the question it refers to shows, if the values
+    /// of a templated question do not fit its parameters, or if students given
+    /// the same version of the paper have different sets.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Error, GenerationOptions, QBank, Generator, Question, Student, Students, SQLiteDB, ESDB };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false), ("D".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string()),
+    ///                               Student::new("Bob".to_string(), "2".to_string())];
+    /// let generator = Generator::new(&qbank, 1, 10, 5, &students).unwrap();
+    ///
+    /// let path = std::env::temp_dir().join("qrate_test_new_with_exam");
+    /// let mut db = SQLiteDB::open(path.to_string_lossy().to_string()).unwrap();
+    /// db.write_exam(&generator.get_exam("2026-10-16".to_string(), "./Test.qbdb".to_string())).unwrap();
+    ///
+    /// let reprinted = Generator::new_with_exam(&qbank, &db.read_exam().unwrap()).unwrap();
+    /// assert_eq!(reprinted.get_seed(), generator.get_seed());
+    /// assert!(reprinted.get_forms().is_empty());
+    /// for ((s1, qbank1), (s2, qbank2)) in generator.get_shuffled_qbanks().unwrap().iter().zip(reprinted.get_shuffled_qbanks().unwrap().iter())
+    /// {
+    ///     assert_eq!(s1.get_id(), s2.get_id());
+    ///     for (q1, q2) in qbank1.get_questions().iter().zip(qbank2.get_questions())
+    ///     {
+    ///         assert_eq!(q1.get_id(), q2.get_id());
+    ///         assert_eq!(q1.get_choices(), q2.get_choices());
+    ///     }
+    /// }
+    ///
+    /// // The other question bank has none of the questions given.
+    /// let mut other = QBank::new_with_default();
+    /// other.push_question(Question::new(11, 11, 1, "Question 11".to_string(), vec![("A".to_string(), true)]));
+    /// let error = Generator::new_with_exam(&other, &generator.get_exam(String::new(), String::new())).unwrap_err();
+    /// assert!(matches!(error, Error::Validation(_)));
+    /// assert!(error.to_string().ends_with("is not in the question bank"));
+    ///
+    /// // The versions that the students were given are saved and restored.
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(2));
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 10, 5), &students, &options, 7).unwrap();
+    /// db.write_exam(&generator.get_exam("2026-12-18".to_string(), "./Test.qbdb".to_string())).unwrap();
+    /// let reprinted = Generator::new_with_exam(&qbank, &db.read_exam().unwrap()).unwrap();
+    /// assert_eq!(reprinted.get_forms().len(), 2);
+    /// assert_eq!(reprinted.get_assigned_form(1), Some(1));
+    /// assert_eq!(reprinted.get_form_students(1)[0].get_name(), "Bob");
+    ///
+    /// // Students given the same version must have the same set.
+    /// let mut exam = generator.get_exam(String::new(), String::new());
+    /// exam.set_assigned_forms(vec![0, 0]);
+    /// assert!(matches!(Generator::new_with_exam(&qbank, &exam), Err(Error::Validation(_))));
+    /// std::fs::remove_file(db.get_path()).unwrap(); // Clean up
+    /// ```
+    pub fn new_with_exam(qbank: &QBank, exam: &Exam) -> Result<Self, Error>
+    {
+        let mut generator = Self::new_empty();
+        generator.origin = qbank.clone();
+        generator.shuffled_qsets = exam.get_shuffled_qsets().clone();
+        generator.seed = exam.get_seed();
+        generator.check_exam()?;
+        generator.restore_forms(exam.get_assigned_forms())?;
+        Ok(generator)
+    }
+
+    // pub fn new_with_exam_from_banks(qbanks: &[QBank], sources: &[String], header: usize, exam: &Exam) -> Result<Self, Error>
+    /// Creates a new `Generator` instance from an `Exam` drawn from several question banks
+    /// and saved earlier, as `new_with_exam()` does for an `Exam` drawn from one.
+    ///
+    /// If `exam` records the sources of its question banks, as `Exam::set_bank_sources()`
+    /// sets them, `sources` must be the same sources in the same order, so that a question
+    /// bank given in another place or a different question bank is found out. An `Exam`
+    /// which records no sources, e.g. one saved by earlier versions, is not checked so.
+    ///
+    /// # Arguments
+    /// * `qbanks` - The `QBank`s that `exam` was generated from, in the same order.
+    /// * `sources` - The source of each question bank of `qbanks`, e.g. its path, in the same order.
+    /// * `header` - The 0-based index of the question bank whose header the papers have.
+    /// * `exam` - A reference to the `Exam`, usually read from an `ESDB`.
+    ///
+    /// # Output
+    /// A `Result<Self, Error>` which is `Ok(Generator)` if successful, `Err(Error::Generation)`
+    /// if the question banks cannot be put together as `new_with_banks()` explains,
+    /// or `Err(Error::Validation)` if the numbers of question banks and sources differ,
+    /// if `sources` differ from those that `exam` records, or if `exam` does not match
+    /// the question banks for the same reasons as `new_with_exam()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Error, Excel, ESDB, GenerationError, GenerationOptions, Generator, QBank, Question, Student, Students };
+    ///
+    /// let mut qbanks = vec![QBank::new_with_default(), QBank::new_with_default()];
+    /// for id in 1..=6
+    /// {
+    ///     qbanks[0].push_question(Question::new(id, id, 1, format!("First Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)]));
+    ///     qbanks[1].push_question(Question::new(id, id, 1, format!("Second Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    /// }
+    /// let sources = vec!["./First.qbdb".to_string(), "./Second.qbdb".to_string()];
+    /// let blueprints = vec![Blueprint::new_with_range(1, 6, 2), Blueprint::new_with_range(1, 6, 2)];
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string())];
+    /// let generator = Generator::new_with_banks(&qbanks, &blueprints, 0, &students, &GenerationOptions::new_empty(), 7).unwrap();
+    ///
+    /// let mut exam = generator.get_exam("2026-10-16".to_string(), sources[0].clone());
+    /// exam.set_bank_sources(sources.clone());
+    /// let path = std::env::temp_dir().join("qrate_test_new_with_exam_from_banks");
+    /// let mut excel = Excel::open(path.to_string_lossy().to_string()).unwrap();
+    /// excel.write_exam(&exam).unwrap();
+    /// let exam = excel.read_exam().unwrap();
+    /// assert_eq!(exam.get_bank_sources(), &sources);
+    ///
+    /// let reprinted = Generator::new_with_exam_from_banks(&qbanks, &sources, 0, &exam).unwrap();
+    /// let (_, paper1) = generator.get_shuffled_qbank(0).unwrap();
+    /// let (_, paper2) = reprinted.get_shuffled_qbank(0).unwrap();
+    /// for (q1, q2) in paper1.get_questions().iter().zip(paper2.get_questions())
+    /// {
+    ///     assert_eq!(q1.get_question(), q2.get_question());
+    ///     assert_eq!(q1.get_choices(), q2.get_choices());
+    /// }
+    /// // The question banks given in the other order are found out.
+    /// let swapped = vec![qbanks[1].clone(), qbanks[0].clone()];
+    /// let swapped_sources = vec![sources[1].clone(), sources[0].clone()];
+    /// assert!(matches!(Generator::new_with_exam_from_banks(&swapped, &swapped_sources, 0, &exam), Err(Error::Validation(_))));
+    /// // The questions of the second bank cannot be found in the first one alone.
+    /// let mut unchecked = exam.clone();
+    /// unchecked.set_bank_sources(Vec::new());
+    /// assert!(matches!(Generator::new_with_exam_from_banks(&qbanks[..1], &sources[..1], 0, &unchecked), Err(Error::Validation(_))));
+    /// let error = Generator::new_with_exam_from_banks(&qbanks, &sources, 2, &exam).unwrap_err();
+    /// assert!(matches!(error, Error::Generation(GenerationError::MissingBank { bank: 2 })));
+    /// std::fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    pub fn new_with_exam_from_banks(qbanks: &[QBank], sources: &[String], header: usize, exam: &Exam) -> Result<Self, Error>
+    {
+        if qbanks.len() != sources.len()
+            { return Err(Error::Validation(format!("{} question banks are given with {} sources", qbanks.len(), sources.len()))); }
+        let bank_sources = exam.get_bank_sources();
+        if !bank_sources.is_empty() && bank_sources.as_slice() != sources
+            { return Err(Error::Validation(format!("the exam was drawn from the question banks \"{}\", not from \"{}\"", bank_sources.join("\", \""), sources.join("\", \"")))); }
+        let mut generator = Self::new_empty();
+        (generator.origin, generator.banks) = Self::merge_banks(qbanks, header)?;
+        generator.shuffled_qsets = exam.get_shuffled_qsets().clone();
+        generator.seed = exam.get_seed();
+        generator.check_exam()?;
+        generator.restore_forms(exam.get_assigned_forms())?;
+        Ok(generator)
+    }
+
+    // fn restore_forms(&mut self, assigned_forms: &[usize]) -> Result<(), Error>
+    /// Restores the versions of the paper from the version that each student was given,
+    /// taking each version's questions from the first student given it.
+    /// A version which no student was given is left without questions, so it has no paper.
+    /// Students given the same version with different questions or choice orders,
+    /// or versions given to only some students, are an `Error::Validation`.
+    fn restore_forms(&mut self, assigned_forms: &[usize]) -> Result<(), Error>
+    {
+        if assigned_forms.is_empty()
+            { return Ok(()); }
+        if assigned_forms.len() != self.shuffled_qsets.len()
+            { return Err(Error::Validation(format!("{} versions are given for {} students", assigned_forms.len(), self.shuffled_qsets.len()))); }
+        let forms = assigned_forms.iter().max().map_or(0, |&form| form + 1);
+        let mut form_qsets: Vec<Option<&ShuffledQSet>> = vec![None; forms];
+        for (shuffled_qset, &form) in self.shuffled_qsets.iter().zip(assigned_forms)
+        {
+            let Some(first) = form_qsets[form]
+            else
+            {
+                form_qsets[form] = Some(shuffled_qset);
+                continue;
+            };
+            let same = first.get_shuffled_questions().len() == shuffled_qset.get_shuffled_questions().len()
+                && first.get_shuffled_questions().iter().zip(shuffled_qset.get_shuffled_questions())
+                    .all(|(theirs, mine)| theirs.is_same_question(mine) && theirs.get_choices() == mine.get_choices() && theirs.get_values() == mine.get_values());
+            if !same
+                { return Err(Error::Validation(format!("student {} is given form {} with other questions than student {}", shuffled_qset.get_student().get_id(), Self::get_form_label(form), first.get_student().get_id()))); }
+        }
+        self.forms = form_qsets.into_iter().enumerate()
+            .map(|(form, shuffled_qset)| {
+                let mut form_qset = shuffled_qset.cloned().unwrap_or_else(ShuffledQSet::new_empty);
+                form_qset.set_student(&Self::get_form_student(form));
+                form_qset
+            })
+            .collect();
+        self.assigned_forms = assigned_forms.to_vec();
+        Ok(())
+    }
+
+    // fn check_exam(&self) -> Result<(), Error>
+    /// Checks that every shuffled question refers to an original question and
+    /// that its choice order is an arrangement of the choices which that question
+    /// shows, including all of its correct answers and pinned choices,
+    /// and that the values of a templated question fit its parameters.
+    /// A shuffled question which breaks any of them is an `Error::Validation`
+    /// that names the student and the question.
+    fn check_exam(&self) -> Result<(), Error>
+    {
+        for shuffled_qset in &self.shuffled_qsets
+        {
+            let student = shuffled_qset.get_student().get_id();
+            for shuffled_question in shuffled_qset.get_shuffled_questions()
+            {
+                let id = shuffled_question.get_question();
+                let Some(question) = self.get_origin_question(shuffled_question)
+                    else { return Err(Error::Validation(format!("question {} given to student {} is not in the question bank{}", id, student,
+                                if self.banks.is_empty() { String::new() } else { format!(" {}", shuffled_question.get_bank()) }))); };
+                let mut choices = shuffled_question.get_choices().clone();
+                choices.sort_unstable();
+                let number_of_choices = question.get_choices().len();
+                let arranged = choices.windows(2).all(|pair| pair[0] < pair[1])
+                    && choices.iter().all(|&c| c >= 1 && c as usize <= number_of_choices)
+                    && choices.len() == question.count_shown_choices()
+                    && (1..=number_of_choices).filter(|&c| question.is_always_shown(c)).all(|c| choices.contains(&(c as u8)));
+                if !arranged
+                    { return Err(Error::Validation(format!("the choice order \"{}\" of question {} given to student {} does not arrange the choices that the question shows", choices_to_text(shuffled_question.get_choices()), id, student))); }
+                if question.is_parameterized() && question.instantiate(shuffled_question.get_values()).is_none()
+                    { return Err(Error::Validation(format!("the values \"{}\" of question {} given to student {} do not fit its parameters", values_to_text(shuffled_question.get_values()), id, student))); }
+            }
+        }
+        Ok(())
+    }
+
+    // fn get_origin_question(&self, shuffled_question: &ShuffledQuestion) -> Option<&Question>
+    /// Finds the original question that `shuffled_question` refers to
+    /// in the question bank that it comes from.
+    fn get_origin_question(&self, shuffled_question: &ShuffledQuestion) -> Option<&Question>
+    {
+        let qbank = if self.banks.is_empty() && shuffled_question.get_bank() == 0
+            { &self.origin }
+        else
+            { self.banks.get(shuffled_question.get_bank() as usize)? };
+        qbank.get_question_by_id(shuffled_question.get_question())
+    }
+
+    // pub fn get_seed(&self) -> Option<u64>
+    /// Retrieves the master seed from which the shuffled sets were generated.
+    ///
+    /// When the `Generator` was created by `new()`, the master seed was chosen
+    /// randomly. Record it in order to regenerate the same sets later with
+    /// `new_with_seed()`.
+    ///
+    /// # Output
+    /// `Option<u64>` - The master seed, or `None` if the `Generator` was created by
+    /// `new_with_source()`, whose sets cannot be regenerated from a seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Question 1".to_string(), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string())];
+    ///
+    /// let generator = Generator::new(&qbank, 1, 1, 1, &students).unwrap();
+    /// let again = Generator::new_with_seed(&qbank, 1, 1, 1, &students, generator.get_seed().unwrap()).unwrap();
+    /// assert_eq!(generator.get_seed(), again.get_seed());
+    /// ```
+    #[inline]
+    pub fn get_seed(&self) -> Option<u64>
+    {
+        self.seed
+    }
+
+    // pub fn get_shuffled_qsets(&self) -> &ShuffledQSets
+    /// Retrieves the shuffled question sets of all students.
+    ///
+    /// # Output
+    /// `&ShuffledQSets` - A reference to the shuffled question sets.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=3
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string())];
+    ///
+    /// let generator = Generator::new(&qbank, 1, 3, 2, &students).unwrap();
+    /// assert_eq!(generator.get_shuffled_qsets().len(), 1);
+    /// assert_eq!(generator.get_shuffled_qsets()[0].get_shuffled_questions().len(), 2);
+    /// ```
+    #[inline]
+    pub fn get_shuffled_qsets(&self) -> &ShuffledQSets
+    {
+        &self.shuffled_qsets
+    }
+
+    // pub fn get_forms(&self) -> &ShuffledQSets
+    /// Retrieves the versions of the paper when the `Generator` was created with the versions that `GenerationOptions::set_forms()` asks for.
+    ///
+    /// # Output
+    /// `&ShuffledQSets` - A reference to the shuffled question sets of the versions,
+    /// which is empty if every student has a paper of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=5).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    ///
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(3));
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 6, 4), &students, &options, 7).unwrap();
+    /// assert_eq!(generator.get_forms().len(), 3);
+    /// assert_eq!(generator.get_forms()[2].get_student().get_id(), "C");
+    ///
+    /// let generator = Generator::new_with_seed(&qbank, 1, 6, 4, &students, 7).unwrap();
+    /// assert!(generator.get_forms().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_forms(&self) -> &ShuffledQSets
+    {
+        &self.forms
+    }
+
+    // pub fn get_assigned_form(&self, idx: usize) -> Option<usize>
+    /// Retrieves the version assigned to a student.
+    ///
+    /// # Arguments
+    /// * `idx` - The zero-based index of the student's shuffled question set.
+    ///
+    /// # Output
+    /// `Option<usize>` - The zero-based index of the version in `get_forms()`,
+    /// or `None` if the index is out of bounds or there are no versions.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=3).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    ///
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(2));
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 6, 4), &students, &options, 7).unwrap();
+    /// assert_eq!(generator.get_assigned_form(2), Some(0));
+    /// assert_eq!(generator.get_assigned_form(3), None);
+    /// ```
+    #[inline]
+    pub fn get_assigned_form(&self, idx: usize) -> Option<usize>
+    {
+        self.assigned_forms.get(idx).copied()
+    }
+
+    // pub fn get_form_students(&self, form: usize) -> Students
+    /// Retrieves the students assigned to a version.
+    ///
+    /// # Arguments
+    /// * `form` - The zero-based index of the version.
+    ///
+    /// # Output
+    /// `Students` - The students assigned to the version, in their original order.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=5).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    ///
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(2));
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 6, 4), &students, &options, 7).unwrap();
+    /// assert_eq!(generator.get_form_students(0).len(), 3);
+    /// assert_eq!(generator.get_form_students(1)[1].get_id(), "4");
+    /// ```
+    pub fn get_form_students(&self, form: usize) -> Students
+    {
+        self.shuffled_qsets.iter().zip(self.assigned_forms.iter())
+            .filter(|&(_, &assigned)| assigned == form)
+            .map(|(shuffled_qset, _)| shuffled_qset.get_student().clone())
+            .collect()
+    }
+
+    // fn get_form_student(form: usize) -> Student
+    /// Gives the `Student` that the set of a version is made for, named after its label.
+    #[inline]
+    fn get_form_student(form: usize) -> Student
+    {
+        Student::new(format!("Form {}", Self::get_form_label(form)), Self::get_form_label(form))
+    }
+
+    // pub fn get_form_label(form: usize) -> String
+    /// Gives the label of a version printed on its paper and answer key.
+    ///
+    /// # Arguments
+    /// * `form` - The zero-based index of the version.
+    ///
+    /// # Output
+    /// `String` - "A" to "Z" for the first 26 versions, then "AA", "AB" and so on.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Generator;
+    /// assert_eq!(Generator::get_form_label(0), "A");
+    /// assert_eq!(Generator::get_form_label(3), "D");
+    /// assert_eq!(Generator::get_form_label(26), "AA");
+    /// ```
+    pub fn get_form_label(form: usize) -> String
+    {
+        let mut label = String::new();
+        let mut n = form + 1;
+        while n > 0
+        {
+            n -= 1;
+            label.insert(0, (b'A' + (n % 26) as u8) as char);
+            n /= 26;
+        }
+        label
+    }
+
+    // pub fn get_overlap_matrix(&self) -> Vec<Vec<usize>>
+    /// Computes how many questions every two students have in common.
+    ///
+    /// # Output
+    /// `Vec<Vec<usize>>` - A square matrix whose element at row `i` and column `j`
+    /// is the number of questions that the `i`-th and the `j`-th shuffled question
+    /// sets share. The diagonal holds the number of questions of each set.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Error, GenerationError, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=30
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=5).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    ///
+    /// let mut blueprint = Blueprint::new_with_range(1, 30, 10);
+    /// blueprint.set_max_overlap(Some(4));
+    /// let generator = Generator::new_with_blueprint(&qbank, &blueprint, &students, 2026).unwrap();
+    /// let matrix = generator.get_overlap_matrix();
+    /// for i in 0..5
+    /// {
+    ///     assert_eq!(matrix[i][i], 10);
+    ///     for j in 0..5
+    ///     {
+    ///         assert_eq!(matrix[i][j], matrix[j][i]);
+    ///         if i != j
+    ///             { assert!(matrix[i][j] <= 4); }
+    ///     }
+    /// }
+    ///
+    /// // Five students taking 10 of 30 questions each are bound to share some.
+    /// blueprint.set_max_overlap(Some(1));
+    /// assert!(blueprint.get_overlap_lower_bound(&qbank, 5) > 1);
+    /// let error = Generator::new_with_blueprint(&qbank, &blueprint, &students, 2026).unwrap_err();
+    /// let lower_bound = blueprint.get_overlap_lower_bound(&qbank, 5);
+    /// assert!(matches!(error, Error::Generation(GenerationError::OverlapUnreachable { max_overlap: 1, students: 5, lower_bound: bound }) if bound == lower_bound));
+    /// ```
+    pub fn get_overlap_matrix(&self) -> Vec<Vec<usize>>
+    {
+        self.shuffled_qsets.iter()
+            .map(|mine| self.shuffled_qsets.iter().map(|theirs| mine.count_overlap(theirs)).collect())
+            .collect()
+    }
+
+    // pub fn get_exam(&self, date: String, source: String) -> Exam
+    /// Makes an `Exam` out of the shuffled question sets and the master seed,
+    /// so that it can be written to an `ESDB` and graded or reprinted later.
+    ///
+    /// The title of the `Exam` is the title of the header of the question bank.
+    /// With the versions made by `GenerationOptions::set_forms()`, every student's set
+    /// is saved with the questions and the choice order of their version, together with
+    /// the version they were given, so that a `Generator` made from the `Exam` by
+    /// `new_with_exam()` prints the same versions again.
+    ///
+    /// # Arguments
+    /// * `date` - The date of the examination.
+    /// * `source` - The question bank that the questions were drawn from, e.g. its path.
+    ///
+    /// # Output
+    /// `Exam` - A new `Exam` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=3
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string())];
+    ///
+    /// let generator = Generator::new_with_seed(&qbank, 1, 3, 2, &students, 5).unwrap();
+    /// let exam = generator.get_exam("2026-10-16".to_string(), "./Test.qbdb".to_string());
+    /// assert_eq!(exam.get_title(), "Examination");
+    /// assert_eq!(exam.get_date(), "2026-10-16");
+    /// assert_eq!(exam.get_seed(), Some(5));
+    /// assert_eq!(exam.get_shuffled_qsets().len(), 1);
+    ///
+    /// // The versions are kept when reprinting.
+    /// let students: Students = (1..=4).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(2));
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 3, 2), &students, &options, 7).unwrap();
+    /// let exam = generator.get_exam(String::new(), String::new());
+    /// assert_eq!(exam.get_assigned_forms(), &vec![0, 1, 0, 1]);
+    /// let reprinted = Generator::new_with_exam(&qbank, &exam).unwrap();
+    /// assert_eq!(reprinted.get_forms().len(), 2);
+    /// for (idx, shuffled_qset) in reprinted.get_shuffled_qsets().iter().enumerate()
+    /// {
+    ///     let form = reprinted.get_assigned_form(idx).unwrap();
+    ///     assert_eq!(Some(form), generator.get_assigned_form(idx));
+    ///     assert_eq!(shuffled_qset.get_student().get_id(), students[idx].get_id());
+    ///     assert_eq!(reprinted.get_forms()[form].get_student().get_id(), generator.get_forms()[form].get_student().get_id());
+    ///     for (sq1, sq2) in reprinted.get_forms()[form].get_shuffled_questions().iter().zip(generator.get_forms()[form].get_shuffled_questions())
+    ///     {
+    ///         assert_eq!(sq1.get_question(), sq2.get_question());
+    ///         assert_eq!(sq1.get_choices(), sq2.get_choices());
+    ///     }
+    /// }
+    /// ```
+    pub fn get_exam(&self, date: String, source: String) -> Exam
+    {
+        let mut exam = Exam::new(self.origin.get_header().get_title().clone(), date, source, self.seed, self.shuffled_qsets.clone());
+        exam.set_assigned_forms(self.assigned_forms.clone());
+        exam
+    }
+
     // pub fn get_title_font_size(&self) -> f32
     /// Retrieves the current title font size in points.
     ///
@@ -1506,6 +2714,10 @@ impl Generator
     ///
     /// This function reconstructs a `QBank` with shuffled questions for a given student
     /// based on the original `QBank` and the shuffled question set at the specified index.
+    /// The choices of every question are rearranged into the student's shuffled order,
+    /// so the correct answers in the returned `QBank` are marked at their shuffled positions.
+    /// A question which shows only some of its distractors has just the choices shown
+    /// to the student, so that its answer key is that of the student's paper.
     ///
     /// # Arguments
     /// * `idx` - The zero-based index of the shuffled question set.
@@ -1516,85 +2728,217 @@ impl Generator
     ///
     /// # Examples
     /// ```
-    /// use qrate::{ QBank, Generator, Student, Students };
+    /// use qrate::{ QBank, Generator, Parameter, Question, Student, Students };
     ///
-    /// let mut qbank = QBank::new_empty();
-    /// qbank.add_question("Question 1".to_string(), "Answer 1".to_string());
-    /// qbank.add_question("Question 2".to_string(), "Answer 2".to_string());
+    /// let mut qbank = QBank::new_with_default();
+    /// let choices = vec![("1".to_string(), false), ("2".to_string(), true), ("3".to_string(), false), ("4".to_string(), false)];
+    /// qbank.push_question(Question::new(1, 1, 1, "What is 1+1?".to_string(), choices.clone()));
+    /// qbank.push_question(Question::new(2, 2, 1, "What is 4-2?".to_string(), choices));
     ///
-    /// let student1 = Student::new_from_name("Alice".to_string());
-    /// let students = Students::new(vec![student1]);
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string())];
     ///
-    /// let generator = Generator::new(&qbank, 1, 2, &students).unwrap();
+    /// let generator = Generator::new(&qbank, 1, 2, 2, &students).unwrap();
     /// let shuffled_qbank_tuple = generator.get_shuffled_qbank(0);
     /// assert!(shuffled_qbank_tuple.is_some());
     /// let (student, shuffled_qbank) = shuffled_qbank_tuple.unwrap();
     /// assert_eq!(student.get_name(), "Alice");
     /// assert_eq!(shuffled_qbank.get_questions().len(), 2);
     ///
+    /// // The correct answer follows its choice wherever the shuffle moved it.
+    /// for question in shuffled_qbank.get_questions()
+    /// {
+    ///     let correct: Vec<&String> = question.get_choices().iter().filter(|c| c.1).map(|c| &c.0).collect();
+    ///     assert_eq!(correct, vec!["2"]);
+    /// }
+    ///
     /// let no_shuffled_qbank = generator.get_shuffled_qbank(1);
     /// assert!(no_shuffled_qbank.is_none());
+    ///
+    /// // Questions are found by their IDs, whatever their positions in the bank are.
+    /// let mut gapped = QBank::new_with_default();
+    /// gapped.push_question(Question::new(30, 3, 1, "Q30".to_string(), vec![("A".to_string(), true)]));
+    /// gapped.push_question(Question::new(10, 1, 1, "Q10".to_string(), vec![("A".to_string(), true)]));
+    /// let generator = Generator::new(&gapped, 10, 10, 1, &students).unwrap();
+    /// let (_, shuffled_qbank) = generator.get_shuffled_qbank(0).unwrap();
+    /// assert_eq!(shuffled_qbank.get_questions()[0].get_question(), "Q10");
+    ///
+    /// // Each student is shown the correct answer and 3 of the 7 distractors.
+    /// let mut sampled = QBank::new_with_default();
+    /// let mut question = Question::new(1, 1, 1, "Q1".to_string(), (1..=8).map(|i| (i.to_string(), i == 8)).collect());
+    /// question.set_shown_distractors(Some(3));
+    /// sampled.push_question(question);
+    /// let generator = Generator::new(&sampled, 1, 1, 1, &students).unwrap();
+    /// let (_, shuffled_qbank) = generator.get_shuffled_qbank(0).unwrap();
+    /// let choices = shuffled_qbank.get_questions()[0].get_choices();
+    /// assert_eq!(choices.len(), 4);
+    /// assert_eq!(choices.iter().filter(|c| c.1).map(|c| c.0.as_str()).collect::<Vec<&str>>(), vec!["8"]);
+    ///
+    /// // Each student is given random values of the parameters of a templated question.
+    /// let mut templated = QBank::new_with_default();
+    /// let mut question = Question::new(1, 1, 1, "What is {a} + {b}?".to_string(),
+    ///         vec![("{a + b}".to_string(), true), ("{a - b}".to_string(), false), ("{a * b}".to_string(), false)]);
+    /// question.set_parameters(vec![Parameter::new("a".to_string(), 10, 99), Parameter::new("b".to_string(), 2, 9)]);
+    /// templated.push_question(question);
+    /// let generator = Generator::new(&templated, 1, 1, 1, &students).unwrap();
+    /// let values = generator.get_shuffled_qsets()[0].get_shuffled_questions()[0].get_values().clone();
+    /// let (a, b) = (values[0].1, values[1].1);
+    /// let (_, shuffled_qbank) = generator.get_shuffled_qbank(0).unwrap();
+    /// let shown = &shuffled_qbank.get_questions()[0];
+    /// assert_eq!(shown.get_question(), &format!("What is {} + {}?", a, b));
+    /// let correct: Vec<String> = shown.get_choices().iter().filter(|c| c.1).map(|c| c.0.clone()).collect();
+    /// assert_eq!(correct, vec![(a + b).to_string()]);
     /// ```
     pub fn get_shuffled_qbank(&self, idx: usize) -> Option<(Student, QBank)>
     {
-        if idx < self.shuffled_qsets.len()
-        {
-            let header = self.origin.get_header().clone();
-            let mut qbank = QBank::new_with_header(header);
-            let mut questions = Questions::new();
-            for i in 0..self.shuffled_qsets[idx].get_shuffled_questions().len()
-            {
-                let qn = self.shuffled_qsets[idx].get_shuffled_questions()[i].get_question();
-                let question = self.origin.get_question(qn as usize)?;
-                questions.push(question.clone());
-            }
-            qbank.set_questions(questions);
-            Some((self.shuffled_qsets[idx].get_student().clone(), qbank))
-        }
-        else
+        let shuffled_qset = self.shuffled_qsets.get(idx)?;
+        Some((shuffled_qset.get_student().clone(), self.make_shuffled_qbank(shuffled_qset)?))
+    }
+
+    // fn make_shuffled_qbank(&self, shuffled_qset: &ShuffledQSet) -> Option<QBank>
+    /// Reconstructs a `QBank` with the questions of `shuffled_qset` in their shuffled order,
+    /// or returns `None` if a question cannot be found.
+    fn make_shuffled_qbank(&self, shuffled_qset: &ShuffledQSet) -> Option<QBank>
+    {
+        let header = self.origin.get_header().clone();
+        let mut qbank = QBank::new_with_header(header);
+        let mut questions = Questions::new();
+        for shuffled_question in shuffled_qset.get_shuffled_questions()
         {
-            None
+            let origin_question = self.get_origin_question(shuffled_question)?;
+            questions.push(shuffled_question.make_shown_question(origin_question)?);
         }
+        qbank.set_questions(questions);
+        Some(qbank)
     }
 
-    // pub fn get_shuffled_qbanks(&self) -> Vec::<(Student, QBank)>
+    // pub fn get_shuffled_qbanks(&self) -> Result<Vec<(Student, QBank)>, Error>
     /// Retrieves all generated shuffled `QBank` instances
     /// with their associated `Student`s.
     ///
     /// This function iterates through all generated shuffled question sets and
     /// reconstructs a `QBank` for each, paired with its corresponding `Student`.
     ///
+    /// The question banks are reconstructed in parallel.
+    ///
     /// # Output
-    /// A `Vec<(Student, QBank)>` containing all shuffled question banks and
-    /// their students.
+    /// A `Result<Vec<(Student, QBank)>, Error>` containing all shuffled question banks
+    /// and their students, or an `Error::Validation` if a set has a question
+    /// that is not in the question bank.
     ///
     /// # Examples
     /// ```
-    /// use qrate::{ QBank, Generator, Student, Students };
+    /// use qrate::{ QBank, Generator, Question, Student, Students };
     ///
-    /// let mut qbank = QBank::new_empty();
-    /// qbank.add_question("Question 1".to_string(), "Answer 1".to_string());
-    /// qbank.add_question("Question 2".to_string(), "Answer 2".to_string());
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Question 1".to_string(), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    /// qbank.push_question(Question::new(2, 2, 1, "Question 2".to_string(), vec![("C".to_string(), false), ("D".to_string(), true)]));
     ///
-    /// let student1 = Student::new_from_name("Alice".to_string());
-    /// let student2 = Student::new_from_name("Bob".to_string());
-    /// let students = Students::new(vec![student1, student2]);
+    /// let students: Students = vec![Student::new("Alice".to_string(), "1".to_string()),
+    ///                               Student::new("Bob".to_string(), "2".to_string())];
     ///
-    /// let generator = Generator::new(&qbank, 1, 2, &students).unwrap();
-    /// let shuffled_qbanks = generator.get_shuffled_qbanks();
+    /// let generator = Generator::new(&qbank, 1, 2, 2, &students).unwrap();
+    /// let shuffled_qbanks = generator.get_shuffled_qbanks().unwrap();
     /// assert_eq!(shuffled_qbanks.len(), 2);
     /// assert_eq!(shuffled_qbanks[0].0.get_name(), "Alice");
     /// assert_eq!(shuffled_qbanks[1].0.get_name(), "Bob");
     /// ```
-    pub fn get_shuffled_qbanks(&self) -> Vec::<(Student, QBank)>
+    pub fn get_shuffled_qbanks(&self) -> Result<Vec<(Student, QBank)>, Error>
+    {
+        Self::map_in_parallel(&self.shuffled_qsets, |shuffled_qset| {
+                let student = shuffled_qset.get_student().clone();
+                let qbank = self.make_shuffled_qbank(shuffled_qset)
+                    .ok_or_else(|| Error::Validation(format!("the paper of student {} has a question that is not in the question bank", student.get_id())))?;
+                Ok((student, qbank))
+            })
+            .into_iter().collect()
+    }
+
+    // pub fn get_form_qbank(&self, form: usize) -> Option<(Students, QBank)>
+    /// Retrieves the shuffled `QBank` of a version and the `Students` assigned to it.
+    ///
+    /// The title of the header of the returned `QBank` is followed by the label
+    /// of the version, such as " - Form A".
+    ///
+    /// # Arguments
+    /// * `form` - The zero-based index of the version.
+    ///
+    /// # Output
+    /// An `Option<(Students, QBank)>` which is `Some((Students, QBank))` if the index is valid,
+    /// or `None` if the index is out of bounds or a question cannot be found.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, QBank, Generator, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=3).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    ///
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(2));
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 6, 4), &students, &options, 7).unwrap();
+    /// let (form_students, form_qbank) = generator.get_form_qbank(1).unwrap();
+    /// assert_eq!(form_students.len(), 1);
+    /// assert_eq!(form_students[0].get_name(), "Student 2");
+    /// assert_eq!(form_qbank.get_header().get_title(), "Examination - Form B");
+    /// assert_eq!(form_qbank.get_questions().len(), 4);
+    /// assert!(generator.get_form_qbank(2).is_none());
+    /// ```
+    pub fn get_form_qbank(&self, form: usize) -> Option<(Students, QBank)>
     {
-        let mut shuffled_qbanks = Vec::new();
-        for i in 0..self.shuffled_qsets.len()
+        let mut qbank = self.make_shuffled_qbank(self.forms.get(form)?)?;
+        let mut header = qbank.get_header().clone();
+        header.set_title(format!("{} - Form {}", header.get_title(), Self::get_form_label(form)));
+        qbank.set_header(header);
+        Some((self.get_form_students(form), qbank))
+    }
+
+    // fn get_papers(&self) -> Result<Vec<Paper>, Error>
+    /// Gathers the papers to be saved, each with the student and the version
+    /// printed on it and the heading of its answer key.
+    ///
+    /// Every student has a paper of their own unless there are versions.
+    /// A version's paper leaves the name and the ID blank to be filled in and
+    /// shows the label of the version, and its answer key lists the students assigned to it.
+    /// A version without questions, which no student of a reprinted exam was given, has no paper.
+    /// A paper with a question that is not in the question bank is an `Error::Validation`.
+    fn get_papers(&self) -> Result<Vec<Paper>, Error>
+    {
+        let header = self.origin.get_header();
+        if self.forms.is_empty()
         {
-            let shuffled_qbank = self.get_shuffled_qbank(i).unwrap();
-            shuffled_qbanks.push(shuffled_qbank);
+            return Ok(self.get_shuffled_qbanks()?.into_iter().map(|(student, qbank)| {
+                    let heading = Self::get_student_line(header, &student, None);
+                    (student, None, qbank, heading)
+                }).collect());
+        }
+
+        let mut papers = Vec::new();
+        for form in 0..self.forms.len()
+        {
+            if self.forms[form].get_shuffled_questions().is_empty()
+                { continue; }
+            let (students, qbank) = self.get_form_qbank(form)
+                .ok_or_else(|| Error::Validation(format!("the paper of form {} has a question that is not in the question bank", Self::get_form_label(form))))?;
+            let roster: Vec<String> = students.iter().map(|student| format!("{} ({})", student.get_name(), student.get_id())).collect();
+            let heading = format!("Form {}: {}", Self::get_form_label(form), roster.join(", "));
+            papers.push((Student::new_empty(), Some(form), qbank, heading));
+        }
+        Ok(papers)
+    }
+
+    // fn get_student_line(header: &Header, student: &Student, form: Option<usize>) -> String
+    /// Gives the line of a paper which shows the student's name and ID,
+    /// followed by the label of the version if the paper is of a version.
+    fn get_student_line(header: &Header, student: &Student, form: Option<usize>) -> String
+    {
+        let line = format!("{}: {}        {}: {}", header.get_name(), student.get_name(), header.get_id(), student.get_id());
+        match form
+        {
+            Some(form) => format!("{}        Form: {}", line, Self::get_form_label(form)),
+            None => line,
         }
-        shuffled_qbanks
     }
 
     // pub fn get_notice(&self) -> String
@@ -1642,7 +2986,7 @@ impl Generator
     ///   - `String`: The category of the current question.
     ///   - `String`: The text of the current question.
     ///   - `Choices`: A vector of tuples `(String, bool)` representing the
-    ///                shuffled choices and whether each is a correct answer.
+    ///     shuffled choices and whether each is a correct answer.
     ///
     /// Returns `None` if there are no more questions in the set.
     ///
@@ -1664,49 +3008,42 @@ impl Generator
     ///
     /// assert!(generator.next().is_none());
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<(u16, String, String, Choices)>
     {
         self.current_question_number += 1;
 
-        let shuffled_qset = self.shuffled_qsets.get(0)?;
+        let shuffled_qset = self.shuffled_qsets.first()?;
         if self.current_question_number as usize > shuffled_qset.get_shuffled_questions().len()
             { return None; }
 
         let shuffled_question = shuffled_qset.get_shuffled_question(self.current_question_number)?;
-        let real_question_number = shuffled_question.get_question();
-        let shuffled_indices = shuffled_question.get_choices();
-
-        let origin_question = self.origin.get_question(real_question_number as usize)?;
+        let origin_question = self.get_origin_question(shuffled_question)?;
         let category = self.origin.get_header().get_category(origin_question.get_category())?.clone();
-        let question_text = origin_question.get_question().clone();
-        let origin_choices = origin_question.get_choices();
-
-        let mut choices = Choices::new();
-        for &shuffled_index in shuffled_indices
-        {
-            if let Some(choice) = origin_choices.get((shuffled_index - 1) as usize)
-                { choices.push(choice.clone()); }
-            else
-                { return None; }
-        }
+        let shown_question = shuffled_question.make_shown_question(origin_question)?;
+        let question_text = shown_question.get_question().clone();
+        let choices = shown_question.get_choices().clone();
 
         Some((self.current_question_number, category, question_text, choices))
     }
 
-    // pub fn save_shuffled_exams(&self, path: String, extention: &str) -> Result<(), String>
+    // pub fn save_shuffled_exams(&self, path: String, extention: &str) -> Result<(), Error>
     /// Saves the shuffled exam sets for all students to a single file.
     ///
     /// The output format is determined by the file extension of the provided path.
     /// Supported formats are: .txt, .docx, and .pdf.
     /// This function delegates the actual saving process to format-specific private functions.
     ///
+    /// If the `Generator` was created with the versions that `GenerationOptions::set_forms()` asks for, one paper and one answer key
+    /// are saved for each version instead, the answer key listing the students of the version.
+    ///
     /// # Arguments
     /// * `path` - The file path where the exams will be saved.
     /// * `extention` - The desired file extension (e.g., "txt", "docx", "pdf").
     ///
     /// # Output
-    /// `Result<(), String>` - Returns `Ok(())` on success, or an `Err` with a
-    ///                        `String` describing the error on failure.
+    /// `Result<(), Error>` - Returns `Ok(())` on success, or an `Err` with an
+    ///                       `Error` describing the failure.
     ///
     /// # Examples
     /// ```no_run
@@ -1728,7 +3065,7 @@ impl Generator
     /// assert!(result.is_ok());
     /// std::fs::remove_file("exam.txt").unwrap();
     /// ```
-    pub fn save_shuffled_exams(&self, path: String, extention: &str) -> Result<(), String>
+    pub fn save_shuffled_exams(&self, path: String, extention: &str) -> Result<(), Error>
     {
         let checked = check_path(path, extention);
         let file_path = Path::new(&checked);
@@ -1737,11 +3074,11 @@ impl Generator
             Some("txt") => self.save_shuffled_exams_in_txt(file_path),
             Some("docx") => self.save_shuffled_exams_in_docx(file_path),
             Some("pdf") => self.save_shuffled_exams_in_pdf(file_path),
-            _ => Err("Unsupported file format. Please use .txt, .docx, or .pdf.".to_string()),
+            _ => Err(Error::Validation("Unsupported file format. Please use .txt, .docx, or .pdf.".to_string())),
         }
     }
 
-    // fn format_exam_for_student(&self, student: &Student, qbank: &QBank) -> String
+    // fn format_exam_for_student(&self, student: &Student, form: Option<usize>, qbank: &QBank) -> String
     /// Formats the exam content for a single student into a human-readable string.
     ///
     /// This private helper function generates the textual representation of an exam
@@ -1750,6 +3087,7 @@ impl Generator
     ///
     /// # Arguments
     /// * `student` - A reference to the `Student` for whom the exam is being formatted.
+    /// * `form` - The zero-based index of the version if the paper is of a version, or `None`.
     /// * `qbank` - A reference to the `QBank` containing the shuffled questions for this student.
     ///
     /// # Output
@@ -1782,7 +3120,7 @@ impl Generator
     /// // In a real test, you'd assert against the output of the function,
     /// // but for a private helper, we rely on its callers to be tested.
     /// ```
-    fn format_exam_for_student(&self, student: &Student, qbank: &QBank) -> String
+    fn format_exam_for_student(&self, student: &Student, form: Option<usize>, qbank: &QBank) -> String
     {
         let mut content = String::new();
         let header = qbank.get_header();
@@ -1791,7 +3129,7 @@ impl Generator
         content.push_str(&format!("{}\n", header.get_title()));
 
         // Student Information
-        content.push_str(&format!("{}: {}        {}: {}\n\n", header.get_name(), student.get_name(), header.get_id(), student.get_id()));
+        content.push_str(&format!("{}\n\n", Self::get_student_line(header, student, form)));
 
         for (i, question) in qbank.get_questions().iter().enumerate()
         {
@@ -1802,12 +3140,12 @@ impl Generator
                 let choice_char = (b'A' + j as u8) as char;
                 content.push_str(&format!("    ({}) {}\n", choice_char, choice_text));
             }
-            content.push_str("\n"); // Blank line after each question
+            content.push('\n'); // Blank line after each question
         }
         content
     }
 
-    // pub fn save_shuffled_exams_in_txt(&self, path: &Path) -> Result<(), String>
+    // pub fn save_shuffled_exams_in_txt(&self, path: &Path) -> Result<(), Error>
     /// Saves the shuffled exam sets to a text file.
     ///
     /// This function generates a text file containing the shuffled exam sets
@@ -1817,8 +3155,8 @@ impl Generator
     /// * `path` - The file path where the text document will be saved.
     ///
     /// # Output
-    /// `Result<(), String>` - Returns `Ok(())` on success, or an `Err` with a
-    ///                        `String` describing the error on failure.
+    /// `Result<(), Error>` - Returns `Ok(())` on success, or an `Err` with an
+    ///                       `Error` describing the failure.
     ///
     /// # Examples
     /// ```no_run
@@ -1845,30 +3183,27 @@ impl Generator
     /// assert!(result.is_ok());
     /// std::fs::remove_file("exam_shuffled.txt").unwrap();
     /// ```
-    pub fn save_shuffled_exams_in_txt(&self, path: &Path) -> Result<(), String>
+    pub fn save_shuffled_exams_in_txt(&self, path: &Path) -> Result<(), Error>
     {
-        let mut file = File::create(path).map_err(|e| e.to_string())?;
-        let shuffled_qbanks = self.get_shuffled_qbanks();
+        let mut file = File::create(path)?;
+        let papers = self.get_papers()?;
 
-        for (student, qbank) in &shuffled_qbanks
+        for (student, form, qbank, _) in &papers
         {
-            let content = self.format_exam_for_student(&student, &qbank);
-            writeln!(file, "{}", content).map_err(|e| e.to_string())?;
+            let content = self.format_exam_for_student(student, *form, qbank);
+            writeln!(file, "{}", content)?;
             // Add a separator for multiple students, if applicable
-            if self.shuffled_qsets.len() > 1
-                { writeln!(file, "-------X------- CUT -------X------- 자르기 -------X------- резать -------X-------\n\n").map_err(|e| e.to_string())?; }
+            if papers.len() > 1
+                { writeln!(file, "-------X------- CUT -------X------- 자르기 -------X------- резать -------X-------\n\n")?; }
         }
         // Add a separator for the answer sheet
         //write!(file, "\n\u{000C}\n").map_err(|e| e.to_string())?; // Form feed for page break
 
-        let header = self.origin.get_header(); // Need the original header for titles
-        writeln!(file, "{}{}", self.answer_sheet_title, "\n").map_err(|e| e.to_string())?;
-        for (student, qbank) in &shuffled_qbanks
+        writeln!(file, "{}\n", self.answer_sheet_title)?;
+        for (_, _, qbank, heading) in &papers
         {
             // Student Info
-            writeln!(file, "{}: {}        {}: {}",
-                header.get_name(), student.get_name(), header.get_id(), student.get_id()
-            ).map_err(|e| e.to_string())?;
+            writeln!(file, "{}", heading)?;
 
             // Answers
             let mut answer_line = String::new();
@@ -1885,32 +3220,36 @@ impl Generator
 
                 // Simple line wrapping logic
                 if answer_line.len() + entry.len() > 80 && !answer_line.is_empty() {
-                    writeln!(file, "{}", answer_line).map_err(|e| e.to_string())?;
+                    writeln!(file, "{}", answer_line)?;
                     answer_line.clear();
                 }
                 answer_line.push_str(&entry);
             }
             if !answer_line.is_empty() {
-                writeln!(file, "{}", answer_line).map_err(|e| e.to_string())?;
+                writeln!(file, "{}", answer_line)?;
             }
-            writeln!(file, "").map_err(|e| e.to_string())?; // Blank line after each student
+            writeln!(file)?; // Blank line after each student
         }
 
         Ok(())
     }
 
-    // pub fn save_shuffled_exams_in_docx(&self, path: &Path) -> Result<(), String>
+    // pub fn save_shuffled_exams_in_docx(&self, path: &Path) -> Result<(), Error>
     /// Saves the shuffled exam sets to a DOCX file.
     ///
     /// This function generates a DOCX document containing the shuffled exam sets
     /// for all students, applying specified page margins and a footer with page numbers.
     ///
+    /// Only the paragraphs of the papers are built in parallel, on as many threads
+    /// as the machine runs at once. The answer sheet is built, and the document is
+    /// put together and packed into the file, on the calling thread one step after another.
+    ///
     /// # Arguments
     /// * `path` - The file path where the DOCX document will be saved.
     ///
     /// # Output
-    /// `Result<(), String>` - Returns `Ok(())` on success, or an `Err` with a
-    ///                        `String` describing the error on failure.
+    /// `Result<(), Error>` - Returns `Ok(())` on success, or an `Err` with an
+    ///                       `Error` describing the failure.
     ///
     /// # Examples
     /// ```no_run
@@ -1931,7 +3270,7 @@ impl Generator
     /// assert!(result.is_ok());
     /// std::fs::remove_file("exam.docx").unwrap();
     /// ```
-    pub fn save_shuffled_exams_in_docx(&self, path: &Path) -> Result<(), String>
+    pub fn save_shuffled_exams_in_docx(&self, path: &Path) -> Result<(), Error>
     {
         let pt_to_usize = |pt: f32| -> usize { (pt as usize) << 1 };
         let linespacing_to_twips = |linespacing: f32| -> i32 { (linespacing * 240.0) as i32 };
@@ -1984,13 +3323,16 @@ impl Generator
                                 .bottom(buttom)
             ) // 1 cm for default left, right, top, bottom
             .footer(footer);
-        let shuffled_qbanks = self.get_shuffled_qbanks();
+        let papers = self.get_papers()?;
 
-        for (idx, (student, qbank)) in shuffled_qbanks.iter().enumerate()
+        // The papers are laid out in parallel, and then put together in order.
+        let contents = Self::map_in_parallel(&papers, |(student, form, qbank, _)| self.make_exam_content_for_docx(student, *form, qbank));
+        for (idx, paragraphs) in contents.into_iter().enumerate()
         {
             if idx > 0
                 { docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_break(BreakType::Page))); } // Page break for subsequent students
-            self.write_exam_content_to_docx(&mut docx, &student, &qbank)?;
+            for paragraph in paragraphs
+                { docx = docx.add_paragraph(paragraph); }
         }
 
         // Add answer sheet
@@ -2024,18 +3366,14 @@ impl Generator
         if self.is_answer_sheet_strike()
             { answer_sheet_run = answer_sheet_run.strike(); }
         
-        let header = self.origin.get_header();
         let line_spacing = linespacing_to_twips(self.line_spacing);
-        for (student, qbank) in &shuffled_qbanks
+        for (_, _, qbank, heading) in &papers
         {
             // Student Info
-            let student_info_text = format!("{}: {}        {}: {}",
-                header.get_name(), student.get_name(), header.get_id(), student.get_id()
-            );
             let student_info_paragraph = Paragraph::new()
                 .add_run(
                     answer_sheet_run.clone()
-                        .add_text(student_info_text)
+                        .add_text(heading.as_str())
                         .size(answer_sheet_font_size)) // 12 pt for default
                 .line_spacing(docx_rs::LineSpacing::new().line(line_spacing));   // Single line spacing
             docx = docx.add_paragraph(student_info_paragraph);
@@ -2063,27 +3401,28 @@ impl Generator
             docx = docx.add_paragraph(Paragraph::new()); // Blank line
         }
 
-        let file = File::create(path).map_err(|e| e.to_string())?;
-        docx.build().pack(file).map_err(|e| e.to_string())?;
+        let file = File::create(path)?;
+        docx.build().pack(file).map_err(|e| Error::Render(e.to_string()))?;
         Ok(())
     }
 
-    // fn write_exam_content_to_docx(&self, docx: &mut Docx, student: &Student, qbank: &QBank) -> Result<(), String>
-    /// Writes the formatted exam content for a single student to a DOCX document.
+    // fn make_exam_content_for_docx(&self, student: &Student, form: Option<usize>, qbank: &QBank) -> Vec<Paragraph>
+    /// Builds the formatted exam content for a single student as DOCX paragraphs.
     ///
-    /// This private helper function takes a mutable DOCX `Docx` object and appends
-    /// the exam content for the given student and their shuffled question bank,
-    /// applying DOCX-specific formatting such as font sizes.
+    /// This private helper function builds the paragraphs of the exam content for
+    /// the given student and their shuffled question bank, applying DOCX-specific
+    /// formatting such as font sizes. It does not touch any document, so the papers
+    /// can be built in parallel and then added to the `Docx` in order.
     ///
     /// # Arguments
-    /// * `docx` - A mutable reference to the `docx_rs::Docx` object.
     /// * `student` - A reference to the `Student` for whom the exam content is being written.
+    /// * `form` - The zero-based index of the version if the paper is of a version, or `None`.
     /// * `qbank` - A reference to the `QBank` containing the shuffled questions for this student.
     ///
     /// # Output
-    /// `Result<(), String>` - Returns `Ok(())` on success, or an `Err` with a
-    ///                        `String` describing the error on failure.
-    fn write_exam_content_to_docx(&self, docx: &mut Docx, student: &Student, qbank: &QBank) -> Result<(), String>
+    /// `Vec<Paragraph>` - The title, the student line, and the questions with their
+    ///                    choices, in the order they are to be added to the document.
+    fn make_exam_content_for_docx(&self, student: &Student, form: Option<usize>, qbank: &QBank) -> Vec<Paragraph>
     {
         let pt_to_usize = |pt: f32| -> usize { (pt as usize) << 1 };
         
@@ -2126,45 +3465,44 @@ impl Generator
         };
 
         // Student Information
-        let st = paragraph(body_run.clone(), format!("{}: {}        {}: {}\n\n", header.get_name(), student.get_name(), header.get_id(), student.get_id()), body_font_size);
+        let st = paragraph(body_run.clone(), format!("{}\n\n", Self::get_student_line(header, student, form)), body_font_size);
 
         // Blank line
-        let blank_line = paragraph(body_run.clone(), format!(""), body_font_size);
+        let blank_line = paragraph(body_run.clone(), String::new(), body_font_size);
 
-        // Clone to prevent move, then reassign
-        *docx = docx.clone().add_paragraph(ex).add_paragraph(st).add_paragraph(blank_line.clone());
+        let mut paragraphs = vec![ex, st, blank_line.clone()];
 
         for (i, question) in qbank.get_questions().iter().enumerate()
         {
             let modum = header.get_category(question.get_category()).unwrap();
-            let para = paragraph(body_run.clone(), format!("{}. [{}]   {}\n", i + 1, modum, question.get_question()), body_font_size);
-            // Clone to prevent move, then reassign
-            *docx = docx.clone().add_paragraph(para);
+            paragraphs.push(paragraph(body_run.clone(), format!("{}. [{}]   {}\n", i + 1, modum, question.get_question()), body_font_size));
             for (j, (choice_text, _is_correct)) in question.get_choices().iter().enumerate()
             {
                 let choice_char = (b'A' + j as u8) as char;
-                let para = paragraph(body_run.clone(), format!("    ({}) {}", choice_char, choice_text), body_font_size);
-                // Clone to prevent move, then reassign
-                *docx = docx.clone().add_paragraph(para);
+                paragraphs.push(paragraph(body_run.clone(), format!("    ({}) {}", choice_char, choice_text), body_font_size));
             }
             // Blank line after each question
-            *docx = docx.clone().add_paragraph(blank_line.clone());
+            paragraphs.push(blank_line.clone());
         }
-        Ok(())
+        paragraphs
     }
 
-    // pub fn save_shuffled_exams_in_pdf(&self, path: &Path) -> Result<(), String>
+    // pub fn save_shuffled_exams_in_pdf(&self, path: &Path) -> Result<(), Error>
     /// Saves the shuffled exam sets to a PDF file.
     ///
     /// This function generates a PDF document containing the shuffled exam sets
     /// for all students, with a footer showing page numbers.
     ///
+    /// Only the paragraphs of the papers are built in parallel, on as many threads
+    /// as the machine runs at once. The answer sheet is built, and `genpdf` lays out
+    /// and renders the whole document, on the calling thread one step after another.
+    ///
     /// # Arguments
     /// * `path` - The file path where the PDF document will be saved.
     ///
     /// # Output
-    /// `Result<(), String>` - Returns `Ok(())` on success, or an `Err` with a
-    /// `String` describing the error on failure.
+    /// `Result<(), Error>` - Returns `Ok(())` on success, or an `Err` with an
+    ///                       `Error` describing the failure.
     ///
     /// # Caution
     /// - This method searches for four specific font files within a `./fonts` 
@@ -2200,22 +3538,25 @@ impl Generator
     /// assert!(result.is_ok());
     /// std::fs::remove_file("exam.pdf").unwrap();
     /// ```
-    pub fn save_shuffled_exams_in_pdf(&self, path: &Path) -> Result<(), String>
+    pub fn save_shuffled_exams_in_pdf(&self, path: &Path) -> Result<(), Error>
     {
-        let font_family = fonts::from_files("./fonts", "font", None).map_err(|e| format!("Failed to load font: {}", e))?;
+        let font_family = fonts::from_files("./fonts", "font", None).map_err(|e| Error::Render(format!("Failed to load font: {}", e)))?;
         let mut doc = Document::new(font_family);
         // Set 1cm margins (10mm) and page numbers for all sides
         let mut decorator = SimplePageDecorator::new();
         let margin = (self.margin_left_in_mm + self.margin_right_in_mm + self.margin_top_in_mm + self.margin_buttom_in_mm) / 4.0;
         decorator.set_margins(margin); // 10mm = 1cm
         doc.set_page_decorator(decorator);
-        let shuffled_qbanks = self.get_shuffled_qbanks();
+        let papers = self.get_papers()?;
 
-        for (idx, (student, qbank)) in shuffled_qbanks.iter().enumerate()
+        // The papers are laid out in parallel, and then put together in order.
+        let contents = Self::map_in_parallel(&papers, |(student, form, qbank, _)| self.make_exam_content_for_pdf(student, *form, qbank));
+        for (idx, paragraphs) in contents.into_iter().enumerate()
         {
             if idx > 0
                 { doc.push(elements::PageBreak::new()); } // Page break for subsequent students
-            self.write_exam_content_to_pdf(&mut doc, &student, &qbank)?;
+            for paragraph in paragraphs
+                { doc.push(paragraph); }
         }
 
         // Add answer sheet
@@ -2238,14 +3579,9 @@ impl Generator
         doc.push(title_paragraph.styled(answer_title_style));
         doc.push(elements::Paragraph::new("")); // Blank line
 
-        let header = self.origin.get_header();
-
-        for (student, qbank) in &shuffled_qbanks {
+        for (_, _, qbank, heading) in &papers {
             // Student Info
-            let student_info_text = format!("{}: {}        {}: {}",
-                header.get_name(), student.get_name(), header.get_id(), student.get_id()
-            );
-            doc.push(elements::Paragraph::new(student_info_text).styled(answer_style));
+            doc.push(elements::Paragraph::new(heading.as_str()).styled(answer_style));
 
             // Answers
             let mut answers_text = String::new();
@@ -2263,29 +3599,30 @@ impl Generator
             doc.push(elements::Paragraph::new("")); // Blank line
         }
 
-        doc.render_to_file(path).map_err(|e| e.to_string())?;
+        doc.render_to_file(path).map_err(|e| Error::Render(e.to_string()))?;
         Ok(())
     }
 
-    // fn write_exam_content_to_pdf(&self, doc: &mut genpdf::Document, student: &Student, qbank: &QBank) -> Result<(), String>
-    /// Writes the formatted exam content for a single student to a PDF document.
+    // fn make_exam_content_for_pdf(&self, student: &Student, form: Option<usize>, qbank: &QBank) -> Vec<elements::StyledElement<elements::Paragraph>>
+    /// Builds the formatted exam content for a single student as PDF paragraphs.
     ///
-    /// This private helper function takes a mutable PDF `genpdf::Document` object
-    /// and appends the exam content for the given student and their shuffled
-    /// question bank, applying PDF-specific formatting such as font sizes.
+    /// This private helper function builds the styled paragraphs of the exam content
+    /// for the given student and their shuffled question bank, applying PDF-specific
+    /// formatting such as font sizes. It does not touch any document, so the papers
+    /// can be built in parallel and then pushed to the `genpdf::Document` in order.
     ///
     /// # Arguments
-    /// * `doc` - A mutable reference to the `genpdf::Document` object.
     /// * `student` - A reference to the `Student` for whom the exam content is being written.
+    /// * `form` - The zero-based index of the version if the paper is of a version, or `None`.
     /// * `qbank` - A reference to the `QBank` containing the shuffled questions for this student.
     ///
     /// # Output
-    /// `Result<(), String>` - Returns `Ok(())` on success, or an `Err` with a
-    ///                        `String` describing the error on failure.
+    /// `Vec<elements::StyledElement<elements::Paragraph>>` - The title, the student line,
+    ///     and the questions with their choices, in the order they are to be pushed to the document.
     /// 
     /// # Caution
     /// - The attributes of underline and strike are not working.
-    fn write_exam_content_to_pdf(&self, doc: &mut genpdf::Document, student: &Student, qbank: &QBank) -> Result<(), String>
+    fn make_exam_content_for_pdf(&self, student: &Student, form: Option<usize>, qbank: &QBank) -> Vec<elements::StyledElement<elements::Paragraph>>
     {
         // Define font sizes
         let title_font_size = self.title_font_size as u8;       // 14 pt for default
@@ -2302,7 +3639,7 @@ impl Generator
         // Exam Title
         let mut title_paragraph = elements::Paragraph::new(format!("{}", header.get_title()));
         title_paragraph.set_alignment(Alignment::Center);
-        doc.push(title_paragraph.styled(title_style));
+        let mut paragraphs = vec![title_paragraph.styled(title_style)];
 
         let mut body_style = style::Style::new();
         body_style.set_font_size(normal_font_size);
@@ -2312,20 +3649,20 @@ impl Generator
             { body_style.set_italic(); }
         
         // Student Information
-        doc.push(elements::Paragraph::new(format!("{}: {}        {}: {}", header.get_name(), student.get_name(), header.get_id(), student.get_id())).styled(body_style));
-        doc.push(elements::Paragraph::new("")); // Blank line
+        paragraphs.push(elements::Paragraph::new(Self::get_student_line(header, student, form)).styled(body_style));
+        paragraphs.push(elements::Paragraph::new("").styled(style::Style::new())); // Blank line
 
         for (i, question) in qbank.get_questions().iter().enumerate()
         {
             let modum = header.get_category(question.get_category()).unwrap();
-            doc.push(elements::Paragraph::new(format!("{}. [{}]   {}", i + 1, modum, question.get_question())).styled(body_style));
+            paragraphs.push(elements::Paragraph::new(format!("{}. [{}]   {}", i + 1, modum, question.get_question())).styled(body_style));
             for (j, (choice_text, _is_correct)) in question.get_choices().iter().enumerate()
             {
                 let choice_char = (b'A' + j as u8) as char;
-                doc.push(elements::Paragraph::new(format!("    ({}) {}", choice_char, choice_text)).styled(body_style));
+                paragraphs.push(elements::Paragraph::new(format!("    ({}) {}", choice_char, choice_text)).styled(body_style));
             }
-            doc.push(elements::Paragraph::new("")); // Blank line after each question
+            paragraphs.push(elements::Paragraph::new("").styled(style::Style::new())); // Blank line after each question
         }
-        Ok(())
+        paragraphs
     }
 }
\ No newline at end of file
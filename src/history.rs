@@ -0,0 +1,193 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::collections::{ HashMap, HashSet };
+
+use crate::Exam;
+
+/// Represents the examinations held so far from a question bank, such as
+/// the midterm before the final, oldest first.
+///
+/// The examinations are usually read back with `ESDB::read_exams()`.
+#[derive(Debug, Clone)]
+pub struct History
+{
+    exams: Vec<Exam>,
+}
+
+impl History
+{
+    // pub fn new_empty() -> Self
+    /// Creates a new `History` without any examination.
+    ///
+    /// # Output
+    /// `Self` - A new, empty `History` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::History;
+    /// let history = History::new_empty();
+    /// assert!(history.get_exams().is_empty());
+    /// ```
+    #[inline]
+    pub fn new_empty() -> Self
+    {
+        Self { exams: Vec::new() }
+    }
+
+    // pub fn new_with_exams(exams: Vec<Exam>) -> Self
+    /// Creates a new `History` from the examinations held so far.
+    ///
+    /// # Arguments
+    /// * `exams` - The examinations, oldest first.
+    ///
+    /// # Output
+    /// `Self` - A new `History` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, History };
+    /// let history = History::new_with_exams(vec![Exam::new_empty(), Exam::new_empty()]);
+    /// assert_eq!(history.get_exams().len(), 2);
+    /// ```
+    #[inline]
+    pub fn new_with_exams(exams: Vec<Exam>) -> Self
+    {
+        Self { exams }
+    }
+
+    // pub fn get_exams(&self) -> &Vec<Exam>
+    /// Gets the examinations held so far.
+    ///
+    /// # Output
+    /// `&Vec<Exam>` - A reference to the examinations, oldest first.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, History };
+    /// let history = History::new_with_exams(vec![Exam::new_empty()]);
+    /// assert_eq!(history.get_exams().len(), 1);
+    /// ```
+    #[inline]
+    pub fn get_exams(&self) -> &Vec<Exam>
+    {
+        &self.exams
+    }
+
+    // pub fn push_exam(&mut self, exam: Exam)
+    /// Adds an examination held after all the others.
+    ///
+    /// # Arguments
+    /// * `exam` - The latest examination.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, History };
+    /// let mut history = History::new_empty();
+    /// history.push_exam(Exam::new_empty());
+    /// assert_eq!(history.get_exams().len(), 1);
+    /// ```
+    #[inline]
+    pub fn push_exam(&mut self, exam: Exam)
+    {
+        self.exams.push(exam);
+    }
+
+    // pub fn get_recent_usage(&self, sittings: usize) -> HashMap<(u16, u16), usize>
+    /// Counts in how many of the latest sittings each question appeared.
+    ///
+    /// Questions are told apart by their question bank as well as their ID, since
+    /// an examination drawn from several question banks may have the same ID in each.
+    ///
+    /// # Arguments
+    /// * `sittings` - The number of the latest examinations to look back on.
+    ///
+    /// # Output
+    /// `HashMap<(u16, u16), usize>` - The number of sittings in which each question appeared,
+    /// keyed by the 0-based index of its question bank and its ID, as `ShuffledQuestion::get_bank()`
+    /// and `ShuffledQuestion::get_question()` give them. A question that did not appear is not in the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, History, ShuffledQSet, ShuffledQuestion };
+    /// let exam_with = |ids: &[u16]| {
+    ///     let mut qset = ShuffledQSet::new_empty();
+    ///     qset.set_shuffled_questions(ids.iter().map(|&id| ShuffledQuestion::new(id, 4)).collect());
+    ///     let mut exam = Exam::new_empty();
+    ///     exam.set_shuffled_qsets(vec![qset.clone(), qset]);
+    ///     exam
+    /// };
+    /// let history = History::new_with_exams(vec![exam_with(&[1, 2]), exam_with(&[2, 3]), exam_with(&[3, 4])]);
+    ///
+    /// let usage = history.get_recent_usage(2);
+    /// assert_eq!(usage.get(&(0, 1)), None);      // Only in the oldest sitting
+    /// assert_eq!(usage.get(&(0, 2)), Some(&1));
+    /// assert_eq!(usage.get(&(0, 3)), Some(&2));  // Counted once for each sitting, not for each student
+    /// assert_eq!(history.get_recent_usage(10).len(), 4);
+    ///
+    /// // Question 1 of the second question bank is not question 1 of the first.
+    /// let mut other = ShuffledQuestion::new(1, 4);
+    /// other.set_bank(1);
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_shuffled_questions(vec![ShuffledQuestion::new(1, 4), other]);
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_shuffled_qsets(vec![qset]);
+    /// let usage = History::new_with_exams(vec![exam]).get_recent_usage(1);
+    /// assert_eq!(usage.get(&(0, 1)), Some(&1));
+    /// assert_eq!(usage.get(&(1, 1)), Some(&1));
+    /// ```
+    pub fn get_recent_usage(&self, sittings: usize) -> HashMap<(u16, u16), usize>
+    {
+        let mut usage = HashMap::new();
+        for exam in self.exams.iter().rev().take(sittings)
+        {
+            let questions: HashSet<(u16, u16)> = exam.get_shuffled_qsets().iter()
+                .flat_map(|shuffled_qset| shuffled_qset.get_shuffled_questions().iter().map(|shuffled_question| (shuffled_question.get_bank(), shuffled_question.get_question())))
+                .collect();
+            for question in questions
+                { *usage.entry(question).or_insert(0) += 1; }
+        }
+        usage
+    }
+
+    // pub fn get_seen_questions(&self, student_id: &str) -> HashSet<(u16, u16)>
+    /// Collects the questions that a student has been given in any of the examinations.
+    ///
+    /// # Arguments
+    /// * `student_id` - The ID of the student.
+    ///
+    /// # Output
+    /// `HashSet<(u16, u16)>` - The questions that the student has been given, each as
+    /// the 0-based index of its question bank and its ID, as in `get_recent_usage()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, History, ShuffledQSet, ShuffledQuestion, Student };
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_student(&Student::new("Alice".to_string(), "1".to_string()));
+    /// qset.set_shuffled_questions(vec![ShuffledQuestion::new(5, 4), ShuffledQuestion::new(9, 4)]);
+    /// let mut exam = Exam::new_empty();
+    /// exam.set_shuffled_qsets(vec![qset]);
+    /// let history = History::new_with_exams(vec![exam]);
+    ///
+    /// let seen = history.get_seen_questions("1");
+    /// assert_eq!(seen.len(), 2);
+    /// assert!(seen.contains(&(0, 5)) && seen.contains(&(0, 9)));
+    /// assert!(history.get_seen_questions("2").is_empty());
+    /// ```
+    pub fn get_seen_questions(&self, student_id: &str) -> HashSet<(u16, u16)>
+    {
+        self.exams.iter()
+            .flat_map(|exam| exam.get_shuffled_qsets().iter())
+            .filter(|shuffled_qset| shuffled_qset.get_student().get_id() == student_id)
+            .flat_map(|shuffled_qset| shuffled_qset.get_shuffled_questions().iter().map(|shuffled_question| (shuffled_question.get_bank(), shuffled_question.get_question())))
+            .collect()
+    }
+}
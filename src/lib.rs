@@ -44,9 +44,45 @@ pub mod student;
 /// The `shuffler` module provides functionalities for shuffling questions and creating shuffled sets.
 pub mod shuffler;
 
+/// The `blueprint` module defines the `Blueprint` and `Section` structures that describe how an exam is assembled.
+pub mod blueprint;
+
+/// The `seating` module defines the `Seating` structure that tells which students sit next to each other.
+pub mod seating;
+
+/// The `options` module defines the `GenerationOptions` structure of the seating, the history and the versions that a generation takes into account.
+pub mod options;
+
 /// The `generator` module provides functionalities for generating various exam formats.
 pub mod generator;
 
+/// The `exam` module defines the `Exam` structure that records a generated examination.
+pub mod exam;
+
+/// The `esdb` module handles the exam set database operations.
+pub mod esdb;
+
+/// The `history` module defines the `History` structure that records the examinations held so far.
+pub mod history;
+
+/// The `random` module defines the `RandomSource` trait for the random numbers that the shuffler draws.
+pub mod random;
+
+/// The `error` module defines the `Error` enumeration of the crate and the `GenerationError` enumeration that tells why question sets could not be generated.
+pub mod error;
+
+/// The `capacity` module defines the `Capacity` structure that tells how many distinct question sets a question bank can support.
+pub mod capacity;
+
+/// The `statistics` module provides the `ChiSquare` test that shows the randomisation is fair.
+pub mod statistics;
+
+/// The `constraint` module defines the `Constraint` enumeration of the rules between two questions beyond their groups.
+pub mod constraint;
+
+/// The `template` module defines the `Parameter` structure of templated questions and fills their values into texts.
+pub mod template;
+
 pub use database::SQLiteDB;
 pub use excel::Excel;
 pub use header::Header;
@@ -57,7 +93,19 @@ pub use sbank::{ SBank, SBankHelper };
 pub use sbdb::SBDB;
 pub use student::{ Student, Students };
 pub use shuffler::{ ShuffledQuestion, ShuffledQuestions, ShuffledQSet, ShuffledQSets};
+pub use blueprint::{ Blueprint, QuestionOrder, Section, Sections };
+pub use seating::Seating;
+pub use options::GenerationOptions;
 pub use generator::Generator;
+pub use exam::Exam;
+pub use esdb::ESDB;
+pub use history::History;
+pub use random::RandomSource;
+pub use error::{ Error, GenerationError };
+pub use capacity::Capacity;
+pub use statistics::ChiSquare;
+pub use constraint::{ Constraint, Constraints };
+pub use template::{ Parameter, Parameters, Values };
 
 
 // pub(crate) fn check_path(path: String, extention: &str) -> String
@@ -89,3 +137,20 @@ pub(crate) fn check_path(path: String, extention: &str) -> String
     else
         { format!("{}.{}", path, extention) }
 }
+
+// pub(crate) fn choices_to_text(choices: &[u8]) -> String
+/// Converts a list of choice numbers such as `[3, 1, 4, 2]` into the text `"3,1,4,2"`.
+pub(crate) fn choices_to_text(choices: &[u8]) -> String
+{
+    choices.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(",")
+}
+
+// pub(crate) fn text_to_choices(text: &str) -> Option<Vec<u8>>
+/// Converts the text `"3,1,4,2"` back into the list of choice numbers `[3, 1, 4, 2]`.
+/// Returns `None` if any element is not a number.
+pub(crate) fn text_to_choices(text: &str) -> Option<Vec<u8>>
+{
+    if text.trim().is_empty()
+        { return Some(Vec::new()); }
+    text.split(',').map(|c| c.trim().parse::<u8>().ok()).collect()
+}
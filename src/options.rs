@@ -0,0 +1,400 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use crate::{ History, Seating };
+
+/// Represents the options of a generation beyond the blueprint: the seating,
+/// the earlier examinations to avoid, the limit of questions a retaker may see again,
+/// and the number of versions of the paper.
+///
+/// The options can be set together in any combination, e.g. retakes with a seating
+/// and recent sittings, or versions which avoid earlier examinations, and are passed
+/// to `Generator::new_with_options()` or `Generator::new_with_source()`.
+///
+/// # Examples
+/// ```
+/// use qrate::{ Blueprint, GenerationOptions, Generator, History, QBank, Question, Seating, Student, Students };
+///
+/// let mut qbank = QBank::new_with_default();
+/// for id in 1..=20
+///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+/// let students: Students = (1..=4).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+///
+/// // The midterm used questions 1 to 5 for everybody.
+/// let midterm = Generator::new_with_blueprint(&qbank, &Blueprint::new_with_range(1, 5, 5), &students, 1).unwrap();
+///
+/// // Students 1 and 2 retake it sitting side by side, seeing none of the midterm questions again.
+/// let mut options = GenerationOptions::new_empty();
+/// options.set_seating(Seating::new_with_pairs(vec![("1".to_string(), "2".to_string())]));
+/// options.set_history(History::new_with_exams(vec![midterm.get_exam(String::new(), String::new())]));
+/// options.set_sittings(1);
+/// options.set_max_repeats(Some(0));
+/// let retake = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 20, 5), &students[..2].to_vec(), &options, 2026).unwrap();
+/// let qsets = retake.get_shuffled_qsets();
+/// assert_eq!(qsets[0].count_same_position(&qsets[1]), 0);
+/// for qset in qsets
+///     { assert!(qset.get_shuffled_questions().iter().all(|q| q.get_question() > 5)); }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenerationOptions
+{
+    seating: Seating,
+    history: History,
+    sittings: usize,                // The number of the latest examinations whose questions every student avoids.
+    max_repeats: Option<usize>,     // The number of questions seen before that a student's set may have, if limited.
+    forms: Option<usize>,           // The number of versions of the paper, if the students share versions.
+}
+
+impl GenerationOptions
+{
+    // pub fn new_empty() -> Self
+    /// Creates new `GenerationOptions` without a seating, without any earlier
+    /// examination, and with a paper of their own for each student.
+    ///
+    /// # Output
+    /// `Self` - New `GenerationOptions` with which the generation is the same as
+    /// that of `Generator::new_with_blueprint()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::GenerationOptions;
+    /// let options = GenerationOptions::new_empty();
+    /// assert!(options.get_seating().get_pairs().is_empty());
+    /// assert!(options.get_history().get_exams().is_empty());
+    /// assert_eq!(options.get_sittings(), 0);
+    /// assert_eq!(options.get_max_repeats(), None);
+    /// assert_eq!(options.get_forms(), None);
+    /// ```
+    #[inline]
+    pub fn new_empty() -> Self
+    {
+        Self
+        {
+            seating: Seating::new_empty(),
+            history: History::new_empty(),
+            sittings: 0,
+            max_repeats: None,
+            forms: None,
+        }
+    }
+
+    // pub fn get_seating(&self) -> &Seating
+    /// Gets the seating which tells which students are adjacent.
+    ///
+    /// # Output
+    /// `&Seating` - A reference to the seating.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ GenerationOptions, Seating };
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_seating(Seating::new_with_pairs(vec![("1".to_string(), "2".to_string())]));
+    /// assert!(options.get_seating().are_adjacent("2", "1"));
+    /// ```
+    #[inline]
+    pub fn get_seating(&self) -> &Seating
+    {
+        &self.seating
+    }
+
+    // pub fn set_seating(&mut self, seating: Seating)
+    /// Sets the seating which tells which students are adjacent.
+    ///
+    /// The question sets are assembled in the order of the students. Each student's set
+    /// avoids the questions of the neighbours who already have sets, as far as the
+    /// question bank and the blueprint allow, and has none of the questions it shares
+    /// with them at the same position as theirs. The questions are moved only within
+    /// the places that the `QuestionOrder` of the blueprint leaves them.
+    /// With versions of the paper, neighbours are given different versions as far as possible.
+    ///
+    /// # Arguments
+    /// * `seating` - The new seating.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, Generator, QBank, Question, QuestionOrder, Seating, Section, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=12
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=4).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// // Students 1 and 2 sit in the front row, and students 3 and 4 behind them.
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_seating(Seating::new_with_grid(&[vec!["1".to_string(), "2".to_string()],
+    ///                                              vec!["3".to_string(), "4".to_string()]]));
+    ///
+    /// let generator = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 12, 8), &students, &options, 2026).unwrap();
+    /// let qsets = generator.get_shuffled_qsets();
+    /// for (i, j) in [(0, 1), (0, 2), (1, 3), (2, 3)]
+    /// {
+    ///     // Two sets of 8 out of 12 questions share at least 4.
+    ///     assert_eq!(qsets[i].count_overlap(&qsets[j]), 4);
+    ///     assert_eq!(qsets[i].count_same_position(&qsets[j]), 0);
+    /// }
+    ///
+    /// // Questions 1 to 6 come before questions 7 to 12 on every paper.
+    /// let mut blueprint = Blueprint::new(vec![Section::new_with_range(1, 6, 4), Section::new_with_range(7, 12, 4)]);
+    /// blueprint.set_order(QuestionOrder::ShuffledWithinSection);
+    /// let generator = Generator::new_with_options(&qbank, &blueprint, &students, &options, 2026).unwrap();
+    /// let qsets = generator.get_shuffled_qsets();
+    /// for qset in qsets
+    ///     { assert!(qset.get_shuffled_questions()[..4].iter().all(|q| q.get_question() <= 6)); }
+    /// assert_eq!(qsets[0].count_same_position(&qsets[1]), 0);
+    /// ```
+    #[inline]
+    pub fn set_seating(&mut self, seating: Seating)
+    {
+        self.seating = seating;
+    }
+
+    // pub fn get_history(&self) -> &History
+    /// Gets the history of the examinations held so far.
+    ///
+    /// # Output
+    /// `&History` - A reference to the history.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Exam, GenerationOptions, History };
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_history(History::new_with_exams(vec![Exam::new_empty()]));
+    /// assert_eq!(options.get_history().get_exams().len(), 1);
+    /// ```
+    #[inline]
+    pub fn get_history(&self) -> &History
+    {
+        &self.history
+    }
+
+    // pub fn set_history(&mut self, history: History)
+    /// Sets the history of the examinations held so far, whose questions are avoided.
+    ///
+    /// Questions are down-weighted rather than excluded, so the generation does not
+    /// fail because of the history unless `set_max_repeats()` sets a limit.
+    /// A question which appeared in the latest `get_sittings()` examinations is avoided
+    /// as much as a neighbour's question for each of those sittings. A question which
+    /// a student has been given in any earlier examination, as a retaker has,
+    /// is avoided for the student more than any other question.
+    ///
+    /// # Arguments
+    /// * `history` - The new history.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, Generator, History, QBank, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=3).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    ///
+    /// // The midterm used questions 1 to 5 for everybody.
+    /// let midterm = Generator::new_with_blueprint(&qbank, &Blueprint::new_with_range(1, 5, 5), &students, 1).unwrap();
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_history(History::new_with_exams(vec![midterm.get_exam("2026-10-16".to_string(), String::new())]));
+    /// options.set_sittings(1);
+    ///
+    /// let final_exam = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 20, 5), &students, &options, 2026).unwrap();
+    /// for shuffled_qset in final_exam.get_shuffled_qsets()
+    /// {
+    ///     for shuffled_question in shuffled_qset.get_shuffled_questions()
+    ///         { assert!(shuffled_question.get_question() > 5); }
+    /// }
+    ///
+    /// // A retaker avoids the questions that they have seen, even when they are not recent.
+    /// options.set_sittings(0);
+    /// let retaker: Students = vec![students[0].clone()];
+    /// let retake = Generator::new_with_options(&qbank, &Blueprint::new_with_range(1, 10, 5), &retaker, &options, 2026).unwrap();
+    /// assert!(retake.get_shuffled_qsets()[0].get_shuffled_questions().iter().all(|q| q.get_question() > 5));
+    /// ```
+    #[inline]
+    pub fn set_history(&mut self, history: History)
+    {
+        self.history = history;
+    }
+
+    // pub fn get_sittings(&self) -> usize
+    /// Gets the number of the latest examinations whose questions every student avoids.
+    ///
+    /// # Output
+    /// `usize` - The number of sittings, 0 if only the questions each student has seen are avoided.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::GenerationOptions;
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_sittings(2);
+    /// assert_eq!(options.get_sittings(), 2);
+    /// ```
+    #[inline]
+    pub fn get_sittings(&self) -> usize
+    {
+        self.sittings
+    }
+
+    // pub fn set_sittings(&mut self, sittings: usize)
+    /// Sets the number of the latest examinations of the history whose questions every student avoids.
+    ///
+    /// # Arguments
+    /// * `sittings` - The new number of sittings.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::GenerationOptions;
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_sittings(1);
+    /// assert_eq!(options.get_sittings(), 1);
+    /// ```
+    #[inline]
+    pub fn set_sittings(&mut self, sittings: usize)
+    {
+        self.sittings = sittings;
+    }
+
+    // pub fn get_max_repeats(&self) -> Option<usize>
+    /// Gets the number of questions seen before that may still be in a student's set.
+    ///
+    /// # Output
+    /// `Option<usize>` - The limit, or `None` if the questions seen before are only avoided.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::GenerationOptions;
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_max_repeats(Some(1));
+    /// assert_eq!(options.get_max_repeats(), Some(1));
+    /// ```
+    #[inline]
+    pub fn get_max_repeats(&self) -> Option<usize>
+    {
+        self.max_repeats
+    }
+
+    // pub fn set_max_repeats(&mut self, max_repeats: Option<usize>)
+    /// Sets the number of questions seen before, according to the history,
+    /// that may still be in a student's set, not counting the anchor questions.
+    ///
+    /// A set with more of them is drawn again, and the generation fails with
+    /// `GenerationError::TooManyRepeats` if no set has few enough. With versions
+    /// of the paper, a student is given only a version with few enough of them.
+    /// Students who are not in the history get their sets as usual.
+    ///
+    /// # Arguments
+    /// * `max_repeats` - The new limit, `Some(0)` for none, or `None` for no limit.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Error, GenerationError, GenerationOptions, Generator, History, QBank, Question, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=12
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=3).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// let blueprint = Blueprint::new_with_range(1, 12, 6);
+    ///
+    /// let first = Generator::new_with_blueprint(&qbank, &blueprint, &students, 1).unwrap();
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_history(History::new_with_exams(vec![first.get_exam(String::new(), String::new())]));
+    /// options.set_max_repeats(Some(0));
+    ///
+    /// // Students 1 and 3 retake the exam.
+    /// let retakers: Students = vec![students[0].clone(), students[2].clone()];
+    /// let retake = Generator::new_with_options(&qbank, &blueprint, &retakers, &options, 2).unwrap();
+    /// assert_eq!(retake.get_shuffled_qsets()[0].count_overlap(&first.get_shuffled_qsets()[0]), 0);
+    /// assert_eq!(retake.get_shuffled_qsets()[1].count_overlap(&first.get_shuffled_qsets()[2]), 0);
+    ///
+    /// // Seven questions out of twelve cannot avoid six earlier ones.
+    /// let bigger = Blueprint::new_with_range(1, 12, 7);
+    /// let error = Generator::new_with_options(&qbank, &bigger, &retakers, &options, 2).unwrap_err();
+    /// assert!(matches!(error, Error::Generation(GenerationError::TooManyRepeats { repeats: 1, max_repeats: 0, .. })));
+    /// options.set_max_repeats(Some(1));
+    /// let retake = Generator::new_with_options(&qbank, &bigger, &retakers, &options, 2).unwrap();
+    /// assert_eq!(retake.get_shuffled_qsets()[0].count_overlap(&first.get_shuffled_qsets()[0]), 1);
+    /// ```
+    #[inline]
+    pub fn set_max_repeats(&mut self, max_repeats: Option<usize>)
+    {
+        self.max_repeats = max_repeats;
+    }
+
+    // pub fn get_forms(&self) -> Option<usize>
+    /// Gets the number of versions of the paper.
+    ///
+    /// # Output
+    /// `Option<usize>` - The number of versions, or `None` if every student has a paper of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::GenerationOptions;
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(4));
+    /// assert_eq!(options.get_forms(), Some(4));
+    /// ```
+    #[inline]
+    pub fn get_forms(&self) -> Option<usize>
+    {
+        self.forms
+    }
+
+    // pub fn set_forms(&mut self, forms: Option<usize>)
+    /// Sets the number of versions of the paper that the students are assigned to
+    /// instead of giving every student a paper of their own.
+    ///
+    /// The versions are assembled from the blueprint, avoiding the questions of
+    /// the latest sittings, and kept apart from each other as the seating keeps
+    /// adjacent students' sets apart. Then the students are assigned in their order,
+    /// each to the version that the fewest of their neighbours already have, then
+    /// that has the fewest of the questions they have seen before, and then that
+    /// the fewest students already have. Without any neighbours or history, this is round-robin.
+    ///
+    /// Every student still has a shuffled question set, which is a copy of their version.
+    /// The saved papers are one for each version, and so are the answer keys,
+    /// which list the students assigned to each version.
+    ///
+    /// # Arguments
+    /// * `forms` - The new number of versions, e.g. `Some(4)` for the versions A, B, C and D,
+    ///   or `None` for a paper for each student. `Some(0)` makes the generation fail
+    ///   with `GenerationError::NoForms`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationOptions, Generator, QBank, Question, Seating, Student, Students };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=12
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Question {}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let students: Students = (1..=6).map(|i| Student::new(format!("Student {}", i), i.to_string())).collect();
+    /// let blueprint = Blueprint::new_with_range(1, 12, 6);
+    ///
+    /// // Round-robin without a seating
+    /// let mut options = GenerationOptions::new_empty();
+    /// options.set_forms(Some(4));
+    /// let generator = Generator::new_with_options(&qbank, &blueprint, &students, &options, 2026).unwrap();
+    /// assert_eq!(generator.get_forms().len(), 4);
+    /// let assigned: Vec<usize> = (0..6).map(|i| generator.get_assigned_form(i).unwrap()).collect();
+    /// assert_eq!(assigned, vec![0, 1, 2, 3, 0, 1]);
+    /// assert_eq!(generator.get_shuffled_qsets()[4].count_same_position(&generator.get_forms()[0]), 6);
+    /// assert_eq!(generator.get_form_students(1).len(), 2);
+    /// // The versions do not have the same question at the same position.
+    /// assert_eq!(generator.get_forms()[0].count_same_position(&generator.get_forms()[1]), 0);
+    ///
+    /// // Two versions for a row of four students, neighbours never sharing one
+    /// options.set_forms(Some(2));
+    /// options.set_seating(Seating::new_with_grid(&[(1..=4).map(|i| i.to_string()).collect()]));
+    /// let generator = Generator::new_with_options(&qbank, &blueprint, &students[..4].to_vec(), &options, 2026).unwrap();
+    /// let assigned: Vec<usize> = (0..4).map(|i| generator.get_assigned_form(i).unwrap()).collect();
+    /// assert_eq!(assigned, vec![0, 1, 0, 1]);
+    /// ```
+    #[inline]
+    pub fn set_forms(&mut self, forms: Option<usize>)
+    {
+        self.forms = forms;
+    }
+}
@@ -8,7 +8,12 @@
 ///////////////////////////////////////////////////////////////////////////////
 
 
+use std::collections::{ HashMap, HashSet };
+
+use crate::Blueprint;
+use crate::Capacity;
 use crate::ChoiceAnswer;
+use crate::{ Constraint, Constraints };
 use crate::Header;
 use crate::Question;
 
@@ -18,6 +23,8 @@ pub struct QBank
 {
     header: Header,
     questions: Vec<Question>,
+    index: HashMap<u16, usize>,     // Maps the ID of a question to its position in `questions`.
+    constraints: Constraints,
 }
 
 impl QBank
@@ -41,6 +48,8 @@ impl QBank
         {
             header: Header::new_empty(),
             questions: Vec::new(),
+            index: HashMap::new(),
+            constraints: Constraints::new(),
         }
     }
 
@@ -63,6 +72,8 @@ impl QBank
         {
             header: Header::new_with_default(),
             questions: Vec::new(),
+            index: HashMap::new(),
+            constraints: Constraints::new(),
         }
     }
 
@@ -89,6 +100,8 @@ impl QBank
         {
             header,
             questions: Vec::new(),
+            index: HashMap::new(),
+            constraints: Constraints::new(),
         }
     }
 
@@ -167,16 +180,22 @@ impl QBank
     pub fn set_questions(&mut self, questions: Vec<Question>)
     {
         self.questions = questions;
+        self.index.clear();
+        for (position, question) in self.questions.iter().enumerate()
+            { self.index.entry(question.get_id()).or_insert(position); }
     }
 
     // pub fn get_question(&self, question_number: usize) -> Option<&Question>
-    /// Gets a reference to a `Question` by its 1-based index.
+    /// Gets a reference to a `Question` by its 1-based position in the bank.
+    ///
+    /// The position is not the ID of the question. Use `get_question_by_id()`
+    /// in order to find a question by its ID.
     ///
     /// # Arguments
-    /// * `question_number` - The 1-based index of the question to retrieve.
+    /// * `question_number` - The 1-based position of the question to retrieve.
     ///
     /// # Output
-    /// `Option<&Question>` - An optional reference to the `Question` at the specified index.
+    /// `Option<&Question>` - An optional reference to the `Question` at the specified position.
     ///
     /// # Examples
     /// ```
@@ -203,6 +222,95 @@ impl QBank
             { None }
     }
 
+    // pub fn get_question_by_id(&self, id: u16) -> Option<&Question>
+    /// Gets a reference to a `Question` by its ID.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the question to retrieve.
+    ///
+    /// # Output
+    /// `Option<&Question>` - An optional reference to the `Question` with the specified ID.
+    /// If two or more questions have the same ID, the first one is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// 
+    /// let mut qbank = QBank::new_empty();
+    /// qbank.push_question(Question::new(10, 1, 1, "Q10".to_string(), vec![]));
+    /// qbank.push_question(Question::new(3, 2, 1, "Q3".to_string(), vec![]));
+    /// assert_eq!(qbank.get_question_by_id(3).unwrap().get_question(), "Q3");
+    /// assert_eq!(qbank.get_question(2).unwrap().get_question(), "Q3");
+    /// assert!(qbank.get_question_by_id(2).is_none());
+    /// ```
+    pub fn get_question_by_id(&self, id: u16) -> Option<&Question>
+    {
+        let position = self.find_position(id)?;
+        Some(&self.questions[position])
+    }
+
+    // pub fn get_question_by_id_mut(&mut self, id: u16) -> Option<&mut Question>
+    /// Gets a mutable reference to a `Question` by its ID.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the question to retrieve.
+    ///
+    /// # Output
+    /// `Option<&mut Question>` - An optional mutable reference to the `Question` with the specified ID.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// 
+    /// let mut qbank = QBank::new_empty();
+    /// qbank.push_question(Question::new(7, 1, 1, "Old".to_string(), vec![]));
+    /// qbank.get_question_by_id_mut(7).unwrap().set_question("New".to_string());
+    /// assert_eq!(qbank.get_question_by_id(7).unwrap().get_question(), "New");
+    /// ```
+    pub fn get_question_by_id_mut(&mut self, id: u16) -> Option<&mut Question>
+    {
+        let position = self.find_position(id)?;
+        Some(&mut self.questions[position])
+    }
+
+    // pub fn find_duplicate_id(&self) -> Option<u16>
+    /// Finds an ID which two or more questions of the bank have in common.
+    ///
+    /// # Output
+    /// `Option<u16>` - The first duplicated ID in the order of the questions,
+    /// or `None` if all the IDs are unique.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// 
+    /// let mut qbank = QBank::new_empty();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![]));
+    /// qbank.push_question(Question::new(2, 2, 1, "Q2".to_string(), vec![]));
+    /// assert!(qbank.find_duplicate_id().is_none());
+    /// qbank.push_question(Question::new(1, 3, 1, "Q1 again".to_string(), vec![]));
+    /// assert_eq!(qbank.find_duplicate_id(), Some(1));
+    /// ```
+    pub fn find_duplicate_id(&self) -> Option<u16>
+    {
+        let mut seen = HashSet::new();
+        self.questions.iter().map(|q| q.get_id()).find(|id| !seen.insert(*id))
+    }
+
+    // fn find_position(&self, id: u16) -> Option<usize>
+    /// Finds the position of the question with the given ID in `questions`.
+    /// The index is checked first. Since a question may have got a new ID through
+    /// a mutable reference, a stale entry falls back to a linear search.
+    fn find_position(&self, id: u16) -> Option<usize>
+    {
+        match self.index.get(&id)
+        {
+            Some(&position) if self.questions.get(position).is_some_and(|q| q.get_id() == id)
+                => Some(position),
+            _ => self.questions.iter().position(|q| q.get_id() == id),
+        }
+    }
+
     // pub fn push_question(&mut self, question: Question)
     /// Adds a `Question` to the bank.
     ///
@@ -220,9 +328,148 @@ impl QBank
     #[inline]
     pub fn push_question(&mut self, question: Question)
     {
+        self.index.entry(question.get_id()).or_insert(self.questions.len());
         self.questions.push(question);
     }
 
+    // pub fn get_constraints(&self) -> &Constraints
+    /// Gets a reference to the constraints between the questions of the bank.
+    ///
+    /// # Output
+    /// `&Constraints` - A reference to the vector of `Constraint`s.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::QBank;
+    /// let qbank = QBank::new_with_default();
+    /// assert!(qbank.get_constraints().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_constraints(&self) -> &Constraints
+    {
+        &self.constraints
+    }
+
+    // pub fn set_constraints(&mut self, constraints: Constraints)
+    /// Sets the constraints between the questions of the bank.
+    ///
+    /// # Arguments
+    /// * `constraints` - The new vector of `Constraint`s.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Constraint, QBank };
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.set_constraints(vec![Constraint::Excludes { question: 12, other: 30 }]);
+    /// assert_eq!(qbank.get_constraints().len(), 1);
+    /// ```
+    #[inline]
+    pub fn set_constraints(&mut self, constraints: Constraints)
+    {
+        self.constraints = constraints;
+    }
+
+    // pub fn push_constraint(&mut self, constraint: Constraint)
+    /// Adds a `Constraint` between two questions of the bank.
+    ///
+    /// Every question set drawn from the bank keeps its constraints:
+    /// two questions which exclude each other are never selected together,
+    /// and a question which requires another is selected only together with it.
+    ///
+    /// # Arguments
+    /// * `constraint` - The `Constraint` to add.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use qrate::{ Constraint, QBank, Question, Student, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    /// qbank.push_constraint(Constraint::Excludes { question: 1, other: 2 });
+    /// qbank.push_constraint(Constraint::Requires { question: 4, required: 3 });
+    ///
+    /// // Question 4 is still drawn, and brings question 3 with it.
+    /// let mut follow_ups = 0;
+    /// for seed in 0..50
+    /// {
+    ///     let student = Student::new("Test".to_string(), seed.to_string());
+    ///     let shuffled_qset = ShuffledQSet::new_with_seed(&qbank, 1, 6, 3, &student, seed).unwrap();
+    ///     let ids: HashSet<u16> = shuffled_qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    ///     assert_eq!(ids.len(), 3);
+    ///     assert!(!(ids.contains(&1) && ids.contains(&2)));
+    ///     assert!(!ids.contains(&4) || ids.contains(&3));
+    ///     if ids.contains(&4)
+    ///         { follow_ups += 1; }
+    /// }
+    /// assert!(follow_ups > 10);
+    /// ```
+    #[inline]
+    pub fn push_constraint(&mut self, constraint: Constraint)
+    {
+        self.constraints.push(constraint);
+    }
+
+    // pub fn find_broken_constraint(&self, selected: &HashSet<u16>) -> Option<&Constraint>
+    /// Finds a constraint of the bank which the questions `selected` break.
+    ///
+    /// # Arguments
+    /// * `selected` - The IDs of the questions of a question set.
+    ///
+    /// # Output
+    /// `Option<&Constraint>` - The first broken constraint, or `None` if all of them are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use qrate::{ Constraint, QBank };
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_constraint(Constraint::Requires { question: 41, required: 40 });
+    /// assert!(qbank.find_broken_constraint(&HashSet::from([40, 41])).is_none());
+    /// assert_eq!(qbank.find_broken_constraint(&HashSet::from([41])), Some(&Constraint::Requires { question: 41, required: 40 }));
+    /// ```
+    pub fn find_broken_constraint(&self, selected: &HashSet<u16>) -> Option<&Constraint>
+    {
+        self.constraints.iter().find(|constraint| !constraint.is_kept_by(selected))
+    }
+
+    // pub fn get_required(&self, id: u16) -> Vec<u16>
+    /// Gets the IDs of the questions which the question `id` requires,
+    /// directly or through the questions it requires, as the constraints of the bank tell.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the question.
+    ///
+    /// # Output
+    /// `Vec<u16>` - The IDs of the required questions, the nearest first, without `id` itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Constraint, QBank };
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_constraint(Constraint::Requires { question: 42, required: 41 });
+    /// qbank.push_constraint(Constraint::Requires { question: 41, required: 40 });
+    /// qbank.push_constraint(Constraint::Requires { question: 40, required: 42 });
+    /// assert_eq!(qbank.get_required(42), vec![41, 40]);
+    /// assert!(qbank.get_required(12).is_empty());
+    /// ```
+    pub fn get_required(&self, id: u16) -> Vec<u16>
+    {
+        let mut required = vec![id];
+        let mut i = 0;
+        while i < required.len()
+        {
+            for constraint in &self.constraints
+            {
+                if let Constraint::Requires { question, required: other } = *constraint && question == required[i] && !required.contains(&other)
+                    { required.push(other); }
+            }
+            i += 1;
+        }
+        required.remove(0);
+        required
+    }
+
     // pub fn get_choice(&self, question_number: usize, choice_number: usize) -> Option<&ChoiceAnswer>
     /// Gets a reference to a choice `ChoiceAnswer` by question number and choice number (both 1-based).
     ///
@@ -274,4 +521,96 @@ impl QBank
     {
         self.get_questions().iter().map(|q| q.get_choices().len()).max().unwrap_or(0)
     }
+
+    // pub fn analyze(&self, start: u16, end: u16, selected: usize, number_of_students: usize) -> Capacity
+    /// Analyses how many distinct question sets the bank can support when each
+    /// of the students is given `selected` questions of the IDs from `start` to `end`.
+    ///
+    /// # Arguments
+    /// * `start` - The smallest question ID to include (inclusive).
+    /// * `end` - The largest question ID to include (inclusive).
+    /// * `selected` - The number of questions to be selected for each student.
+    /// * `number_of_students` - The number of students who take the examination.
+    ///
+    /// # Output
+    /// `Capacity` - The number and the sizes of the groups, the number of possible sets,
+    /// the expected overlap of two students and whether the selection can be made.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=51    // Questions 1 to 51 fall into 8 groups.
+    ///     { qbank.push_question(Question::new(id, id % 8 + 1, 1, format!("Q{}", id), vec![])); }
+    ///
+    /// let capacity = qbank.analyze(1, 51, 10, 40);
+    /// assert_eq!(capacity.get_groups(), 8);
+    /// assert!(!capacity.is_feasible());   // Two more groups are needed.
+    /// assert!(qbank.analyze(1, 51, 8, 40).is_feasible());
+    /// ```
+    #[inline]
+    pub fn analyze(&self, start: u16, end: u16, selected: usize, number_of_students: usize) -> Capacity
+    {
+        Capacity::new_with_blueprint(self, &Blueprint::new_with_range(start, end, selected), number_of_students)
+    }
+
+    // pub fn analyze_with_quotas(&self, start: u16, end: u16, quotas: &[(u8, usize)], number_of_students: usize) -> Capacity
+    /// Analyses how many distinct question sets the bank can support when each
+    /// of the students is given, for each pair `(category, count)` of `quotas`,
+    /// `count` questions of that category among the IDs from `start` to `end`.
+    ///
+    /// # Arguments
+    /// * `start` - The smallest question ID to include (inclusive).
+    /// * `end` - The largest question ID to include (inclusive).
+    /// * `quotas` - The number of questions to be selected from each category.
+    /// * `number_of_students` - The number of students who take the examination.
+    ///
+    /// # Output
+    /// `Capacity` - The result of the analysis, which tells also whether the quotas can be met.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, if id <= 4 { 1 } else { 2 }, format!("Q{}", id), vec![])); }
+    ///
+    /// let capacity = qbank.analyze_with_quotas(1, 6, &[(1, 2), (2, 1)], 30);
+    /// assert_eq!(capacity.get_possible_sets(), 6 * 2);
+    /// assert!(!qbank.analyze_with_quotas(1, 6, &[(3, 1)], 30).is_feasible());
+    /// ```
+    #[inline]
+    pub fn analyze_with_quotas(&self, start: u16, end: u16, quotas: &[(u8, usize)], number_of_students: usize) -> Capacity
+    {
+        Capacity::new_with_blueprint(self, &Blueprint::new_with_quotas(start, end, quotas), number_of_students)
+    }
+
+    // pub fn analyze_with_blueprint(&self, blueprint: &Blueprint, number_of_students: usize) -> Capacity
+    /// Analyses how many distinct question sets the bank can support for `blueprint`,
+    /// as `Capacity::new_with_blueprint()` does.
+    ///
+    /// # Arguments
+    /// * `blueprint` - A reference to the `Blueprint` that every student's set is assembled from.
+    /// * `number_of_students` - The number of students who take the examination.
+    ///
+    /// # Output
+    /// `Capacity` - The result of the analysis.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Question, Section };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![])); }
+    ///
+    /// let blueprint = Blueprint::new(vec![Section::new_with_range(1, 10, 2), Section::new_with_range(11, 20, 3)]);
+    /// let capacity = qbank.analyze_with_blueprint(&blueprint, 30);
+    /// assert_eq!(capacity.get_groups(), 20);
+    /// assert_eq!(capacity.get_possible_sets(), 45 * 120);
+    /// ```
+    #[inline]
+    pub fn analyze_with_blueprint(&self, blueprint: &Blueprint, number_of_students: usize) -> Capacity
+    {
+        Capacity::new_with_blueprint(self, blueprint, number_of_students)
+    }
 }
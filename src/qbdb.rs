@@ -8,14 +8,17 @@
 ///////////////////////////////////////////////////////////////////////////////
 
 
-use calamine::{ DataType, Reader, open_workbook_auto };
+use calamine::DataType;
 use rust_xlsxwriter::{ Format, FormatBorder, Workbook };
 
+use crate::Error;
 use crate::Header;
 use crate::QBank;
 use crate::SQLiteDB;
 use crate::Excel;
-use crate::{ Choices, Question };
+use crate::{ Choices, Constraint, Question };
+use crate::{ choices_to_text, text_to_choices };
+use crate::template::{ parameters_to_text, text_to_parameters };
 
 /// A trait defining the database operations for a Question Bank (`QBank`).
 ///
@@ -23,7 +26,7 @@ use crate::{ Choices, Question };
 /// backend implementations (e.g., SQLite, flat files).
 pub trait QBDB
 {
-    // fn open(path: String, extention: &str) -> Option<Self> where Self: Sized;
+    // fn open(path: String) -> Result<Self, Error> where Self: Sized;
     /// Opens a connection to the question bank database.
     /// If the path has no extension, proper extention is appended.
     ///
@@ -32,14 +35,15 @@ pub trait QBDB
     /// * `extention` - The file extension to append.
     ///
     /// # Output
-    /// `Option<Self>` - An optional `Self` instance if the connection is successful.
+    /// `Result<Self, Error>` - A `Self` instance if the connection is successful,
+    /// or an `Error` telling why it is not.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
     /// use qrate::{ SQLiteDB, QBDB };
     ///
     /// let db = SQLiteDB::open(":memory:".to_string());
-    /// assert!(db.is_some());
+    /// assert!(db.is_ok());
     /// ```
     ///
     /// # Example 2 for Excel
@@ -47,11 +51,11 @@ pub trait QBDB
     /// use qrate::{ Excel, QBDB };
     ///
     /// let excel = Excel::open("test_quiz.qb.xlsx".to_string());
-    /// assert!(excel.is_some());
+    /// assert!(excel.is_ok());
     /// ```
-    fn open(path: String) -> Option<Self> where Self: Sized;
+    fn open(path: String) -> Result<Self, Error> where Self: Sized;
 
-    // fn make_tables(&self, categories: u8, choices: u8) -> Result<(), String>
+    // fn make_tables(&self, categories: u8, choices: u8) -> Result<(), Error>
     /// Creates the necessary tables in the database.
     ///
     /// # Arguments
@@ -59,7 +63,7 @@ pub trait QBDB
     /// * `choices` - The number of choice columns to create in Questions table.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error string on failure.
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
     /// 
     /// # Features
     /// If `choices` is zero, this method will not make Questions table.
@@ -85,13 +89,13 @@ pub trait QBDB
     /// assert!(Path::new(file_path).exists());
     /// std::fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn make_tables(&self, categories: u8, choices: u8) -> Result<(), String>;
+    fn make_tables(&self, categories: u8, choices: u8) -> Result<(), Error>;
 
-    // fn read_header(&self) -> Option<Header>
+    // fn read_header(&self) -> Result<Header, Error>
     /// Reads the `Header` data from the database.
     ///
     /// # Output
-    /// `Option<Header>` - An `Option<Header>` which is `Some(Header)` on success, or `None` if not found or on error.
+    /// `Result<Header, Error>` - The `Header` on success, or an `Error` if it is not found or cannot be read.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -102,7 +106,7 @@ pub trait QBDB
     /// db.write_header_with_default().unwrap();
     ///
     /// let header = db.read_header();
-    /// assert!(header.is_some());
+    /// assert!(header.is_ok());
     /// assert_eq!(header.unwrap().get_title(), "Examination");
     /// ```
     ///
@@ -116,17 +120,17 @@ pub trait QBDB
     /// excel.write_header_with_default().unwrap();
     ///
     /// let header = excel.read_header();
-    /// assert!(header.is_some());
+    /// assert!(header.is_ok());
     /// assert_eq!(header.unwrap().get_title(), "Examination");
     /// fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn read_header(&self) -> Option<Header>;
+    fn read_header(&self) -> Result<Header, Error>;
 
-    // fn write_header_with_default(&self) -> Result<(), String>
+    // fn write_header_with_default(&self) -> Result<(), Error>
     /// Writes a default `Header` to the database.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -151,16 +155,16 @@ pub trait QBDB
     /// assert!(Path::new(file_path).exists());
     /// fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn write_header_with_default(&mut self) -> Result<(), String>;
+    fn write_header_with_default(&mut self) -> Result<(), Error>;
 
-    // fn write_header(&self, header: &Header) -> Result<(), String>
+    // fn write_header(&self, header: &Header) -> Result<(), Error>
     /// Writes a given `Header` to the database.
     ///
     /// # Arguments
     /// * `header` - A reference to the `Header` to be written to the database.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -202,13 +206,16 @@ pub trait QBDB
     /// assert_eq!(read_header.get_title(), "Math Exam");
     /// fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn write_header(&mut self, header: &Header) -> Result<(), String>;
+    fn write_header(&mut self, header: &Header) -> Result<(), Error>;
 
-    // fn read_qbank(&self) -> Option<QBank>
+    // fn read_qbank(&self) -> Result<QBank, Error>
     /// Reads the entire `QBank` (header and all questions) from the database.
     ///
     /// # Output
-    /// `Option<QBank>` - An `Option<QBank>` which is `Some(QBank)` on success, or `None` on failure.
+    /// `Result<QBank, Error>` - The `QBank` on success, or an `Error` on failure.
+    /// A question bank in which two or more questions have the same ID is
+    /// an `Error::Validation`, and a table or a sheet laid out otherwise than
+    /// expected is an `Error::Schema`.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -225,7 +232,7 @@ pub trait QBDB
     /// db.write_qbank(&qbank_to_write).unwrap();
     ///
     /// let qbank_read = db.read_qbank();
-    /// assert!(qbank_read.is_some());
+    /// assert!(qbank_read.is_ok());
     /// let read_bank = qbank_read.unwrap();
     /// assert_eq!(read_bank.get_questions().len(), 1);
     /// assert_eq!(read_bank.get_header().get_title(), "Examination");
@@ -233,27 +240,31 @@ pub trait QBDB
     ///
     /// # Example 2 for Excel
     /// ```
-    /// use qrate::{ Excel, QBDB, QBank, Question, Choices };
+    /// use qrate::{ Error, Excel, QBDB, QBank, Question, Choices };
     /// use std::fs;
     ///
-    /// let file_path = "test_read_qbank.qb.xlsx";
-    /// let mut excel = Excel::open(file_path.to_string()).unwrap();
+    /// let mut excel = Excel::open("test_read_qbank".to_string()).unwrap();
     ///
     /// let mut qbank_to_write = QBank::new_with_default();
     /// let choices = vec![("Paris".to_string(), true), ("Berlin".to_string(), false)];
-    /// qbank_to_write.push_question(Question::new(1, 1, 1, "Capital of France?".to_string(), choices));
+    /// qbank_to_write.push_question(Question::new(1, 1, 1, "Capital of France?".to_string(), choices.clone()));
     /// excel.write_qbank(&qbank_to_write).unwrap();
     ///
     /// let qbank_read = excel.read_qbank();
-    /// assert!(qbank_read.is_some());
+    /// assert!(qbank_read.is_ok());
     /// let read_bank = qbank_read.unwrap();
     /// assert_eq!(read_bank.get_questions().len(), 1);
     /// assert_eq!(read_bank.get_header().get_title(), "Examination");
-    /// fs::remove_file(file_path).unwrap(); // Clean up
+    ///
+    /// // Two questions with the same ID are rejected.
+    /// qbank_to_write.push_question(Question::new(1, 2, 1, "Capital of Germany?".to_string(), choices));
+    /// excel.write_qbank(&qbank_to_write).unwrap();
+    /// assert!(matches!(excel.read_qbank(), Err(Error::Validation(_))));
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
     /// ```
-    fn read_qbank(&self) -> Option<QBank>;
+    fn read_qbank(&self) -> Result<QBank, Error>;
 
-    // fn write_qbank(&mut self, qbank: &QBank) -> Result<(), String>
+    // fn write_qbank(&mut self, qbank: &QBank) -> Result<(), Error>
     /// Writes an entire `QBank` (header and all questions) to the database.
     ///
     /// Note: This typically writes the questions. The header should be written separately
@@ -263,7 +274,7 @@ pub trait QBDB
     /// * `qbank` - A reference to the `QBank` to be written to the database.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -309,12 +320,116 @@ pub trait QBDB
     ///
     /// fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn write_qbank(&mut self, qbank: &QBank) -> Result<(), String>;
+    ///
+    /// # Example 3 for pinned and ordered choices
+    /// ```
+    /// use qrate::{ SQLiteDB, Excel, QBDB, QBank, Question };
+    /// use std::fs;
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// let choices = vec![("Red".to_string(), false), ("Blue".to_string(), false), ("All of the above".to_string(), true)];
+    /// let mut question = Question::new(1, 1, 1, "Which colours are primary?".to_string(), choices);
+    /// question.set_pinned(vec![3]);
+    /// question.set_ordered(vec![1, 2]);
+    /// qbank.push_question(question);
+    ///
+    /// let mut db = SQLiteDB::open("test_write_pinned".to_string()).unwrap();
+    /// db.write_qbank(&qbank).unwrap();
+    /// assert_eq!(db.read_qbank().unwrap().get_question(1).unwrap().get_pinned(), &vec![3]);
+    /// assert_eq!(db.read_qbank().unwrap().get_question(1).unwrap().get_ordered(), &vec![1, 2]);
+    /// fs::remove_file(db.get_path()).unwrap(); // Clean up
+    ///
+    /// let mut excel = Excel::open("test_write_pinned".to_string()).unwrap();
+    /// excel.write_qbank(&qbank).unwrap();
+    /// assert_eq!(excel.read_qbank().unwrap().get_question(1).unwrap().get_pinned(), &vec![3]);
+    /// assert_eq!(excel.read_qbank().unwrap().get_question(1).unwrap().get_ordered(), &vec![1, 2]);
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 4 for sampled distractors
+    /// ```
+    /// use qrate::{ SQLiteDB, Excel, QBDB, QBank, Question };
+    /// use std::fs;
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// let choices = (1..=8).map(|i| (format!("Choice {}", i), i == 1)).collect();
+    /// let mut question = Question::new(1, 1, 1, "Which is correct?".to_string(), choices);
+    /// question.set_shown_distractors(Some(3));
+    /// qbank.push_question(question);
+    /// qbank.push_question(Question::new(2, 2, 1, "Which is true?".to_string(), vec![("Yes".to_string(), true), ("No".to_string(), false)]));
+    ///
+    /// let mut db = SQLiteDB::open("test_write_distractors".to_string()).unwrap();
+    /// db.write_qbank(&qbank).unwrap();
+    /// let read_bank = db.read_qbank().unwrap();
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_shown_distractors(), Some(3));
+    /// assert_eq!(read_bank.get_question(2).unwrap().get_shown_distractors(), None);
+    /// fs::remove_file(db.get_path()).unwrap(); // Clean up
+    ///
+    /// let mut excel = Excel::open("test_write_distractors".to_string()).unwrap();
+    /// excel.write_qbank(&qbank).unwrap();
+    /// let read_bank = excel.read_qbank().unwrap();
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_choices().len(), 8);
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_shown_distractors(), Some(3));
+    /// assert_eq!(read_bank.get_question(2).unwrap().get_shown_distractors(), None);
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 5 for constraints
+    /// ```
+    /// use qrate::{ Constraint, SQLiteDB, Excel, QBDB, QBank, Question };
+    /// use std::fs;
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in [12, 30, 40, 41]
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// qbank.push_constraint(Constraint::Excludes { question: 12, other: 30 });
+    /// qbank.push_constraint(Constraint::Requires { question: 41, required: 40 });
+    ///
+    /// let mut db = SQLiteDB::open("test_write_constraints".to_string()).unwrap();
+    /// db.write_qbank(&qbank).unwrap();
+    /// assert_eq!(db.read_qbank().unwrap().get_constraints(), qbank.get_constraints());
+    /// fs::remove_file(db.get_path()).unwrap(); // Clean up
+    ///
+    /// let mut excel = Excel::open("test_write_constraints".to_string()).unwrap();
+    /// excel.write_qbank(&qbank).unwrap();
+    /// assert_eq!(excel.read_qbank().unwrap().get_constraints(), qbank.get_constraints());
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    ///
+    /// # Example 6 for templated questions
+    /// ```
+    /// use qrate::{ Parameter, SQLiteDB, Excel, QBDB, QBank, Question };
+    /// use std::fs;
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// let mut question = Question::new(1, 1, 1, "What is {a} * {b}?".to_string(),
+    ///         vec![("{a * b}".to_string(), true), ("{a + b}".to_string(), false), ("{a * b - 1}".to_string(), false)]);
+    /// question.set_parameters(vec![Parameter::new("a".to_string(), 2, 9), Parameter::new("b".to_string(), -9, -2)]);
+    /// qbank.push_question(question);
+    /// qbank.push_question(Question::new(2, 2, 1, "Which is true?".to_string(), vec![("Yes".to_string(), true), ("No".to_string(), false)]));
+    ///
+    /// let mut db = SQLiteDB::open("test_write_parameters".to_string()).unwrap();
+    /// db.write_qbank(&qbank).unwrap();
+    /// let read_bank = db.read_qbank().unwrap();
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_parameters(), qbank.get_question(1).unwrap().get_parameters());
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_choice(3).unwrap().0, "{a * b - 1}");
+    /// assert!(!read_bank.get_question(2).unwrap().is_parameterized());
+    /// fs::remove_file(db.get_path()).unwrap(); // Clean up
+    ///
+    /// let mut excel = Excel::open("test_write_parameters".to_string()).unwrap();
+    /// excel.write_qbank(&qbank).unwrap();
+    /// let read_bank = excel.read_qbank().unwrap();
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_choices().len(), 3);
+    /// assert_eq!(read_bank.get_question(1).unwrap().get_parameters(), qbank.get_question(1).unwrap().get_parameters());
+    /// assert!(!read_bank.get_question(2).unwrap().is_parameterized());
+    /// fs::remove_file(excel.get_path()).unwrap(); // Clean up
+    /// ```
+    fn write_qbank(&mut self, qbank: &QBank) -> Result<(), Error>;
 }
 
 impl QBDB for SQLiteDB
 {
-    // fn open(path: String) -> Option<Self> where Self: Sized
+    // fn open(path: String) -> Result<Self, Error> where Self: Sized
     /// Implements `open` for `SQLiteDB`.
     /// Appends `.qbdb` to the path if no extension is present and opens a connection.
     ///
@@ -322,27 +437,28 @@ impl QBDB for SQLiteDB
     /// * `path` - The file path for the database.
     ///
     /// # Output
-    /// `Option<SQLiteDB>` - An optional `SQLiteDB` instance if the connection is successful.
+    /// `Result<SQLiteDB, Error>` - An `SQLiteDB` instance if the connection is successful.
     #[inline]
-    fn open(path: String) -> Option<Self>
+    fn open(path: String) -> Result<Self, Error>
     where Self: Sized
     {
         SQLiteDB::open_with_ext(path, "qbdb")
     }
 
-    // fn make_tables(&self, categories: u8, choices: u8) -> Result<(), String>
+    // fn make_tables(&self, categories: u8, choices: u8) -> Result<(), Error>
     /// Creates tables for `SQLiteDB`.
     ///
     /// Dynamically constructs `CREATE TABLE` SQL statements for `tblHeader` and `tblQuestions`
-    /// based on the number of categories and choices required.
+    /// based on the number of categories and choices required, and makes `tblConstraints`
+    /// for the constraints between the questions together with `tblQuestions`.
     ///
     /// # Arguments
     /// * `categories` - The number of category columns to create in `tblHeader`.
     /// * `choices` - The number of choice columns to create in `tblQuestions`.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
-    fn make_tables(&self, categories: u8, choices: u8) -> Result<(), String>
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    fn make_tables(&self, categories: u8, choices: u8) -> Result<(), Error>
     {
         let mut sql = r#"CREATE TABLE IF NOT EXISTS tblHeader (
     title	TEXT NOT NULL,
@@ -352,8 +468,7 @@ impl QBDB for SQLiteDB
         for i in 1..=categories
             { sql += format!(",\n\tcategory{}\tTEXT NOT NULL", i).as_str(); }
         sql += "\n);";
-        if let Err(e) = self.conn.execute(sql.as_str(), [])
-            { return Err(format!("Failed to create table tblHeader!! {}", e)) }
+        self.conn.execute(sql.as_str(), [])?;
         if choices == 0
             { return Ok(()); }
 
@@ -367,25 +482,41 @@ impl QBDB for SQLiteDB
             sql += &format!(",\n\tchoice{}_text\tTEXT", i);
             sql += &format!(",\n\tchoice{}_is_answer\tBOOLEAN", i);
         }
+        sql += ",\n\tpinned\tTEXT";
+        sql += ",\n\tordered\tTEXT";
+        sql += ",\n\tdistractors\tINTEGER";
+        sql += ",\n\tparameters\tTEXT";
         sql += ",\n\tPRIMARY KEY(id)\n);";
-        match self.conn.execute(sql.as_str(), [])
-        {
-            Ok(_) => Ok(()),
-            Err(e) => Err(format!("Failed to create table tblQuestions!! {}", e)),
-        }
+        self.conn.execute(sql.as_str(), [])?;
+
+        // Tables made by earlier versions lack the columns added since.
+        for column in ["pinned", "ordered"]
+            { self.add_column_if_missing("tblQuestions", column, "TEXT")?; }
+        self.add_column_if_missing("tblQuestions", "distractors", "INTEGER")?;
+        self.add_column_if_missing("tblQuestions", "parameters", "TEXT")?;
+
+        let sql = r#"CREATE TABLE IF NOT EXISTS tblConstraints (
+    question	INTEGER NOT NULL,
+    kind	    TEXT NOT NULL,
+    other	    INTEGER NOT NULL
+);"#;
+        self.conn.execute(sql, [])?;
+        Ok(())
     }
 
-    // fn read_header(&self) -> Option<Header>
+    // fn read_header(&self) -> Result<Header, Error>
     /// Implements `read_header` for `SQLiteDB`.
     ///
-    /// Queries the `tblHeader` table and maps the first row to a `Header` struct.
+    /// Checks the columns of the `tblHeader` table, queries it,
+    /// and maps the first row to a `Header` struct.
     ///
     /// # Output
-    /// `Option<Header>` - An optional `Header` containing the header data from the database.
-    fn read_header(&self) -> Option<Header>
+    /// `Result<Header, Error>` - The `Header` containing the header data from the database.
+    fn read_header(&self) -> Result<Header, Error>
     {
-        let mut stmt = self.conn.prepare("SELECT * FROM tblHeader;").ok()?;
-        let vec_header = stmt.query_map([], |row| {
+        self.check_columns("tblHeader", &["title", "name", "id", "notice"])?;
+        let mut stmt = self.conn.prepare("SELECT * FROM tblHeader;")?;
+        let mut vec_header = stmt.query_map([], |row| {
             let mut categories = Vec::new();
             let mut i = 4;
             while let Ok(c) = row.get(i)
@@ -394,29 +525,28 @@ impl QBDB for SQLiteDB
                 i += 1;
             }
             Ok(Header::new(row.get(0)?, row.get(1)?, row.get(2)?, categories, row.get(3)?))
-        }).ok()?;
+        })?;
 
-        for info in vec_header
+        match vec_header.next()
         {
-            if let Ok(ff) = info
-                { return Some(ff); }
+            Some(header) => Ok(header?),
+            None => Err(Error::Validation("tblHeader has no header row".to_string())),
         }
-        None
     }
 
-    // fn write_header_with_default(&self) -> Result<(), String>
+    // fn write_header_with_default(&self) -> Result<(), Error>
     /// Implements `write_header_with_default` for `SQLiteDB`.
     /// Creates a default `Header` and calls `write_header`.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
     #[inline]
-    fn write_header_with_default(&mut self) -> Result<(), String>
+    fn write_header_with_default(&mut self) -> Result<(), Error>
     {
         self.write_header(&Header::new_with_default())
     }
 
-    // fn write_header(&self, header: &Header) -> Result<(), String>
+    // fn write_header(&self, header: &Header) -> Result<(), Error>
     /// Implements `write_header` for `SQLiteDB`.
     ///
     /// Constructs and executes an `INSERT` statement for the `tblHeader` table.
@@ -426,40 +556,39 @@ impl QBDB for SQLiteDB
     /// * `header` - A reference to the `Header` to be written to the database.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
-    fn write_header(&mut self, header: &Header) -> Result<(), String>
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    fn write_header(&mut self, header: &Header) -> Result<(), Error>
     {
-        let _ = self.make_tables(header.get_categories().len() as u8, 0);
+        self.make_tables(header.get_categories().len() as u8, 0)?;
         let length = header.get_categories().len();
-        let mut sql = format!("INSERT INTO tblHeader values (?1, ?2, ?3, ?4");
+        let mut sql = "INSERT INTO tblHeader values (?1, ?2, ?3, ?4".to_string();
         for i in 5..(5 + length)
             { sql += format!(", ?{}", i).as_str(); }
         sql += ");";
 
-        let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-        params.push(header.get_title());
-        params.push(header.get_name());
-        params.push(header.get_id());
-        params.push(header.get_notice());
+        let mut params: Vec<&dyn rusqlite::ToSql> = vec![header.get_title(), header.get_name(), header.get_id(), header.get_notice()];
         for category in header.get_categories()
             { params.push(category); }
 
-        self.conn.execute(sql.as_str(), &params[..]).map_err(|e| e.to_string())?;
+        self.conn.execute(sql.as_str(), &params[..])?;
         Ok(())
     }
 
-    // fn read_qbank(&self) -> Option<QBank>
+    // fn read_qbank(&self) -> Result<QBank, Error>
     /// Implements `read_qbank` for `SQLiteDB`.
     ///
-    /// First, it reads the header using `read_header`. Then, it queries the `tblQuestions` table,
-    /// maps each row to a `Question` struct, and collects them into a new `QBank`.
+    /// First, it reads the header using `read_header`. Then, it checks the columns of
+    /// the `tblQuestions` table, queries it, maps each row to a `Question` struct,
+    /// and collects them into a new `QBank`. The constraints are read from `tblConstraints`,
+    /// which a database made before constraints were stored does not have.
     ///
     /// # Output
-    /// `Option<QBank>` - An optional `QBank` containing the header and all questions from the database.
-    fn read_qbank(&self) -> Option<QBank>
+    /// `Result<QBank, Error>` - The `QBank` containing the header and all questions from the database.
+    fn read_qbank(&self) -> Result<QBank, Error>
     {
         let header = self.read_header()?;
-        let mut stmt = self.conn.prepare("SELECT * FROM tblQuestions;").ok()?;
+        self.check_columns("tblQuestions", &["id", "modum", "category", "question"])?;
+        let mut stmt = self.conn.prepare("SELECT * FROM tblQuestions;")?;
         let vec_question = stmt.query_map([], |row| {
             let id: u16 = row.get(0)?;
             let group: u16 = row.get(1)?;
@@ -479,21 +608,41 @@ impl QBDB for SQLiteDB
                     { break; }
                 idx += 2;
             }
-            Ok(Question::new(id, group, category, question, choices))
-        }).ok()?;
+            let mut question = Question::new(id, group, category, question, choices);
+            let pinned: Option<String> = row.get("pinned").unwrap_or(None);
+            question.set_pinned(pinned.and_then(|text| text_to_choices(&text)).unwrap_or_default());
+            let ordered: Option<String> = row.get("ordered").unwrap_or(None);
+            question.set_ordered(ordered.and_then(|text| text_to_choices(&text)).unwrap_or_default());
+            question.set_shown_distractors(row.get("distractors").unwrap_or(None));
+            let parameters: Option<String> = row.get("parameters").unwrap_or(None);
+            question.set_parameters(parameters.and_then(|text| text_to_parameters(&text)).unwrap_or_default());
+            Ok(question)
+        })?;
 
         let mut question_bank = QBank::new_with_header(header);
         for result_question in vec_question
+            { question_bank.push_question(result_question?); }
+        if let Some(id) = question_bank.find_duplicate_id()
+            { return Err(Error::Validation(format!("question ID {} is used by two or more questions", id))); }
+
+        let has_constraints: bool = self.conn.query_row("SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'tblConstraints';", [], |row| row.get(0))?;
+        if has_constraints
         {
-            if let Ok(question) = result_question
-                { question_bank.push_question(question); }
-            else
-                { return None; }
+            self.check_columns("tblConstraints", &["question", "kind", "other"])?;
+            let mut stmt = self.conn.prepare("SELECT question, kind, other FROM tblConstraints;")?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, u16>(0)?, row.get::<_, String>(1)?, row.get::<_, u16>(2)?)))?;
+            for row in rows
+            {
+                let (question, kind, other) = row?;
+                let constraint = Constraint::new_with_kind(&kind, question, other)
+                    .ok_or_else(|| Error::Validation(format!("the constraint of question {} has the unknown kind \"{}\"", question, kind)))?;
+                question_bank.push_constraint(constraint);
+            }
         }
-        Some(question_bank)
+        Ok(question_bank)
     }
 
-    // fn write_qbank(&mut self, qbank: &QBank) -> Result<(), String>
+    // fn write_qbank(&mut self, qbank: &QBank) -> Result<(), Error>
     /// Implements `write_qbank` for `SQLiteDB`.
     ///
     /// Iterates through the questions in the provided `QBank` and inserts each one
@@ -504,16 +653,16 @@ impl QBDB for SQLiteDB
     /// * `qbank` - A reference to the `QBank` containing questions to be written to the database.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
-    fn write_qbank(&mut self, qbank: &QBank) -> Result<(), String>
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    fn write_qbank(&mut self, qbank: &QBank) -> Result<(), Error>
     {
         let categories = qbank.get_header().get_categories().len() as u8;
         // 1. Determine the maximum number of choices in the entire bank to create a uniform SQL statement.
         let max_choices = qbank.get_max_choices();
-        let _ = self.make_tables(categories, max_choices as u8);
-        let _ = self.write_header(qbank.get_header());
+        self.make_tables(categories, max_choices as u8)?;
+        self.write_header(qbank.get_header())?;
         if qbank.get_questions().is_empty()   // Nothing to write
-            { return Err("Empty QBank".to_string()); }
+            { return Err(Error::Validation("the question bank has no question".to_string())); }
 
         // 2. Build the SQL statement dynamically.
         let mut sql = "INSERT INTO tblQuestions (id, modum, category, question".to_string();
@@ -523,16 +672,15 @@ impl QBDB for SQLiteDB
             sql += &format!(", choice{}_text, choice{}_is_answer", i, i);
             values += ", ?, ?";
         }
+        sql += ", pinned, ordered, distractors, parameters";
+        values += ", ?, ?, ?, ?";
         sql += &format!(") VALUES ({});", values);
 
         // 3. Iterate through questions and execute the INSERT statement.
         for elem in qbank.get_questions()
         {
-            let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-            params.push(Box::new(elem.get_id()));
-            params.push(Box::new(elem.get_group()));
-            params.push(Box::new(elem.get_category()));
-            params.push(Box::new(elem.get_question().clone()));
+            let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(elem.get_id()), Box::new(elem.get_group()),
+                                                                 Box::new(elem.get_category()), Box::new(elem.get_question().clone())];
 
             let choices = elem.get_choices();
             for i in 0..max_choices
@@ -548,9 +696,20 @@ impl QBDB for SQLiteDB
                     params.push(Box::new(rusqlite::types::Value::Null));
                 }
             }
+            params.push(Box::new(choices_to_text(elem.get_pinned())));
+            params.push(Box::new(choices_to_text(elem.get_ordered())));
+            params.push(Box::new(elem.get_shown_distractors()));
+            params.push(Box::new(parameters_to_text(elem.get_parameters())));
 
             let params_for_exec: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-            self.conn.execute(&sql, &params_for_exec[..]).map_err(|e| e.to_string())?;
+            self.conn.execute(&sql, &params_for_exec[..])?;
+        }
+
+        // 4. Write the constraints between the questions.
+        for constraint in qbank.get_constraints()
+        {
+            self.conn.execute("INSERT INTO tblConstraints (question, kind, other) VALUES (?1, ?2, ?3);",
+                rusqlite::params![constraint.get_question(), constraint.get_kind(), constraint.get_other()])?;
         }
         Ok(())
     }
@@ -560,59 +719,68 @@ impl QBDB for SQLiteDB
 
 impl QBDB for Excel
 {
-    // fn open(path: String) -> Option<Self> where Self: Sized
+    // fn open(path: String) -> Result<Self, Error> where Self: Sized
     /// Implements `open` for `Excel`.
     /// Appends `.qb.xlsx` to the path if no extension is present.
     #[inline]
-    fn open(path: String) -> Option<Self>
+    fn open(path: String) -> Result<Self, Error>
     where Self: Sized
     {
         Excel::open_with_ext(path, "qb.xlsx")
     }
 
-    // fn make_tables(&self, choices: u8) -> Result<(), String>
+    // fn make_tables(&self, choices: u8) -> Result<(), Error>
     /// Creates sheets for `Excel`.
-    fn make_tables(&self, _categories: u8, choices: u8) -> Result<(), String>
+    fn make_tables(&self, _categories: u8, choices: u8) -> Result<(), Error>
     {
         let mut workbook = Workbook::new();
         let bold_border_format = Format::new().set_bold().set_border(FormatBorder::Thin);
 
         // 1. Create "Header" sheet
-        let header_sheet = workbook.add_worksheet().set_name("Header").map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(0, 0, "Title", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(1, 0, "Name", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(2, 0, "ID", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(3, 0, "Notice", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(4, 0, "Categories", &bold_border_format).map_err(|e| e.to_string())?;
+        let header_sheet = workbook.add_worksheet().set_name("Header")?;
+        header_sheet.write_string_with_format(0, 0, "Title", &bold_border_format)?;
+        header_sheet.write_string_with_format(1, 0, "Name", &bold_border_format)?;
+        header_sheet.write_string_with_format(2, 0, "ID", &bold_border_format)?;
+        header_sheet.write_string_with_format(3, 0, "Notice", &bold_border_format)?;
+        header_sheet.write_string_with_format(4, 0, "Categories", &bold_border_format)?;
 
         
         // 2. Create "Questions" sheet
         if choices != 0
         {
-            let questions_sheet = workbook.add_worksheet().set_name("Questions").map_err(|e| e.to_string())?;
-            questions_sheet.write_string_with_format(0, 0, "ID", &bold_border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_string_with_format(0, 1, "Group", &bold_border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_string_with_format(0, 2, "Category", &bold_border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_string_with_format(0, 3, "Question", &bold_border_format).map_err(|e| e.to_string())?;
+            let questions_sheet = workbook.add_worksheet().set_name("Questions")?;
+            questions_sheet.write_string_with_format(0, 0, "ID", &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, 1, "Group", &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, 2, "Category", &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, 3, "Question", &bold_border_format)?;
 
             let mut current_col = 4;
             for i in 1..=choices
             {
-                questions_sheet.write_string_with_format(0, current_col, &format!("Choice{}", i), &bold_border_format).map_err(|e| e.to_string())?;
+                questions_sheet.write_string_with_format(0, current_col, format!("Choice{}", i), &bold_border_format)?;
                 current_col += 1;
-                questions_sheet.write_string_with_format(0, current_col, &format!("IsAnswer{}", i), &bold_border_format).map_err(|e| e.to_string())?;
+                questions_sheet.write_string_with_format(0, current_col, format!("IsAnswer{}", i), &bold_border_format)?;
                 current_col += 1;
             }
+            questions_sheet.write_string_with_format(0, current_col, "Pinned", &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, current_col + 1, "Ordered", &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, current_col + 2, "Distractors", &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, current_col + 3, "Parameters", &bold_border_format)?;
+
+            // 3. Create "Constraints" sheet
+            let constraints_sheet = workbook.add_worksheet().set_name("Constraints")?;
+            for (col, title) in ["Question", "Kind", "Other"].iter().enumerate()
+                { constraints_sheet.write_string_with_format(0, col as u16, *title, &bold_border_format)?; }
         }
-        workbook.save(&self.path).map_err(|e| e.to_string())
+        workbook.save(&self.path)?;
+        Ok(())
     }
 
-    // fn read_header(&self) -> Option<Header>
+    // fn read_header(&self) -> Result<Header, Error>
     /// Implements `read_header` for `Excel`.
-    fn read_header(&self) -> Option<Header>
+    fn read_header(&self) -> Result<Header, Error>
     {
-        let mut excel = open_workbook_auto(&self.path).ok()?;
-        let range = excel.worksheet_range("Header").ok()?;
+        let range = self.read_sheet("Header")?;
         let title = range.get((0, 1)).and_then(|c| c.as_string()).unwrap_or_default();
         let name = range.get((1, 1)).and_then(|c| c.as_string()).unwrap_or_default();
         let id = range.get((2, 1)).and_then(|c| c.as_string()).unwrap_or_default();
@@ -626,117 +794,139 @@ impl QBDB for Excel
             categories.push(cat);
             col += 1;
         }
-        Some(Header::new(title, name, id, categories, notice))
+        Ok(Header::new(title, name, id, categories, notice))
     }
 
-    // fn write_header_with_default(&mut self) -> Result<(), String>
+    // fn write_header_with_default(&mut self) -> Result<(), Error>
     /// Implements `write_header_with_default` for `Excel`.
     #[inline]
-    fn write_header_with_default(&mut self) -> Result<(), String>
+    fn write_header_with_default(&mut self) -> Result<(), Error>
     {
         self.write_header(&Header::new_with_default())
     }
 
-    // fn write_header(&mut self, header: &Header) -> Result<(), String>
+    // fn write_header(&mut self, header: &Header) -> Result<(), Error>
     /// Implements `write_header` for `Excel`.
     /// This is done by reading the existing questions, creating a new QBank in memory with the new header,
     /// and then writing the entire QBank back to the file. This is necessary due to the write-only nature
     /// of the Excel writer library.
-    fn write_header(&mut self, header: &Header) -> Result<(), String>
+    fn write_header(&mut self, header: &Header) -> Result<(), Error>
     {
         
         // Create a new QBank with the new header.
         let mut qbank = QBank::new_with_header(header.clone());
 
         // Read questions from the existing file, if it exists.
-        if let Ok(mut excel) = open_workbook_auto(&self.path)
+        if let Ok(range) = self.read_sheet("Questions")
         {
-            if let Some(range) = excel.worksheet_range("Questions").ok()
-            {
-                // Safely read questions, skipping header row
-                for row in range.rows().skip(1) {
-                    if let Some(question) = Excel::parse_question_row(row)
-                    {
-                        qbank.push_question(question);
-                    }
-                }
-            }
+            // Safely read questions, skipping header row
+            for question in Excel::parse_question_rows(&range)?
+                { qbank.push_question(question); }
         }
+        if let Ok(range) = self.read_sheet("Constraints")
+            { qbank.set_constraints(Excel::parse_constraint_rows(&range)?); }
         
         // Write the entire QBank (new header + old/existing questions) back to the file.
         self.write_qbank(&qbank)
     }
 
-    // fn read_qbank(&self) -> Option<QBank>
+    // fn read_qbank(&self) -> Result<QBank, Error>
     /// Implements `read_qbank` for `Excel`.
-    fn read_qbank(&self) -> Option<QBank> {
+    /// A row of the "Questions" sheet which cannot be parsed is an `Error::ExcelParse`.
+    /// The constraints are read from the "Constraints" sheet, if the workbook has it.
+    fn read_qbank(&self) -> Result<QBank, Error> {
         let header = self.read_header()?;
         let mut qbank = QBank::new_with_header(header);
 
-        let mut excel = open_workbook_auto(&self.path).ok()?;
-        let range = excel.worksheet_range("Questions").ok()?;
-
-        for row in range.rows().skip(1) // Skip header row
-        {
-            if let Some(question) = Excel::parse_question_row(row)
-                { qbank.push_question(question); }
-        }
-        Some(qbank)
+        let range = self.read_sheet("Questions")?;
+        for question in Excel::parse_question_rows(&range)?
+            { qbank.push_question(question); }
+        if let Some(id) = qbank.find_duplicate_id()
+            { return Err(Error::Validation(format!("question ID {} is used by two or more questions", id))); }
+        if let Ok(range) = self.read_sheet("Constraints")
+            { qbank.set_constraints(Excel::parse_constraint_rows(&range)?); }
+        Ok(qbank)
     }
 
-    // fn write_qbank(&mut self, qbank: &QBank) -> Result<(), String>
+    // fn write_qbank(&mut self, qbank: &QBank) -> Result<(), Error>
     /// Implements `write_qbank` for `Excel`.
-    fn write_qbank(&mut self, qbank: &QBank) -> Result<(), String>
+    fn write_qbank(&mut self, qbank: &QBank) -> Result<(), Error>
     {
         let mut workbook = Workbook::new();
         let border_format = Format::new().set_border(FormatBorder::Thin);
         let bold_border_format = Format::new().set_bold().set_border(FormatBorder::Thin);
         
         // 1. Write "Header" sheet
-        let header_sheet = workbook.add_worksheet().set_name("Header").map_err(|e| e.to_string())?;
+        let header_sheet = workbook.add_worksheet().set_name("Header")?;
         let header = qbank.get_header();
-        header_sheet.write_string_with_format(0, 0, "Title", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(0, 1, header.get_title(), &border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(1, 0, "Name", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(1, 1, header.get_name(), &border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(2, 0, "ID", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(2, 1, header.get_id(), &border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(3, 0, "Notice", &bold_border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(3, 1, header.get_notice(), &border_format).map_err(|e| e.to_string())?;
-        header_sheet.write_string_with_format(4, 0, "Categories", &bold_border_format).map_err(|e| e.to_string())?;
+        header_sheet.write_string_with_format(0, 0, "Title", &bold_border_format)?;
+        header_sheet.write_string_with_format(0, 1, header.get_title(), &border_format)?;
+        header_sheet.write_string_with_format(1, 0, "Name", &bold_border_format)?;
+        header_sheet.write_string_with_format(1, 1, header.get_name(), &border_format)?;
+        header_sheet.write_string_with_format(2, 0, "ID", &bold_border_format)?;
+        header_sheet.write_string_with_format(2, 1, header.get_id(), &border_format)?;
+        header_sheet.write_string_with_format(3, 0, "Notice", &bold_border_format)?;
+        header_sheet.write_string_with_format(3, 1, header.get_notice(), &border_format)?;
+        header_sheet.write_string_with_format(4, 0, "Categories", &bold_border_format)?;
         for (i, cat) in header.get_categories().iter().enumerate()
-            { header_sheet.write_string_with_format(4, i as u16 + 1, cat, &border_format).map_err(|e| e.to_string())?; }
+            { header_sheet.write_string_with_format(4, i as u16 + 1, cat, &border_format)?; }
 
         // 2. Write "Questions" sheet
-        let questions_sheet = workbook.add_worksheet().set_name("Questions").map_err(|e| e.to_string())?;
-        questions_sheet.write_string_with_format(0, 0, "ID", &bold_border_format).map_err(|e| e.to_string())?;
-        questions_sheet.write_string_with_format(0, 1, "Group", &bold_border_format).map_err(|e| e.to_string())?;
-        questions_sheet.write_string_with_format(0, 2, "Category", &bold_border_format).map_err(|e| e.to_string())?;
-        questions_sheet.write_string_with_format(0, 3, "Question", &bold_border_format).map_err(|e| e.to_string())?;
+        let questions_sheet = workbook.add_worksheet().set_name("Questions")?;
+        questions_sheet.write_string_with_format(0, 0, "ID", &bold_border_format)?;
+        questions_sheet.write_string_with_format(0, 1, "Group", &bold_border_format)?;
+        questions_sheet.write_string_with_format(0, 2, "Category", &bold_border_format)?;
+        questions_sheet.write_string_with_format(0, 3, "Question", &bold_border_format)?;
 
         let max_choices = qbank.get_max_choices();
         for i in 1..=max_choices
         {
-            questions_sheet.write_string_with_format(0, (i * 2 + 2) as u16, &format!("Choice{}", i), &bold_border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_string_with_format(0, (i * 2 + 3) as u16, &format!("IsAnswer{}", i), &bold_border_format).map_err(|e| e.to_string())?;
+            questions_sheet.write_string_with_format(0, (i * 2 + 2) as u16, format!("Choice{}", i), &bold_border_format)?;
+            questions_sheet.write_string_with_format(0, (i * 2 + 3) as u16, format!("IsAnswer{}", i), &bold_border_format)?;
         }
+        let pinned_col = (max_choices * 2 + 4) as u16;
+        questions_sheet.write_string_with_format(0, pinned_col, "Pinned", &bold_border_format)?;
+        questions_sheet.write_string_with_format(0, pinned_col + 1, "Ordered", &bold_border_format)?;
+        questions_sheet.write_string_with_format(0, pinned_col + 2, "Distractors", &bold_border_format)?;
+        questions_sheet.write_string_with_format(0, pinned_col + 3, "Parameters", &bold_border_format)?;
 
         for (row_idx, question) in qbank.get_questions().iter().enumerate()
         {
             let current_row = (row_idx + 1) as u32;
-            questions_sheet.write_number_with_format(current_row, 0, question.get_id() as f64, &border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_number_with_format(current_row, 1, question.get_group() as f64, &border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_number_with_format(current_row, 2, question.get_category() as f64, &border_format).map_err(|e| e.to_string())?;
-            questions_sheet.write_string_with_format(current_row, 3, question.get_question(), &border_format).map_err(|e| e.to_string())?;
+            questions_sheet.write_number_with_format(current_row, 0, question.get_id() as f64, &border_format)?;
+            questions_sheet.write_number_with_format(current_row, 1, question.get_group() as f64, &border_format)?;
+            questions_sheet.write_number_with_format(current_row, 2, question.get_category() as f64, &border_format)?;
+            questions_sheet.write_string_with_format(current_row, 3, question.get_question(), &border_format)?;
 
             for (i, (choice_text, is_answer)) in question.get_choices().iter().enumerate()
             {
                 let choice_col = (i * 2 + 4) as u16;
-                questions_sheet.write_string_with_format(current_row, choice_col, choice_text, &border_format).map_err(|e| e.to_string())?;
-                questions_sheet.write_string_with_format(current_row, choice_col + 1, &is_answer.to_string().to_uppercase(), &border_format).map_err(|e| e.to_string())?;
+                questions_sheet.write_string_with_format(current_row, choice_col, choice_text, &border_format)?;
+                questions_sheet.write_string_with_format(current_row, choice_col + 1, is_answer.to_string().to_uppercase(), &border_format)?;
             }
+            if !question.get_pinned().is_empty()
+                { questions_sheet.write_string_with_format(current_row, pinned_col, choices_to_text(question.get_pinned()), &border_format)?; }
+            if !question.get_ordered().is_empty()
+                { questions_sheet.write_string_with_format(current_row, pinned_col + 1, choices_to_text(question.get_ordered()), &border_format)?; }
+            if let Some(shown_distractors) = question.get_shown_distractors()
+                { questions_sheet.write_number_with_format(current_row, pinned_col + 2, shown_distractors as f64, &border_format)?; }
+            if question.is_parameterized()
+                { questions_sheet.write_string_with_format(current_row, pinned_col + 3, parameters_to_text(question.get_parameters()), &border_format)?; }
+        }
+
+        // 3. Write "Constraints" sheet
+        let constraints_sheet = workbook.add_worksheet().set_name("Constraints")?;
+        for (col, title) in ["Question", "Kind", "Other"].iter().enumerate()
+            { constraints_sheet.write_string_with_format(0, col as u16, *title, &bold_border_format)?; }
+        for (row_idx, constraint) in qbank.get_constraints().iter().enumerate()
+        {
+            let current_row = (row_idx + 1) as u32;
+            constraints_sheet.write_number_with_format(current_row, 0, constraint.get_question() as f64, &border_format)?;
+            constraints_sheet.write_string_with_format(current_row, 1, constraint.get_kind(), &border_format)?;
+            constraints_sheet.write_number_with_format(current_row, 2, constraint.get_other() as f64, &border_format)?;
         }
         
-        workbook.save(&self.path).map_err(|e| e.to_string())
+        workbook.save(&self.path)?;
+        Ok(())
     }
 }
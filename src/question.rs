@@ -8,6 +8,8 @@
 ///////////////////////////////////////////////////////////////////////////////
 
 
+use crate::template::{ self, Parameters };
+
 pub type ChoiceAnswer = (String, bool);
 pub type Choices = Vec<ChoiceAnswer>;
 pub type Questions = Vec<Question>;
@@ -21,6 +23,10 @@ pub struct Question
     category: u8,   // 1-based category: 1 for single choice, 2 for multiple choice, 3 for short answer.
     question: String,   // The text of the question
     choices: Choices,   // For category 3, choice[0].0 or get_choice(1).0 is the answer.
+    pinned: Vec<u8>,    // 1-based choice numbers that stay at their original positions when shuffled.
+    ordered: Vec<u8>,   // 1-based choice numbers that move when shuffled but keep their relative order.
+    shown_distractors: Option<u8>,  // The number of incorrect choices shown to each student. All are shown if None.
+    parameters: Parameters, // The parameters that the placeholders of the text and the choices refer to.
 }
 
 impl Question
@@ -48,6 +54,10 @@ impl Question
             category: 1,
             question: String::new(),
             choices: Choices::new(),
+            pinned: Vec::new(),
+            ordered: Vec::new(),
+            shown_distractors: None,
+            parameters: Parameters::new(),
         }
     }
 
@@ -74,7 +84,7 @@ impl Question
     #[inline]
     pub fn new(id: u16, group: u16, category: u8, question: String, choices: Choices) -> Self
     {
-        Self { id, group, category, question, choices }
+        Self { id, group, category, question, choices, pinned: Vec::new(), ordered: Vec::new(), shown_distractors: None, parameters: Parameters::new() }
     }
 
     // pub fn get_id(&self) -> u16
@@ -317,4 +327,331 @@ impl Question
     {
         self.choices = choices;
     }
+
+    // pub fn get_pinned(&self) -> &Vec<u8>
+    /// Gets the choices that stay at their original positions when shuffled.
+    ///
+    /// # Output
+    /// `&Vec<u8>` - A reference to the 1-based numbers of the pinned choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let question = Question::new_empty();
+    /// assert!(question.get_pinned().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_pinned(&self) -> &Vec<u8>
+    {
+        &self.pinned
+    }
+
+    // pub fn set_pinned(&mut self, pinned: Vec<u8>)
+    /// Sets the choices that stay at their original positions when shuffled.
+    ///
+    /// A choice such as "All of the above" is pinned by its own number so that
+    /// it is always last. A choice such as "Both A and B" is kept meaningful by
+    /// pinning it together with the choices that it refers to.
+    ///
+    /// # Arguments
+    /// * `pinned` - The 1-based numbers of the choices to pin.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let mut question = Question::new(1, 1, 1, "Q".to_string(), vec![("A".to_string(), false), ("B".to_string(), false), ("All of the above".to_string(), true)]);
+    /// question.set_pinned(vec![3]);
+    /// assert!(question.is_pinned(3));
+    /// assert!(!question.is_pinned(1));
+    /// ```
+    #[inline]
+    pub fn set_pinned(&mut self, pinned: Vec<u8>)
+    {
+        self.pinned = pinned;
+    }
+
+    // pub fn is_pinned(&self, choice_number: usize) -> bool
+    /// Checks whether a choice stays at its original position when shuffled.
+    ///
+    /// # Arguments
+    /// * `choice_number` - The 1-based index of the choice.
+    ///
+    /// # Output
+    /// `bool` - `true` if the choice is pinned.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let mut question = Question::new_empty();
+    /// question.set_pinned(vec![4]);
+    /// assert!(question.is_pinned(4));
+    /// assert!(!question.is_pinned(2));
+    /// ```
+    #[inline]
+    pub fn is_pinned(&self, choice_number: usize) -> bool
+    {
+        self.pinned.iter().any(|&c| c as usize == choice_number)
+    }
+
+    // pub fn get_ordered(&self) -> &Vec<u8>
+    /// Gets the choices that keep their relative order when shuffled.
+    ///
+    /// # Output
+    /// `&Vec<u8>` - A reference to the 1-based numbers of the ordered choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let question = Question::new_empty();
+    /// assert!(question.get_ordered().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_ordered(&self) -> &Vec<u8>
+    {
+        &self.ordered
+    }
+
+    // pub fn set_ordered(&mut self, ordered: Vec<u8>)
+    /// Sets the choices that keep their relative order when shuffled.
+    ///
+    /// Unlike pinned choices, these choices move to random positions, but each
+    /// of them still comes before those of greater numbers, e.g. "1 m", "1 km"
+    /// and "1,000 km" stay in ascending order wherever they land. A choice which
+    /// is also pinned stays at its original position.
+    ///
+    /// # Arguments
+    /// * `ordered` - The 1-based numbers of the choices to keep in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let mut question = Question::new(1, 1, 1, "Q".to_string(), vec![("1 m".to_string(), false), ("1 km".to_string(), true), ("1,000 km".to_string(), false), ("Red".to_string(), false)]);
+    /// question.set_ordered(vec![1, 2, 3]);
+    /// assert_eq!(question.get_ordered(), &vec![1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn set_ordered(&mut self, ordered: Vec<u8>)
+    {
+        self.ordered = ordered;
+    }
+
+    // pub fn get_shown_distractors(&self) -> Option<u8>
+    /// Gets the number of incorrect choices that each student is shown.
+    ///
+    /// # Output
+    /// `Option<u8>` - The number of incorrect choices drawn for each student,
+    /// or `None` if every student is shown all the choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let question = Question::new_empty();
+    /// assert!(question.get_shown_distractors().is_none());
+    /// ```
+    #[inline]
+    pub fn get_shown_distractors(&self) -> Option<u8>
+    {
+        self.shown_distractors
+    }
+
+    // pub fn set_shown_distractors(&mut self, shown_distractors: Option<u8>)
+    /// Sets the number of incorrect choices that each student is shown.
+    ///
+    /// A question with one correct answer and many plausible distractors can
+    /// show each student the correct answers together with a few distractors
+    /// drawn at random, so that students see different choices as well as
+    /// a different order. Pinned choices are always shown, like the correct
+    /// answers, and do not count as distractors.
+    ///
+    /// # Arguments
+    /// * `shown_distractors` - The number of incorrect choices drawn for each student,
+    ///   or `None` to show all the choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let choices = (1..=8).map(|i| (format!("Choice {}", i), i == 1)).collect();
+    /// let mut question = Question::new(1, 1, 1, "Q".to_string(), choices);
+    /// question.set_shown_distractors(Some(3));
+    /// assert_eq!(question.get_shown_distractors(), Some(3));
+    /// assert_eq!(question.count_shown_choices(), 4);
+    /// ```
+    #[inline]
+    pub fn set_shown_distractors(&mut self, shown_distractors: Option<u8>)
+    {
+        self.shown_distractors = shown_distractors;
+    }
+
+    // pub fn is_always_shown(&self, choice_number: usize) -> bool
+    /// Checks whether a choice is shown to every student, which is the case for
+    /// the correct answers and the pinned choices.
+    ///
+    /// # Arguments
+    /// * `choice_number` - The 1-based index of the choice.
+    ///
+    /// # Output
+    /// `bool` - `true` if the choice is never left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let mut question = Question::new(1, 1, 1, "Q".to_string(), vec![("A".to_string(), true), ("B".to_string(), false), ("None of the above".to_string(), false)]);
+    /// question.set_pinned(vec![3]);
+    /// assert!(question.is_always_shown(1));
+    /// assert!(!question.is_always_shown(2));
+    /// assert!(question.is_always_shown(3));
+    /// ```
+    #[inline]
+    pub fn is_always_shown(&self, choice_number: usize) -> bool
+    {
+        self.get_choice(choice_number).is_some_and(|choice| choice.1) || self.is_pinned(choice_number)
+    }
+
+    // pub fn count_shown_choices(&self) -> usize
+    /// Counts the choices that each student is shown.
+    ///
+    /// # Output
+    /// `usize` - The number of the choices which are always shown and
+    /// of the distractors drawn, or the number of all the choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let choices = (1..=4).map(|i| (format!("Choice {}", i), i == 1)).collect();
+    /// let mut question = Question::new(1, 1, 1, "Q".to_string(), choices);
+    /// assert_eq!(question.count_shown_choices(), 4);
+    /// question.set_shown_distractors(Some(2));
+    /// assert_eq!(question.count_shown_choices(), 3);
+    /// question.set_shown_distractors(Some(9));     // There are only 3 distractors.
+    /// assert_eq!(question.count_shown_choices(), 4);
+    /// ```
+    pub fn count_shown_choices(&self) -> usize
+    {
+        let number_of_choices = self.choices.len();
+        let always_shown = (1..=number_of_choices).filter(|&c| self.is_always_shown(c)).count();
+        match self.shown_distractors
+        {
+            Some(shown_distractors) => always_shown + (shown_distractors as usize).min(number_of_choices - always_shown),
+            None => number_of_choices,
+        }
+    }
+
+    // pub fn get_parameters(&self) -> &Parameters
+    /// Gets the parameters of the question.
+    ///
+    /// # Output
+    /// `&Parameters` - A reference to the parameters, which is empty
+    /// unless the question is templated.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Question;
+    /// let question = Question::new_empty();
+    /// assert!(question.get_parameters().is_empty());
+    /// ```
+    #[inline]
+    pub fn get_parameters(&self) -> &Parameters
+    {
+        &self.parameters
+    }
+
+    // pub fn set_parameters(&mut self, parameters: Parameters)
+    /// Sets the parameters of the question, which makes it a templated question.
+    ///
+    /// Each student is given random values of the parameters, which are filled
+    /// into the placeholders of the question text and of the choices, such as
+    /// `{a}` or `{a * b + 1}`, as `template::fill_in()` tells. So a choice such as
+    /// `{a + b}` computes the answer and a choice such as `{a - b}` a distractor
+    /// from the same values. Values with which a placeholder cannot be evaluated
+    /// or two choices come out the same are drawn again.
+    ///
+    /// # Arguments
+    /// * `parameters` - The parameters, or an empty vector to use the texts as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Parameter, Question };
+    /// let mut question = Question::new(1, 1, 1, "What is {a} + {b}?".to_string(),
+    ///         vec![("{a + b}".to_string(), true), ("{a - b}".to_string(), false), ("{a * b}".to_string(), false)]);
+    /// question.set_parameters(vec![Parameter::new("a".to_string(), 2, 9), Parameter::new("b".to_string(), 10, 20)]);
+    /// assert!(question.is_parameterized());
+    /// assert_eq!(question.get_parameters()[1].get_name(), "b");
+    /// ```
+    #[inline]
+    pub fn set_parameters(&mut self, parameters: Parameters)
+    {
+        self.parameters = parameters;
+    }
+
+    // pub fn is_parameterized(&self) -> bool
+    /// Checks whether the question is templated, that is, whether it has parameters.
+    ///
+    /// # Output
+    /// `bool` - `true` if the question has one or more parameters.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Parameter, Question };
+    /// let mut question = Question::new_empty();
+    /// assert!(!question.is_parameterized());
+    /// question.set_parameters(vec![Parameter::new("a".to_string(), 1, 9)]);
+    /// assert!(question.is_parameterized());
+    /// ```
+    #[inline]
+    pub fn is_parameterized(&self) -> bool
+    {
+        !self.parameters.is_empty()
+    }
+
+    // pub fn instantiate(&self, values: &[(String, i64)]) -> Option<Question>
+    /// Makes the concrete question that the given values of the parameters give.
+    ///
+    /// # Arguments
+    /// * `values` - The values of the parameters, as pairs of a name and a value.
+    ///
+    /// # Output
+    /// `Option<Question>` - The question of which the text and the choices have
+    /// the values filled in and which has no parameters, or `None` if a value is
+    /// missing or out of its range, a placeholder cannot be evaluated, or two
+    /// choices come out the same so that the answer would be ambiguous.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Parameter, Question };
+    /// let mut question = Question::new(1, 1, 1, "What is {a} + {b}?".to_string(),
+    ///         vec![("{a + b}".to_string(), true), ("{a - b}".to_string(), false), ("{a * b}".to_string(), false)]);
+    /// question.set_parameters(vec![Parameter::new("a".to_string(), 1, 9), Parameter::new("b".to_string(), 1, 9)]);
+    ///
+    /// let concrete = question.instantiate(&[("a".to_string(), 7), ("b".to_string(), 3)]).unwrap();
+    /// assert_eq!(concrete.get_question(), "What is 7 + 3?");
+    /// assert_eq!(concrete.get_choice(1).unwrap().0, "10");
+    /// assert_eq!(concrete.get_choice(3).unwrap().0, "21");
+    /// assert!(!concrete.is_parameterized());
+    ///
+    /// // 2 + 2 and 2 * 2 are both 4.
+    /// assert!(question.instantiate(&[("a".to_string(), 2), ("b".to_string(), 2)]).is_none());
+    /// // b is missing.
+    /// assert!(question.instantiate(&[("a".to_string(), 7)]).is_none());
+    /// ```
+    pub fn instantiate(&self, values: &[(String, i64)]) -> Option<Question>
+    {
+        for parameter in self.parameters.iter()
+        {
+            let (_, value) = values.iter().find(|(name, _)| name == parameter.get_name())?;
+            if *value < parameter.get_min() || *value > parameter.get_max().max(parameter.get_min())
+                { return None; }
+        }
+
+        let mut concrete = self.clone();
+        concrete.parameters = Parameters::new();
+        concrete.question = template::fill_in(&self.question, values)?;
+        for (choice, _) in concrete.choices.iter_mut()
+            { *choice = template::fill_in(choice, values)?; }
+        for (i, (choice, _)) in concrete.choices.iter().enumerate()
+        {
+            if concrete.choices[..i].iter().any(|(other, _)| other == choice)
+                { return None; }
+        }
+        Some(concrete)
+    }
 }
@@ -0,0 +1,91 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use cryptocol::random::RandGen;
+
+/// A source of random numbers that questions and choices are shuffled with.
+///
+/// By default, `qrate` uses `cryptocol::random::Random`, seeded when a seed is given.
+/// Implement this trait to plug in another generator, such as a deterministic mock
+/// in tests or a cryptographically secure generator of your choice, and pass it to
+/// the methods whose names end with `_with_source`.
+///
+/// # Examples
+/// ```
+/// use qrate::RandomSource;
+///
+/// // A mock that always gives the same number
+/// struct Constant(u64);
+/// impl RandomSource for Constant
+/// {
+///     fn random_u64(&mut self) -> u64 { self.0 }
+/// }
+///
+/// let mut source = Constant(7);
+/// assert_eq!(source.random_u64(), 7);
+/// assert_eq!(source.random_under(5), 2);
+/// ```
+pub trait RandomSource
+{
+    // fn random_u64(&mut self) -> u64
+    /// Generates a random number, every `u64` being equally likely.
+    ///
+    /// # Output
+    /// `u64` - A random number.
+    fn random_u64(&mut self) -> u64;
+
+    // fn random_under(&mut self, ceiling: usize) -> usize
+    /// Generates a random number less than `ceiling`, every such number being equally likely.
+    ///
+    /// The numbers from `random_u64()` that are less than the remainder of 2^64
+    /// divided by `ceiling` are drawn again, so that the smaller results are not
+    /// more likely than the others. A source must therefore not keep giving
+    /// such small numbers forever.
+    ///
+    /// # Arguments
+    /// * `ceiling` - The number that the random number is less than, which must not be zero.
+    ///
+    /// # Output
+    /// `usize` - A random number from 0 to `ceiling - 1`.
+    ///
+    /// # Panics
+    /// Panics if `ceiling` is zero, since no number is less than zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::RandomSource;
+    /// let mut source = Random::new();
+    /// for _ in 0..10
+    ///     { assert!(source.random_under(6) < 6); }
+    /// ```
+    fn random_under(&mut self, ceiling: usize) -> usize
+    {
+        assert!(ceiling > 0, "random_under() needs a ceiling greater than zero");
+        let ceiling = ceiling as u64;
+        let threshold = ceiling.wrapping_neg() % ceiling;
+        loop
+        {
+            let number = self.random_u64();
+            if number >= threshold
+                { return (number % ceiling) as usize; }
+        }
+    }
+}
+
+impl RandomSource for RandGen
+{
+    // fn random_u64(&mut self) -> u64
+    #[inline]
+    fn random_u64(&mut self) -> u64
+    {
+        RandGen::random_u64(self)
+    }
+}
@@ -8,8 +8,9 @@
 ///////////////////////////////////////////////////////////////////////////////
 
 
-use calamine::{ Reader, DataType }; // Add DataType here
+use calamine::DataType;
 
+use crate::Error;
 use crate::Excel;
 use crate::SBank;
 use crate::SQLiteDB;
@@ -31,14 +32,14 @@ pub trait SBDB
     /// * `extention` - The file extension to append.
     ///
     /// # Output
-    /// `Some(Self)` if the connection is successful, otherwise `None`.
+    /// `Ok(Self)` if the connection is successful, otherwise an `Error`.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
     /// use qrate::{SBDB, SQLiteDB};
     ///
     /// let db = SQLiteDB::open(":memory:".to_string(), ".db");
-    /// assert!(db.is_some());
+    /// assert!(db.is_ok());
     /// ```
     ///
     /// # Example 2 for Excel
@@ -46,17 +47,17 @@ pub trait SBDB
     /// use qrate::{SBDB, Excel};
     ///
     /// let excel = Excel::open("students.sb.xlsx".to_string(), ".sb.xlsx");
-    /// assert!(excel.is_some());
+    /// assert!(excel.is_ok());
     /// // assert_eq!(excel.unwrap().get_path(), "students.sb.xlsx"); // Excel doesn't have get_path method directly on SBDB
     /// ```
-    fn open(path: String) -> Option<Self> where Self: Sized;
+    fn open(path: String) -> Result<Self, Error> where Self: Sized;
 
     /// Creates the necessary table(s) for storing student data.
     ///
     /// For a database that already has the table, this should not produce an error.
     ///
     /// # Output
-    /// `Ok(())` on success, or an error string on failure.
+    /// `Ok(())` on success, or an `Error` on failure.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -79,14 +80,15 @@ pub trait SBDB
     /// assert!(Path::new(file_path).exists());
     /// std::fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn make_table(&self) -> Result<(), String>;
+    fn make_table(&self) -> Result<(), Error>;
 
     /// Reads all student data from the database into an `SBank`.
     ///
     /// # Output
-    /// `Some(SBank)` containing all students found in the database. Returns an
-    /// empty `SBank` if no students are found. Returns `None` if a database
-    /// read error occurs.
+    /// `Ok(SBank)` containing all students found in the database. Returns an
+    /// empty `SBank` if no students are found. Returns an `Error` if a database
+    /// read error occurs, such as `Error::ExcelParse` for a row of the
+    /// "Students" sheet without a name or an ID.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -100,7 +102,7 @@ pub trait SBDB
     /// db.write_sbank(&sbank).unwrap();
     ///
     /// let read_sbank = db.read_sbank();
-    /// assert!(read_sbank.is_some());
+    /// assert!(read_sbank.is_ok());
     /// let read_bank = read_sbank.unwrap();
     /// assert_eq!(read_bank.len(), 1);
     /// assert_eq!(read_bank.get(0).unwrap().get_name(), "Alice");
@@ -119,14 +121,14 @@ pub trait SBDB
     /// excel.write_sbank(&sbank).unwrap();
     ///
     /// let read_sbank = excel.read_sbank();
-    /// assert!(read_sbank.is_some());
+    /// assert!(read_sbank.is_ok());
     /// let read_bank = read_sbank.unwrap();
     /// assert_eq!(read_bank.len(), 1);
     /// assert_eq!(read_bank.get(0).unwrap().get_name(), "Bob");
     ///
     /// fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn read_sbank(&self) -> Option<SBank>;
+    fn read_sbank(&self) -> Result<SBank, Error>;
 
     /// Writes the contents of an `SBank` to the database.
     ///
@@ -138,7 +140,7 @@ pub trait SBDB
     /// * `sbank` - A reference to the `SBank` containing the students to be written.
     ///
     /// # Output
-    /// `Ok(())` on success, or an error string on failure.
+    /// `Ok(())` on success, or an `Error` on failure.
     ///
     /// # Example 1 for SQLiteDB
     /// ```
@@ -178,13 +180,13 @@ pub trait SBDB
     ///
     /// fs::remove_file(file_path).unwrap(); // Clean up
     /// ```
-    fn write_sbank(&mut self, sbank: &SBank) -> Result<(), String>;
+    fn write_sbank(&mut self, sbank: &SBank) -> Result<(), Error>;
 }
 
 
 impl SBDB for SQLiteDB
 {
-    // fn open(path: String, extention: &str) -> Option<SQLiteDB>
+    // fn open(path: String) -> Result<SQLiteDB, Error>
     /// Implements `open` for `SQLiteDB`.
     /// Appends `.sbdb` to the path if no extension is present and opens a connection.
     ///
@@ -193,44 +195,47 @@ impl SBDB for SQLiteDB
     /// * `extention` - The file extension to append.
     ///
     /// # Output
-    /// `Option<SQLiteDB>` - An optional `SQLiteDB` instance if the connection is successful.
-    fn open(path: String) -> Option<SQLiteDB>
+    /// `Result<SQLiteDB, Error>` - An `SQLiteDB` instance if the connection is successful.
+    fn open(path: String) -> Result<SQLiteDB, Error>
     {
         SQLiteDB::open_with_ext(path, "sbdb")
     }
 
-    // fn make_table(&self) -> Result<(), String>
+    // fn make_table(&self) -> Result<(), Error>
     /// Implements `make_table` for `SQLiteDB`.
     /// Executes a `CREATE TABLE` SQL statement for `tblStudents`.
     ///
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
-    fn make_table(&self) -> Result<(), String>
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    fn make_table(&self) -> Result<(), Error>
     {
         let sql = r#"CREATE TABLE IF NOT EXISTS tblStudents (
     name        TEXT NOT NULL,
     id          TEXT NOT NULL
 );"#;
-        self.conn.execute(sql, []).map(|_| ()).map_err(|e| format!("Failed to create table tblStudents: {}", e))
+        self.conn.execute(sql, [])?;
+        Ok(())
     }
 
-    // fn read_sbank(&self) -> Option<SBank>
+    // fn read_sbank(&self) -> Result<SBank, Error>
     /// Implements `read_sbank` for `SQLiteDB`.
-    /// Queries the `tblStudents` table and maps each row to a `Student` struct.
+    /// Checks the columns of the `tblStudents` table, queries it,
+    /// and maps each row to a `Student` struct.
     ///
     /// # Output
-    /// `Option<SBank>` - An optional `SBank` containing all students from the database.
-    fn read_sbank(&self) -> Option<SBank>
+    /// `Result<SBank, Error>` - The `SBank` containing all students from the database.
+    fn read_sbank(&self) -> Result<SBank, Error>
     {
-        let mut stmt = self.conn.prepare("SELECT * FROM tblStudents;").ok()?;
+        self.check_columns("tblStudents", &["name", "id"])?;
+        let mut stmt = self.conn.prepare("SELECT * FROM tblStudents;")?;
         let student_iter = stmt.query_map([], |row| {
             Ok(Student::new(row.get(0)?, row.get(1)?))
-        }).ok()?;
+        })?;
 
-        student_iter.collect::<Result<SBank, _>>().ok()
+        Ok(student_iter.collect::<Result<SBank, _>>()?)
     }
 
-    // fn write_sbank(&self, sbank: &SBank) -> Result<(), String>
+    // fn write_sbank(&mut self, sbank: &SBank) -> Result<(), Error>
     /// Implements `write_sbank` for `SQLiteDB`.
     /// Iterates through the `SBank` and inserts each `Student` into the `tblStudents` table.
     ///
@@ -238,20 +243,21 @@ impl SBDB for SQLiteDB
     /// * `sbank` - A reference to the `SBank` to be written to the database.
     /// 
     /// # Output
-    /// `Result<(), String>` - `Ok(())` on success, or an error message string on failure.
-    fn write_sbank(&mut self, sbank: &SBank) -> Result<(), String>
+    /// `Result<(), Error>` - `Ok(())` on success, or an `Error` on failure.
+    fn write_sbank(&mut self, sbank: &SBank) -> Result<(), Error>
     {
-        let _ = self.make_table();
+        self.make_table()?;
         if sbank.is_empty()
             { return Ok(()); }  // Nothing to write, which is a success.
 
-        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        let tx = self.conn.transaction()?;
         {
-            let mut stmt = tx.prepare("INSERT INTO tblStudents (name, id) VALUES (?1, ?2);").map_err(|e| e.to_string())?;
+            let mut stmt = tx.prepare("INSERT INTO tblStudents (name, id) VALUES (?1, ?2);")?;
             for student in sbank
-                { stmt.execute((student.get_name(), student.get_id())).map_err(|e| format!("Failed to insert student {}: {}", student.get_id(), e))?; }
+                { stmt.execute((student.get_name(), student.get_id()))?; }
         }
-        tx.commit().map_err(|e| e.to_string())
+        tx.commit()?;
+        Ok(())
     }
 }
 
@@ -259,60 +265,66 @@ impl SBDB for SQLiteDB
 impl SBDB for Excel
 {
     #[inline]
-    fn open(path: String) -> Option<Self>
+    fn open(path: String) -> Result<Self, Error>
     where Self: Sized
     {
         Excel::open_with_ext(path, "sb.xlsx")
     }
 
     /// Creates a new Excel file with a "Students" sheet and headers.
-    fn make_table(&self) -> Result<(), String>
+    fn make_table(&self) -> Result<(), Error>
     {
         let mut workbook = rust_xlsxwriter::Workbook::new();
-        let sheet = workbook.add_worksheet().set_name("Students").map_err(|e| e.to_string())?;
+        let sheet = workbook.add_worksheet().set_name("Students")?;
         let format = rust_xlsxwriter::Format::new().set_bold();
 
-        sheet.write_string_with_format(0, 0, "Name", &format).map_err(|e| e.to_string())?;
-        sheet.write_string_with_format(0, 1, "ID", &format).map_err(|e| e.to_string())?;
+        sheet.write_string_with_format(0, 0, "Name", &format)?;
+        sheet.write_string_with_format(0, 1, "ID", &format)?;
 
-        workbook.save(&self.path).map_err(|e| e.to_string())
+        workbook.save(&self.path)?;
+        Ok(())
     }
 
     /// Reads students from the "Students" sheet in an Excel file.
-        fn read_sbank(&self) -> Option<SBank>
+    /// Blank rows are skipped.
+    fn read_sbank(&self) -> Result<SBank, Error>
+    {
+        let range = self.read_sheet("Students")?;
+
+        let mut sbank = SBank::new();
+        for (i, row) in range.rows().enumerate().skip(1) // Skip header row
         {
-            let mut excel = calamine::open_workbook_auto(&self.path).ok()?;
-            let range = excel.worksheet_range("Students").ok()?;
-            
-            let mut sbank = SBank::new();
-            for row in range.rows().skip(1) { // Skip header row
-                sbank.push(Student::new(
-                    row.get(0).and_then(|d| d.as_string())?,
-                    row.get(1).and_then(|d| d.as_string())? // Assuming ID is always string or convertible
-                ));
-            }
-            Some(sbank)
+            if row.iter().all(|cell| cell.is_empty())
+                { continue; }
+            let name = row.first().and_then(|d| d.as_string())
+                        .ok_or_else(|| Excel::parse_error(&range, "Students", i, "the name is missing"))?;
+            let id = row.get(1).and_then(|d| d.as_string())   // Assuming ID is always string or convertible
+                        .ok_or_else(|| Excel::parse_error(&range, "Students", i, "the ID is missing"))?;
+            sbank.push(Student::new(name, id));
         }
+        Ok(sbank)
+    }
     
     /// Writes a collection of students to a "Students" sheet in an Excel file.
-    fn write_sbank(&mut self, sbank: &SBank) -> Result<(), String>
+    fn write_sbank(&mut self, sbank: &SBank) -> Result<(), Error>
     {
         let mut workbook = rust_xlsxwriter::Workbook::new();
-        let sheet = workbook.add_worksheet().set_name("Students").map_err(|e| e.to_string())?;
+        let sheet = workbook.add_worksheet().set_name("Students")?;
         let header_format = rust_xlsxwriter::Format::new().set_bold();
         
         // Write header
-        sheet.write_string_with_format(0, 0, "Name", &header_format).map_err(|e| e.to_string())?;
-        sheet.write_string_with_format(0, 1, "ID", &header_format).map_err(|e| e.to_string())?;
+        sheet.write_string_with_format(0, 0, "Name", &header_format)?;
+        sheet.write_string_with_format(0, 1, "ID", &header_format)?;
 
         // Write student data
         for (row_idx, student) in sbank.iter().enumerate()
         {
             let row = (row_idx + 1) as u32;
-            sheet.write_string(row, 0, student.get_name()).map_err(|e| e.to_string())?;
-            sheet.write_string(row, 1, student.get_id()).map_err(|e| e.to_string())?;
+            sheet.write_string(row, 0, student.get_name())?;
+            sheet.write_string(row, 1, student.get_id())?;
         }
 
-        workbook.save(&self.path).map_err(|e| e.to_string())
+        workbook.save(&self.path)?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,197 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use std::collections::{ HashMap, HashSet };
+
+/// Represents a seating layout as pairs of adjacent students, given by their IDs.
+///
+/// Adjacency is symmetric: the pair `("1", "2")` makes student "2" a neighbour
+/// of student "1" and vice versa.
+#[derive(Debug, Clone)]
+pub struct Seating
+{
+    pairs: Vec<(String, String)>,
+    neighbours: HashMap<String, HashSet<String>>,   // ID -> IDs of the adjacent students
+}
+
+impl Seating
+{
+    // pub fn new_empty() -> Self
+    /// Creates a new `Seating` without any adjacent students.
+    ///
+    /// # Output
+    /// `Self` - A new, empty `Seating` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let seating = Seating::new_empty();
+    /// assert!(seating.get_pairs().is_empty());
+    /// ```
+    #[inline]
+    pub fn new_empty() -> Self
+    {
+        Self { pairs: Vec::new(), neighbours: HashMap::new() }
+    }
+
+    // pub fn new_with_pairs(pairs: Vec<(String, String)>) -> Self
+    /// Creates a new `Seating` from pairs of adjacent students.
+    ///
+    /// # Arguments
+    /// * `pairs` - Pairs of the IDs of adjacent students.
+    ///
+    /// # Output
+    /// `Self` - A new `Seating` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let seating = Seating::new_with_pairs(vec![("1".to_string(), "2".to_string())]);
+    /// assert!(seating.are_adjacent("2", "1"));
+    /// ```
+    pub fn new_with_pairs(pairs: Vec<(String, String)>) -> Self
+    {
+        let mut seating = Self::new_empty();
+        for (id1, id2) in pairs
+            { seating.push_pair(id1, id2); }
+        seating
+    }
+
+    // pub fn new_with_grid(grid: &[Vec<String>]) -> Self
+    /// Creates a new `Seating` from a grid of seats, each row being a row of the room.
+    ///
+    /// Students sitting next to each other in a row, or one behind the other
+    /// in a column, are adjacent. An empty string is an empty seat.
+    ///
+    /// # Arguments
+    /// * `grid` - The rows of the room, holding the IDs of the students in their seats.
+    ///
+    /// # Output
+    /// `Self` - A new `Seating` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let grid = vec![vec!["1".to_string(), "2".to_string(), "3".to_string()],
+    ///                 vec!["4".to_string(), String::new(), "6".to_string()]];
+    /// let seating = Seating::new_with_grid(&grid);
+    /// assert!(seating.are_adjacent("1", "2"));
+    /// assert!(seating.are_adjacent("1", "4"));
+    /// assert!(seating.are_adjacent("3", "6"));
+    /// assert!(!seating.are_adjacent("1", "3"));
+    /// assert!(!seating.are_adjacent("4", "6"));     // The seat between them is empty.
+    /// ```
+    pub fn new_with_grid(grid: &[Vec<String>]) -> Self
+    {
+        let mut seating = Self::new_empty();
+        for (r, row) in grid.iter().enumerate()
+        {
+            for (c, id) in row.iter().enumerate()
+            {
+                if id.is_empty()
+                    { continue; }
+                if let Some(right) = row.get(c + 1).filter(|right| !right.is_empty())
+                    { seating.push_pair(id.clone(), right.clone()); }
+                if let Some(behind) = grid.get(r + 1).and_then(|next| next.get(c)).filter(|behind| !behind.is_empty())
+                    { seating.push_pair(id.clone(), behind.clone()); }
+            }
+        }
+        seating
+    }
+
+    // pub fn get_pairs(&self) -> &Vec<(String, String)>
+    /// Gets the pairs of adjacent students.
+    ///
+    /// # Output
+    /// `&Vec<(String, String)>` - A reference to the pairs of the IDs of adjacent students.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let grid = vec![vec!["1".to_string(), "2".to_string()]];
+    /// assert_eq!(Seating::new_with_grid(&grid).get_pairs(), &vec![("1".to_string(), "2".to_string())]);
+    /// ```
+    #[inline]
+    pub fn get_pairs(&self) -> &Vec<(String, String)>
+    {
+        &self.pairs
+    }
+
+    // pub fn push_pair(&mut self, id1: String, id2: String)
+    /// Makes two students adjacent.
+    ///
+    /// # Arguments
+    /// * `id1` - The ID of a student.
+    /// * `id2` - The ID of the other student.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let mut seating = Seating::new_empty();
+    /// seating.push_pair("7".to_string(), "8".to_string());
+    /// assert!(seating.are_adjacent("7", "8"));
+    /// ```
+    pub fn push_pair(&mut self, id1: String, id2: String)
+    {
+        self.neighbours.entry(id1.clone()).or_default().insert(id2.clone());
+        self.neighbours.entry(id2.clone()).or_default().insert(id1.clone());
+        self.pairs.push((id1, id2));
+    }
+
+    // pub fn get_neighbours(&self, id: &str) -> Option<&HashSet<String>>
+    /// Gets the students adjacent to a student.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the student.
+    ///
+    /// # Output
+    /// `Option<&HashSet<String>>` - A reference to the IDs of the adjacent students,
+    /// or `None` if the student has no neighbour.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let grid = vec![vec!["1".to_string(), "2".to_string(), "3".to_string()]];
+    /// let seating = Seating::new_with_grid(&grid);
+    /// let neighbours = seating.get_neighbours("2").unwrap();
+    /// assert_eq!(neighbours.len(), 2);
+    /// assert!(neighbours.contains("1") && neighbours.contains("3"));
+    /// assert!(seating.get_neighbours("4").is_none());
+    /// ```
+    #[inline]
+    pub fn get_neighbours(&self, id: &str) -> Option<&HashSet<String>>
+    {
+        self.neighbours.get(id)
+    }
+
+    // pub fn are_adjacent(&self, id1: &str, id2: &str) -> bool
+    /// Checks whether two students are adjacent.
+    ///
+    /// # Arguments
+    /// * `id1` - The ID of a student.
+    /// * `id2` - The ID of the other student.
+    ///
+    /// # Output
+    /// `bool` - `true` if the two students are adjacent in either order.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Seating;
+    /// let seating = Seating::new_with_pairs(vec![("1".to_string(), "2".to_string())]);
+    /// assert!(seating.are_adjacent("1", "2"));
+    /// assert!(seating.are_adjacent("2", "1"));
+    /// assert!(!seating.are_adjacent("1", "3"));
+    /// ```
+    #[inline]
+    pub fn are_adjacent(&self, id1: &str, id2: &str) -> bool
+    {
+        self.get_neighbours(id1).is_some_and(|neighbours| neighbours.contains(id2))
+    }
+}
@@ -1,11 +1,11 @@
 // use std::io;
 // use std::fs::File;
 // use std::io::Write;
-use std::collections::HashMap;
+use std::collections::{ HashMap, HashSet };
 
 use cryptocol::random::Random as PRNG;
 
-use crate::{ QBank, Student };
+use crate::{ Blueprint, ChiSquare, Choices, Constraint, GenerationError, QBank, Question, QuestionOrder, RandomSource, Student, Values };
 
 
 // const NUMBER_QUESTIONS: usize = 48; // 51;
@@ -24,17 +24,25 @@ pub type ShuffledQSets = Vec<ShuffledQSet>;
 #[derive(Debug, Clone)]
 pub struct ShuffledQuestion
 {
-    question: u16,      // 1-based index into the original QBank.
-    choices: Vec<u8>,   // 1-based indices representing the shuffled order of choices.
+    bank: u16,          // 0-based index of the QBank which the question comes from.
+    question: u16,      // The ID of the original question in its QBank.
+    choices: Vec<u8>,   // 1-based indices representing the shuffled order of the choices shown.
+    pinned: Vec<u8>,    // 1-based indices of the choices that stay at their original positions.
+    ordered: Vec<u8>,   // 1-based indices of the choices that keep their relative order.
+    values: Values,     // The values of the parameters of a templated question given to the student.
 }
 
 impl ShuffledQuestion
 {
+    /// The number of times that `draw_values()` draws the values of the
+    /// parameters of a templated question before it gives up.
+    pub const VALUE_ATTEMPTS: u64 = 64;
+
     // pub fn new(question: u16, number_of_choices: u8) -> Self
     /// Creates a new `ShuffledQuestion` with an ordered list of choices.
     /// 
     /// # Arguments
-    /// * `question` - The 1-based index of the question in the `QBank`.
+    /// * `question` - The ID of the original question in the `QBank`.
     /// * `number_of_choices` - The total number of choices for this question.
     /// 
     /// # Output
@@ -44,6 +52,7 @@ impl ShuffledQuestion
     /// ```
     /// use qrate::shuffler::ShuffledQuestion;
     /// let sq = ShuffledQuestion::new(10, 4);
+    /// assert_eq!(sq.get_bank(), 0);
     /// assert_eq!(sq.get_question(), 10);
     /// assert_eq!(sq.how_many_choices(), 4);
     /// ```
@@ -52,14 +61,108 @@ impl ShuffledQuestion
         let mut choices = Vec::new();
         for i in 1..=number_of_choices
             { choices.push(i); }
-        ShuffledQuestion { question, choices }
+        ShuffledQuestion { bank: 0, question, choices, pinned: Vec::new(), ordered: Vec::new(), values: Values::new() }
+    }
+
+    // pub fn new_with_question(origin: &Question) -> Self
+    /// Creates a new `ShuffledQuestion` with an ordered list of the choices of `origin`,
+    /// keeping the pinned choices of `origin` in place and its ordered choices
+    /// in their relative order when shuffled.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question`.
+    ///
+    /// # Output
+    /// `Self` - A new `ShuffledQuestion` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Question, shuffler::ShuffledQuestion };
+    /// let mut origin = Question::new(7, 1, 1, "Q".to_string(), vec![("A".to_string(), true), ("B".to_string(), false), ("None of the above".to_string(), false)]);
+    /// origin.set_pinned(vec![3]);
+    /// let sq = ShuffledQuestion::new_with_question(&origin);
+    /// assert_eq!(sq.get_question(), 7);
+    /// assert_eq!(sq.get_choices(), &vec![1, 2, 3]);
+    /// assert_eq!(sq.get_pinned(), &vec![3]);
+    /// ```
+    pub fn new_with_question(origin: &Question) -> Self
+    {
+        let mut shuffled_question = Self::new(origin.get_id(), origin.get_choices().len() as u8);
+        shuffled_question.pinned = origin.get_pinned().clone();
+        shuffled_question.ordered = origin.get_ordered().clone();
+        shuffled_question
+    }
+
+    // pub fn get_bank(&self) -> u16
+    /// Gets the 0-based index of the question bank which the original question comes from.
+    /// It is 0 unless the exam is drawn from several question banks.
+    ///
+    /// # Output
+    /// `u16` - The index of the question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let sq = ShuffledQuestion::new(5, 4);
+    /// assert_eq!(sq.get_bank(), 0);
+    /// ```
+    #[inline]
+    pub fn get_bank(&self) -> u16
+    {
+        self.bank
+    }
+
+    // pub fn set_bank(&mut self, bank: u16)
+    /// Sets the 0-based index of the question bank which the original question comes from.
+    ///
+    /// # Arguments
+    /// * `bank` - The new 0-based index of the question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let mut sq = ShuffledQuestion::new(5, 4);
+    /// sq.set_bank(1);
+    /// assert_eq!(sq.get_bank(), 1);
+    /// assert_eq!(sq.get_question(), 5);
+    /// ```
+    #[inline]
+    pub fn set_bank(&mut self, bank: u16)
+    {
+        self.bank = bank;
+    }
+
+    // pub fn is_same_question(&self, other: &ShuffledQuestion) -> bool
+    /// Checks if this and `other` refer to the same original question,
+    /// that is, the question of the same ID in the same question bank.
+    ///
+    /// # Arguments
+    /// * `other` - A reference to the other `ShuffledQuestion`.
+    ///
+    /// # Output
+    /// `bool` - `true` if both refer to the same original question, regardless of the order of their choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let sq1 = ShuffledQuestion::new(5, 4);
+    /// let mut sq2 = ShuffledQuestion::new(5, 4);
+    /// sq2.set_choices(vec![2, 1, 4, 3]);
+    /// assert!(sq1.is_same_question(&sq2));
+    /// sq2.set_bank(1);
+    /// assert!(!sq1.is_same_question(&sq2));
+    /// ```
+    #[inline]
+    pub fn is_same_question(&self, other: &ShuffledQuestion) -> bool
+    {
+        self.bank == other.bank && self.question == other.question
     }
 
     // pub fn get_question(&self) -> u16
-    /// Gets the 1-based index of the original question.
+    /// Gets the ID of the original question, as `Question::get_id()` gives it.
     /// 
     /// # Output
-    /// `u16` - The index of the question.
+    /// `u16` - The ID of the question, which is not its position in the `QBank`.
     /// 
     /// # Examples
     /// ```
@@ -74,10 +177,10 @@ impl ShuffledQuestion
     }
 
     // pub fn set_question(&mut self, question: u16)
-    /// Sets the 1-based index of the original question.
+    /// Sets the ID of the original question.
     /// 
     /// # Arguments
-    /// * `question` - The new 1-based index for the question.
+    /// * `question` - The ID of the new original question.
     /// 
     /// # Examples
     /// ```
@@ -180,291 +283,1374 @@ impl ShuffledQuestion
         self.choices = choices;
     }
 
-    // pub fn how_many_choices(&self) -> usize
-    /// Returns the number of choices for the question.
-    /// 
+    // pub fn get_pinned(&self) -> &Vec<u8>
+    /// Gets the choices that stay at their original positions when shuffled.
+    ///
     /// # Output
-    /// `usize` - The number of choices.
-    /// 
+    /// `&Vec<u8>` - A reference to the 1-based indices of the pinned choices.
+    ///
     /// # Examples
     /// ```
     /// use qrate::shuffler::ShuffledQuestion;
-    /// let sq = ShuffledQuestion::new(1, 5);
-    /// assert_eq!(sq.how_many_choices(), 5);
+    /// let sq = ShuffledQuestion::new(1, 4);
+    /// assert!(sq.get_pinned().is_empty());
     /// ```
     #[inline]
-    pub fn how_many_choices(&self) -> usize
+    pub fn get_pinned(&self) -> &Vec<u8>
     {
-        self.choices.len()
+        &self.pinned
     }
 
-    // pub fn shuffle(&mut self)
-    /// Shuffles the order of the choices in place.
-    /// 
+    // pub fn set_pinned(&mut self, pinned: Vec<u8>)
+    /// Sets the choices that stay at their original positions when shuffled.
+    ///
+    /// # Arguments
+    /// * `pinned` - The 1-based indices of the choices to pin.
+    ///
     /// # Examples
     /// ```
     /// use qrate::shuffler::ShuffledQuestion;
     /// let mut sq = ShuffledQuestion::new(1, 4);
-    /// let original_choices = sq.get_choices().clone();
-    /// sq.shuffle();
-    /// // The order is random, so we just check that the elements are the same
-    /// let mut shuffled_choices = sq.get_choices().clone();
-    /// shuffled_choices.sort();
-    /// assert_eq!(original_choices, shuffled_choices);
+    /// sq.set_pinned(vec![4]);
+    /// assert_eq!(sq.get_pinned(), &vec![4]);
     /// ```
-    pub fn shuffle(&mut self)
+    #[inline]
+    pub fn set_pinned(&mut self, pinned: Vec<u8>)
     {
-        let mut prng = PRNG::new();
-        let max = self.how_many_choices();
-        for _ in 0..3
-        {
-            for i in 0..max
-            {
-                let j = prng.random_under_uint_(max);
-                (self.choices[i], self.choices[j]) = (self.choices[j], self.choices[i]);
-            }
-        }
+        self.pinned = pinned;
     }
-}
-
-
-/// Represents a complete set of shuffled questions for a single student.
-#[derive(Debug, Clone)]
-pub struct ShuffledQSet
-{
-    student: Student,
-    questions: ShuffledQuestions,
-}
 
-impl ShuffledQSet
-{
-    // pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, student: &Student) -> Option<Self>
-    /// Creates a new set of shuffled questions for a student by randomly selecting a specified number of questions from a `QBank` within a given range.
-    /// Each selected question will belong to a unique group. The choices for each question are shuffled upon creation.
-    ///
-    /// # Arguments
-    /// * `qbank` - A reference to the `QBank` to draw questions from.
-    /// * `start` - The 1-based starting index of questions to consider from the `QBank`.
-    /// * `end` - The 1-based ending index of questions to consider from the `QBank`.
-    /// * `selected` - The number of questions to randomly select. Each selected question will have a unique group ID.
-    /// * `student` - The `Student` for whom this question set is.
+    // pub fn get_ordered(&self) -> &Vec<u8>
+    /// Gets the choices that keep their relative order when shuffled.
     ///
     /// # Output
-    /// `Option<Self>` - A new `ShuffledQSet` instance, or `None` if:
-    ///                  - The question range is invalid (start > end, start > last, end > last, or selected is 0).
-    ///                  - The number of available unique question groups is less than `selected`.
+    /// `&Vec<u8>` - A reference to the 1-based indices of the ordered choices.
     ///
     /// # Examples
     /// ```
-    /// use qrate::{QBank, Student, Question, shuffler::ShuffledQSet};
-    /// let mut qbank = QBank::new_with_default();
-    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // id 1, group 1
-    /// qbank.push_question(Question::new(2, 1, 1, "Q2".to_string(), vec![])); // id 2, group 1
-    /// qbank.push_question(Question::new(3, 2, 1, "Q3".to_string(), vec![])); // id 3, group 2
-    /// qbank.push_question(Question::new(4, 3, 1, "Q4".to_string(), vec![])); // id 4, group 3
-    /// qbank.push_question(Question::new(5, 4, 1, "Q5".to_string(), vec![])); // id 5, group 4
-    ///
-    /// let student = Student::new("Test".to_string(), "123".to_string());
-    /// // Select 3 questions from unique groups between ID 1 and 5
-    /// let qset = ShuffledQSet::new(&qbank, 1, 5, 3, &student).unwrap();
-    /// assert_eq!(qset.get_student().get_name(), "Test");
-    /// assert_eq!(qset.get_shuffled_questions().len(), 3);
-    ///
-    /// // Try to select more questions than available unique groups (4 unique groups total)
-    /// let qset_none = ShuffledQSet::new(&qbank, 1, 5, 5, &student);
-    /// assert!(qset_none.is_none());
-    ///
-    /// // Invalid range
-    /// let qset_invalid_range = ShuffledQSet::new(&qbank, 5, 1, 1, &student);
-    /// assert!(qset_invalid_range.is_none());
-    ///
-    /// // Selected count is 0
-    /// let qset_zero_selected = ShuffledQSet::new(&qbank, 1, 5, 0, &student);
-    /// assert!(qset_zero_selected.is_none());
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let sq = ShuffledQuestion::new(1, 4);
+    /// assert!(sq.get_ordered().is_empty());
     /// ```
-    pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, student: &Student) -> Option<Self>
+    #[inline]
+    pub fn get_ordered(&self) -> &Vec<u8>
     {
-        let last = qbank.get_questions().len() as u16;
-        if (start == 0) || (start > end) || (start > last) || (end > last) || (selected == 0)
-            { return None }
-
-        // Filter questions by range
-        let questions_in_range: Vec<crate::Question> = qbank.get_questions()
-            .iter()
-            .filter(|q| q.get_id() >= start && q.get_id() <= end)
-            .cloned() // Clone to get owned Question objects
-            .collect();
-
-        // Group questions by group id
-        let mut grouped_questions: HashMap<u16, Vec<crate::Question>> = HashMap::new(); // Change Vec<&crate::Question> to Vec<crate::Question>
-        for question in questions_in_range // question is now crate::Question
-            { grouped_questions.entry(question.get_group()).or_default().push(question); }
-
-        let mut available_groups_keys: Vec<u16> = grouped_questions.keys().cloned().collect();
-        if available_groups_keys.len() < selected
-            { return None; }
-
-        let mut prng = PRNG::new(); // Slapdash::new() returns a Random_Generic object
-        let mut selected_shuffled_questions = ShuffledQuestions::new();
-
-        for _ in 0..selected
-        {
-            // Randomly select a group key
-            let group_key_index = prng.random_under_uint_(available_groups_keys.len());
-            let selected_group_key = available_groups_keys.remove(group_key_index as usize);
-
-            // From the selected group, pick one question randomly
-            if let Some(questions_in_group) = grouped_questions.get(&selected_group_key)
-            {
-                if !questions_in_group.is_empty()
-                {
-                    let question_index = prng.random_under_uint_(questions_in_group.len());
-                    let original_question = &questions_in_group[question_index as usize]; // original_question is now &crate::Question
-                    let number_of_choices = original_question.get_choices().len() as u8;
-                    let mut shuffled_question = ShuffledQuestion::new(original_question.get_id(), number_of_choices);
-                    shuffled_question.shuffle();
-                    selected_shuffled_questions.push(shuffled_question);
-                }
-            }
-        }
-        
-        Some(Self{ student: student.clone(), questions: selected_shuffled_questions })
+        &self.ordered
     }
 
-    // pub fn shuffle(&mut self)
-    /// Shuffles the order of the questions within the set.
-    /// 
+    // pub fn set_ordered(&mut self, ordered: Vec<u8>)
+    /// Sets the choices that keep their relative order when shuffled.
+    ///
+    /// # Arguments
+    /// * `ordered` - The 1-based indices of the choices to keep in order.
+    ///
     /// # Examples
     /// ```
-    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
-    /// use qrate::Question;
-    /// let mut qbank = QBank::new_with_default();
-    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
-    /// qbank.push_question(Question::new(2, 1, 1, "Q2".to_string(), vec![]));
-    /// qbank.push_question(Question::new(3, 1, 1, "Q3".to_string(), vec![]));
-    /// qbank.push_question(Question::new(4, 1, 1, "Q4".to_string(), vec![]));
-    /// qbank.push_question(Question::new(5, 1, 1, "Q5".to_string(), vec![]));
-    /// let student = Student::new("Test".to_string(), "123".to_string());
-    /// let mut qset = ShuffledQSet::new(&qbank, &student, 1, 5).unwrap();
-    /// let original_order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
-    /// qset.shuffle();
-    /// let shuffled_order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
-    /// assert_eq!(original_order.len(), shuffled_order.len());
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let mut sq = ShuffledQuestion::new(1, 4);
+    /// sq.set_ordered(vec![1, 2, 3]);
+    /// assert_eq!(sq.get_ordered(), &vec![1, 2, 3]);
     /// ```
-    pub fn shuffle(&mut self)
+    #[inline]
+    pub fn set_ordered(&mut self, ordered: Vec<u8>)
     {
-        let mut prng = PRNG::new();
-        let max = self.questions.len();
-        for _ in 0..3
-        {
-            for i in 0..max
-            {
-                let j = prng.random_under_uint_(max);
-                (self.questions[i], self.questions[j]) = (self.questions[j].clone(), self.questions[i].clone());
-            }
-        }
+        self.ordered = ordered;
     }
 
-    // pub fn get_student(&self) -> &Student
-    /// Gets a reference to the `Student` associated with this question set.
-    /// 
+    // pub fn get_values(&self) -> &Values
+    /// Gets the values of the parameters that the student is given
+    /// for a templated question.
+    ///
     /// # Output
-    /// `&Student` - A reference to the student.
-    /// 
+    /// `&Values` - A reference to the values as pairs of a name and a value,
+    /// which is empty unless the original question is templated.
+    ///
     /// # Examples
     /// ```
-    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
-    /// use qrate::Question;
-    /// let mut qbank = QBank::new_with_default();
-    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
-    /// let student = Student::new("Test".to_string(), "123".to_string());
-    /// let qset = ShuffledQSet::new(&qbank, &student, 1, 1).unwrap();
-    /// assert_eq!(qset.get_student().get_name(), "Test");
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let sq = ShuffledQuestion::new(1, 4);
+    /// assert!(sq.get_values().is_empty());
     /// ```
     #[inline]
-    pub fn get_student(&self) -> &Student
+    pub fn get_values(&self) -> &Values
     {
-        &self.student
+        &self.values
     }
 
-    // pub fn set_student(&mut self, student: &Student)
-    /// Sets the `Student` for this question set.
-    /// 
+    // pub fn set_values(&mut self, values: Values)
+    /// Sets the values of the parameters that the student is given
+    /// for a templated question.
+    ///
     /// # Arguments
-    /// * `student` - A reference to the new `Student`.
-    /// 
+    /// * `values` - The values as pairs of a name and a value.
+    ///
     /// # Examples
     /// ```
-    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
-    /// use qrate::Question;
-    /// let mut qbank = QBank::new_with_default();
-    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
-    /// let mut student1 = Student::new("Test1".to_string(), "123".to_string());
-    /// let student2 = Student::new("Test2".to_string(), "456".to_string());
-    /// let mut qset = ShuffledQSet::new(&qbank, &student1, 1, 1).unwrap();
-    /// qset.set_student(&student2);
-    /// assert_eq!(qset.get_student().get_name(), "Test2");
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let mut sq = ShuffledQuestion::new(1, 4);
+    /// sq.set_values(vec![("a".to_string(), 3), ("b".to_string(), 42)]);
+    /// assert_eq!(sq.get_values()[1], ("b".to_string(), 42));
     /// ```
     #[inline]
-    pub fn set_student(&mut self, student: &Student)
+    pub fn set_values(&mut self, values: Values)
     {
-        self.student = student.clone();
+        self.values = values;
     }
 
-    // pub fn get_shuffled_questions(&self) -> &ShuffledQuestions
-    /// Gets a reference to the shuffled questions.
+
+    // pub fn how_many_choices(&self) -> usize
+    /// Returns the number of choices for the question.
     /// 
     /// # Output
-    /// `&ShuffledQuestions` - A reference to the vector of `ShuffledQuestion`s.
+    /// `usize` - The number of choices.
     /// 
     /// # Examples
     /// ```
-    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
-    /// use qrate::Question;
-    /// let mut qbank = QBank::new_with_default();
-    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
-    /// qbank.push_question(Question::new(2, 1, 1, "Q2".to_string(), vec![]));
-    /// qbank.push_question(Question::new(3, 1, 1, "Q3".to_string(), vec![]));
-    /// let student = Student::new("Test".to_string(), "123".to_string());
-    /// let qset = ShuffledQSet::new(&qbank, &student, 1, 3).unwrap();
-    /// assert_eq!(qset.get_shuffled_questions().len(), 3);
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let sq = ShuffledQuestion::new(1, 5);
+    /// assert_eq!(sq.how_many_choices(), 5);
     /// ```
     #[inline]
-    pub fn get_shuffled_questions(&self) -> &ShuffledQuestions
+    pub fn how_many_choices(&self) -> usize
     {
-        &self.questions
+        self.choices.len()
     }
 
-    // pub fn set_shuffled_questions(&mut self, questions: ShuffledQuestions)
-    /// Replaces the shuffled questions in this set.
-    /// 
+    // pub fn get_shuffled_choices(&self, origin: &Question) -> Option<Choices>
+    /// Rearranges the choices of the original question into the shuffled order.
+    ///
     /// # Arguments
-    /// * `questions` - The new vector of `ShuffledQuestion`s.
-    /// 
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    ///
+    /// # Output
+    /// `Option<Choices>` - The choices of `origin` in the shuffled order,
+    /// or `None` if a shuffled index does not exist in `origin`.
+    ///
     /// # Examples
     /// ```
-    /// use qrate::{QBank, Student, shuffler::{ShuffledQSet, ShuffledQuestion}};
-    /// use qrate::Question;
-    /// let mut qbank = QBank::new_with_default();
-    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
-    /// let student = Student::new("Test".to_string(), "123".to_string());
-    /// let mut qset = ShuffledQSet::new(&qbank, &student, 1, 1).unwrap();
-    /// let new_questions = vec![ShuffledQuestion::new(10, 4), ShuffledQuestion::new(11, 4)];
-    /// qset.set_shuffled_questions(new_questions);
-    /// assert_eq!(qset.get_shuffled_questions().len(), 2);
-    /// assert_eq!(qset.get_shuffled_questions()[0].get_question(), 10);
+    /// use qrate::{ Question, shuffler::ShuffledQuestion };
+    /// let origin = Question::new(1, 1, 1, "Q".to_string(), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)]);
+    /// let mut sq = ShuffledQuestion::new(1, 3);
+    /// sq.set_choices(vec![3, 1, 2]);
+    /// let choices = sq.get_shuffled_choices(&origin).unwrap();
+    /// assert_eq!(choices[0].0, "C");
+    /// assert_eq!(choices[1], ("A".to_string(), true));
+    /// assert_eq!(choices[2].0, "B");
     /// ```
-    #[inline]
-    pub fn set_shuffled_questions(&mut self, questions: ShuffledQuestions)
+    pub fn get_shuffled_choices(&self, origin: &Question) -> Option<Choices>
     {
-        self.questions = questions;
+        let mut choices = Choices::new();
+        for &idx in &self.choices
+            { choices.push(origin.get_choice(idx as usize)?.clone()); }
+        Some(choices)
     }
 
-    // pub fn get_shuffled_question(&self, question_number: u16) -> Option<&ShuffledQuestion>
-    /// Retrieves a reference to a `ShuffledQuestion` by its 1-based question number.
-    ///
-    /// # Arguments
-    /// * `question_number` - The 1-based index of the question to retrieve.
+    // pub fn shuffle(&mut self)
+    /// Shuffles the order of the choices in place.
+    ///
+    /// Every order of the choices is equally likely, as `test_uniformity()` checks.
+    /// The pinned choices are put at their original positions and stay there,
+    /// while the other choices are shuffled among the remaining positions.
+    /// The ordered choices land on random positions, but in their original order.
+    /// 
+    /// # Examples
+    /// ```
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let mut sq = ShuffledQuestion::new(1, 4);
+    /// let original_choices = sq.get_choices().clone();
+    /// sq.shuffle();
+    /// // The order is random, so we just check that the elements are the same
+    /// let mut shuffled_choices = sq.get_choices().clone();
+    /// shuffled_choices.sort();
+    /// assert_eq!(original_choices, shuffled_choices);
+    ///
+    /// // "Both A and B" and "None of the above" keep their meaning.
+    /// let mut sq = ShuffledQuestion::new(1, 5);
+    /// sq.set_pinned(vec![1, 2, 4, 5]);
+    /// sq.shuffle();
+    /// assert_eq!(sq.get_choices(), &vec![1, 2, 3, 4, 5]);
+    /// let mut sq = ShuffledQuestion::new(1, 5);
+    /// sq.set_pinned(vec![5]);
+    /// for _ in 0..10
+    /// {
+    ///     sq.shuffle();
+    ///     assert_eq!(sq.get_choice(5), 5);
+    /// }
+    ///
+    /// // "1 m", "1 km" and "1,000 km" move but stay in ascending order.
+    /// let mut sq = ShuffledQuestion::new(1, 5);
+    /// sq.set_ordered(vec![1, 2, 3]);
+    /// for _ in 0..10
+    /// {
+    ///     sq.shuffle();
+    ///     let ordered: Vec<u8> = sq.get_choices().iter().copied().filter(|&c| c <= 3).collect();
+    ///     assert_eq!(ordered, vec![1, 2, 3]);
+    /// }
+    /// ```
+    #[inline]
+    pub fn shuffle(&mut self)
+    {
+        self.shuffle_with_source(&mut PRNG::new());
+    }
+
+    // pub fn shuffle_with_source(&mut self, source: &mut dyn RandomSource)
+    /// Shuffles the order of the choices in place as `shuffle()` does,
+    /// drawing from the given `source` instead of the default generator.
+    ///
+    /// # Arguments
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::shuffler::ShuffledQuestion;
+    ///
+    /// let mut sq1 = ShuffledQuestion::new(1, 4);
+    /// sq1.shuffle_with_source(&mut Random::new_with_seeds(7, 7));
+    /// let mut sq2 = ShuffledQuestion::new(1, 4);
+    /// sq2.shuffle_with_source(&mut Random::new_with_seeds(7, 7));
+    /// assert_eq!(sq1.get_choices(), sq2.get_choices());
+    /// let mut choices = sq1.get_choices().clone();
+    /// choices.sort();
+    /// assert_eq!(choices, vec![1, 2, 3, 4]);
+    /// ```
+    pub fn shuffle_with_source(&mut self, source: &mut dyn RandomSource)
+    {
+        let is_pinned = |choice: u8| self.pinned.contains(&choice);
+        let mut movable: Vec<u8> = self.choices.iter().copied().filter(|&c| !is_pinned(c)).collect();
+        shuffle_slice(&mut movable, source);
+
+        // The pinned choices go back to their original positions among the choices shown,
+        // and the others fill the rest.
+        let mut shown = self.choices.clone();
+        shown.sort_unstable();
+        let mut movable = movable.into_iter();
+        self.choices = shown.into_iter()
+            .map(|choice| if is_pinned(choice) { choice } else { movable.next().unwrap_or(choice) })
+            .collect();
+        self.keep_relative_order();
+    }
+
+    // fn keep_relative_order(&mut self)
+    /// Puts the ordered choices which are not pinned back in their original order
+    /// on the positions that they have landed on.
+    fn keep_relative_order(&mut self)
+    {
+        let is_kept_in_order = |choice: u8| self.ordered.contains(&choice) && !self.pinned.contains(&choice);
+        let positions: Vec<usize> = (0..self.choices.len()).filter(|&p| is_kept_in_order(self.choices[p])).collect();
+        let mut ordered: Vec<u8> = positions.iter().map(|&p| self.choices[p]).collect();
+        ordered.sort_unstable();
+        for (&p, choice) in positions.iter().zip(ordered)
+            { self.choices[p] = choice; }
+    }
+
+    // pub fn sample_choices(&mut self, origin: &Question)
+    /// Keeps the choices of `origin` that are always shown, that is, its correct
+    /// answers and its pinned choices, together with as many of its other choices
+    /// as `origin.get_shown_distractors()` tells, drawn at random.
+    ///
+    /// The kept choices are put in their original order, to be shuffled afterwards.
+    /// If `origin` shows all of its choices, nothing is left out.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Question, shuffler::ShuffledQuestion };
+    /// let choices = (1..=8).map(|i| (format!("Choice {}", i), i == 5)).collect();
+    /// let mut origin = Question::new(1, 1, 1, "Q".to_string(), choices);
+    /// origin.set_shown_distractors(Some(3));
+    ///
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// sq.sample_choices(&origin);
+    /// sq.shuffle();
+    /// assert_eq!(sq.how_many_choices(), 4);
+    /// assert!(sq.get_choices().contains(&5));   // The correct answer is always shown.
+    ///
+    /// // The answer key follows the choices shown.
+    /// let shuffled_choices = sq.get_shuffled_choices(&origin).unwrap();
+    /// assert_eq!(shuffled_choices.iter().filter(|choice| choice.1).count(), 1);
+    /// ```
+    #[inline]
+    pub fn sample_choices(&mut self, origin: &Question)
+    {
+        self.sample_choices_with_source(origin, &mut PRNG::new());
+    }
+
+    // pub fn sample_choices_with_source(&mut self, origin: &Question, source: &mut dyn RandomSource)
+    /// Keeps the choices of `origin` as `sample_choices()` does,
+    /// drawing from the given `source` instead of the default generator.
+    /// Nothing is drawn if `origin` shows all of its choices.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ Question, shuffler::ShuffledQuestion };
+    /// let choices = (1..=7).map(|i| (format!("Choice {}", i), i == 1)).collect();
+    /// let mut origin = Question::new(1, 1, 1, "Q".to_string(), choices);
+    /// origin.set_pinned(vec![7]);     // "None of the above"
+    /// origin.set_shown_distractors(Some(2));
+    ///
+    /// let mut source = Random::new_with_seeds(2026, 2026);
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// sq.sample_choices_with_source(&origin, &mut source);
+    /// assert_eq!(sq.how_many_choices(), 4);
+    /// assert_eq!((sq.get_choice(1), sq.get_choice(4)), (1, 7));
+    /// sq.shuffle_with_source(&mut source);
+    /// assert_eq!(sq.get_choice(4), 7);    // The pinned choice stays last.
+    /// ```
+    pub fn sample_choices_with_source(&mut self, origin: &Question, source: &mut dyn RandomSource)
+    {
+        let Some(shown_distractors) = origin.get_shown_distractors()
+            else { return; };
+        let number_of_choices = origin.get_choices().len() as u8;
+        let mut distractors: Vec<u8> = (1..=number_of_choices).filter(|&c| !origin.is_always_shown(c as usize)).collect();
+        shuffle_slice(&mut distractors, source);
+        distractors.truncate(shown_distractors as usize);
+        self.choices = (1..=number_of_choices).filter(|c| origin.is_always_shown(*c as usize) || distractors.contains(c)).collect();
+        self.pinned = origin.get_pinned().clone();
+    }
+
+    // pub fn draw_values(&mut self, origin: &Question) -> bool
+    /// Draws the values of the parameters of the templated question `origin`
+    /// until its text and its choices can be filled in and the choices are
+    /// distinct, trying at most `ShuffledQuestion::VALUE_ATTEMPTS` times.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    ///
+    /// # Output
+    /// `bool` - `true` if the values are drawn or `origin` is not templated,
+    /// or `false` if no values are found, in which case the values are left empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Parameter, Question, shuffler::ShuffledQuestion };
+    /// let mut origin = Question::new(1, 1, 1, "What is {a} * {b}?".to_string(),
+    ///         vec![("{a * b}".to_string(), true), ("{a + b}".to_string(), false), ("{a * b + 1}".to_string(), false)]);
+    /// origin.set_parameters(vec![Parameter::new("a".to_string(), 2, 9), Parameter::new("b".to_string(), 2, 9)]);
+    ///
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// assert!(sq.draw_values(&origin));
+    /// let (a, b) = (sq.get_values()[0].1, sq.get_values()[1].1);
+    /// assert!((2..=9).contains(&a) && (2..=9).contains(&b));
+    /// assert_ne!(a * b, a + b);   // 2 * 2 and 2 + 2 are never given.
+    /// ```
+    #[inline]
+    pub fn draw_values(&mut self, origin: &Question) -> bool
+    {
+        self.draw_values_with_source(origin, &mut PRNG::new())
+    }
+
+    // pub fn draw_values_with_source(&mut self, origin: &Question, source: &mut dyn RandomSource) -> bool
+    /// Draws the values of the parameters of `origin` as `draw_values()` does,
+    /// drawing from the given `source` instead of the default generator.
+    /// Nothing is drawn if `origin` is not templated.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Output
+    /// `bool` - `true` if the values are drawn or `origin` is not templated.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ Parameter, Question, shuffler::ShuffledQuestion };
+    /// let mut origin = Question::new(1, 1, 1, "What is {a} - {b}?".to_string(),
+    ///         vec![("{a - b}".to_string(), true), ("{b - a}".to_string(), false)]);
+    /// origin.set_parameters(vec![Parameter::new("a".to_string(), 5, 5), Parameter::new("b".to_string(), 5, 5)]);
+    ///
+    /// // 5 - 5 and 5 - 5 are always the same choice.
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// assert!(!sq.draw_values_with_source(&origin, &mut Random::new_with_seeds(2026, 2026)));
+    /// assert!(sq.get_values().is_empty());
+    /// ```
+    pub fn draw_values_with_source(&mut self, origin: &Question, source: &mut dyn RandomSource) -> bool
+    {
+        self.values = Values::new();
+        if !origin.is_parameterized()
+            { return true; }
+        for _ in 0..Self::VALUE_ATTEMPTS
+        {
+            let values: Values = origin.get_parameters().iter().map(|p| (p.get_name().clone(), p.draw_with_source(source))).collect();
+            if origin.instantiate(&values).is_some()
+            {
+                self.values = values;
+                return true;
+            }
+        }
+        false
+    }
+
+    // pub fn make_shown_question(&self, origin: &Question) -> Option<Question>
+    /// Makes the question as the student is shown it, that is, `origin` with
+    /// the values of its parameters filled in and with the shown choices in
+    /// the shuffled order.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    ///
+    /// # Output
+    /// `Option<Question>` - The question shown to the student, or `None` if
+    /// the values do not fit `origin` or a shuffled index does not exist in `origin`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Parameter, Question, shuffler::ShuffledQuestion };
+    /// let mut origin = Question::new(1, 1, 1, "What is {a} + {b}?".to_string(),
+    ///         vec![("{a + b}".to_string(), true), ("{a - b}".to_string(), false), ("{a * b}".to_string(), false)]);
+    /// origin.set_parameters(vec![Parameter::new("a".to_string(), 1, 9), Parameter::new("b".to_string(), 1, 9)]);
+    ///
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// sq.set_choices(vec![3, 1, 2]);
+    /// sq.set_values(vec![("a".to_string(), 6), ("b".to_string(), 4)]);
+    /// let shown = sq.make_shown_question(&origin).unwrap();
+    /// assert_eq!(shown.get_question(), "What is 6 + 4?");
+    /// assert_eq!(shown.get_choice(1).unwrap().0, "24");
+    /// assert_eq!(shown.get_choice(2).unwrap(), &("10".to_string(), true));
+    /// ```
+    pub fn make_shown_question(&self, origin: &Question) -> Option<Question>
+    {
+        let mut shown = if origin.is_parameterized() { origin.instantiate(&self.values)? } else { origin.clone() };
+        shown.set_choices(self.get_shuffled_choices(&shown)?);
+        shown.set_shown_distractors(None);
+        Some(shown)
+    }
+
+    // pub fn shuffle_balanced(&mut self, origin: &Question, answer_counts: &mut Vec<usize>)
+    /// Shuffles the order of the choices so that the correct answers land on
+    /// the positions where the fewest correct answers have landed so far.
+    ///
+    /// Ties are broken at random, and the incorrect choices fill the other positions
+    /// in a random order. Calling this for every question of a paper with the same
+    /// `answer_counts` spreads the correct answers evenly over the positions,
+    /// so that always picking the same position gives no advantage.
+    /// The pinned choices of `origin` stay at their original positions, and its
+    /// ordered choices are placed first, in their original order.
+    /// Both are then left where they are, and only the other choices are placed
+    /// to balance the correct answers.
+    /// Only the choices shown, as `sample_choices()` has kept them, are arranged.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    /// * `answer_counts` - The number of correct answers at each 0-based position so far, which is updated.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Question, shuffler::ShuffledQuestion };
+    /// let origin = Question::new(1, 1, 1, "Q".to_string(), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false), ("D".to_string(), false)]);
+    /// let mut answer_counts = Vec::new();
+    /// for _ in 0..8
+    /// {
+    ///     let mut sq = ShuffledQuestion::new(1, 4);
+    ///     sq.shuffle_balanced(&origin, &mut answer_counts);
+    /// }
+    /// assert_eq!(answer_counts, vec![2, 2, 2, 2]);
+    ///
+    /// let mut origin = Question::new(2, 2, 1, "Q".to_string(), vec![("A".to_string(), false), ("B".to_string(), false), ("C".to_string(), false), ("All of the above".to_string(), true)]);
+    /// origin.set_pinned(vec![4]);
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// sq.shuffle_balanced(&origin, &mut answer_counts);
+    /// assert_eq!(sq.get_choice(4), 4);
+    /// assert_eq!(answer_counts, vec![2, 2, 2, 3]);
+    ///
+    /// // The units stay in ascending order, and the answers of the other
+    /// // questions still go where the fewest answers have landed.
+    /// let mut origin = Question::new(3, 3, 1, "Q".to_string(), vec![("1 m".to_string(), false), ("1 km".to_string(), false), ("1,000 km".to_string(), false), ("Red".to_string(), true)]);
+    /// origin.set_ordered(vec![1, 2, 3]);
+    /// let mut answer_counts = vec![1, 1, 0, 1];
+    /// let mut sq = ShuffledQuestion::new_with_question(&origin);
+    /// sq.shuffle_balanced(&origin, &mut answer_counts);
+    /// assert_eq!(sq.get_choices(), &vec![1, 2, 4, 3]);
+    /// assert_eq!(answer_counts, vec![1, 1, 1, 1]);
+    /// ```
+    #[inline]
+    pub fn shuffle_balanced(&mut self, origin: &Question, answer_counts: &mut Vec<usize>)
+    {
+        self.shuffle_balanced_with_source(origin, answer_counts, &mut PRNG::new());
+    }
+
+    // pub fn shuffle_balanced_with_source(&mut self, origin: &Question, answer_counts: &mut Vec<usize>, source: &mut dyn RandomSource)
+    /// Shuffles the order of the choices as `shuffle_balanced()` does,
+    /// drawing from the given `source` instead of the default generator.
+    ///
+    /// # Arguments
+    /// * `origin` - The original `Question` that this `ShuffledQuestion` refers to.
+    /// * `answer_counts` - The number of correct answers at each 0-based position so far, which is updated.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ Question, shuffler::ShuffledQuestion };
+    /// let origin = Question::new(1, 1, 1, "Q".to_string(), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)]);
+    /// let mut source = Random::new_with_seeds(2026, 2026);
+    /// let mut answer_counts = Vec::new();
+    /// for _ in 0..6
+    /// {
+    ///     let mut sq = ShuffledQuestion::new(1, 3);
+    ///     sq.shuffle_balanced_with_source(&origin, &mut answer_counts, &mut source);
+    /// }
+    /// assert_eq!(answer_counts, vec![2, 2, 2]);
+    /// ```
+    pub fn shuffle_balanced_with_source(&mut self, origin: &Question, answer_counts: &mut Vec<usize>, source: &mut dyn RandomSource)
+    {
+        // Only the choices shown are arranged, in their original order to begin with.
+        let mut shown = self.choices.clone();
+        shown.sort_unstable();
+        let number_of_choices = shown.len();
+        if answer_counts.len() < number_of_choices
+            { answer_counts.resize(number_of_choices, 0); }
+        let is_correct = |c: u8| origin.get_choice(c as usize).is_some_and(|choice| choice.1);
+
+        // The pinned choices stay where they are, and the ordered choices take other
+        // positions in their order. Neither is moved afterwards. When no ordered choice
+        // is correct, they take the positions with the most answers, leaving the others
+        // for the answers.
+        let mut choices = vec![0_u8; number_of_choices];
+        for (p, &c) in shown.iter().enumerate()
+        {
+            if origin.is_pinned(c as usize)
+                { choices[p] = c; }
+        }
+        let is_ordered = |c: u8| origin.get_ordered().contains(&c) && !origin.is_pinned(c as usize);
+        let ordered: Vec<u8> = shown.iter().copied().filter(|&c| is_ordered(c)).collect();
+        let mut positions: Vec<usize> = (0..number_of_choices).filter(|&p| !origin.is_pinned(shown[p] as usize)).collect();
+        shuffle_slice(&mut positions, source);
+        if !ordered.iter().any(|&c| is_correct(c))
+            { positions.sort_by_key(|&p| answer_counts[p]); }
+        let mut ordered_positions = positions.split_off(positions.len() - ordered.len());
+        ordered_positions.sort_unstable();
+        for (&p, &c) in ordered_positions.iter().zip(&ordered)
+            { choices[p] = c; }
+
+        // Counting where the answers of the fixed choices land
+        for (p, &c) in choices.iter().enumerate()
+        {
+            if c != 0 && is_correct(c)
+                { answer_counts[p] += 1; }
+        }
+        let (mut correct, mut incorrect): (Vec<u8>, Vec<u8>) = shown.iter().copied()
+            .filter(|&c| !origin.is_pinned(c as usize) && !is_ordered(c))
+            .partition(|&c| is_correct(c));
+        shuffle_slice(&mut correct, source);
+        shuffle_slice(&mut incorrect, source);
+
+        // The free positions with the fewest correct answers first, in a random order among equals
+        positions.sort_by_key(|&p| answer_counts[p]);
+
+        for (i, &p) in positions.iter().enumerate()
+        {
+            if i < correct.len()
+            {
+                choices[p] = correct[i];
+                answer_counts[p] += 1;
+            }
+            else
+            {
+                choices[p] = incorrect[i - correct.len()];
+            }
+        }
+        self.choices = choices;
+        self.pinned = origin.get_pinned().clone();
+        self.ordered = origin.get_ordered().clone();
+    }
+
+    // pub fn test_uniformity(number_of_choices: u8, trials: usize, seed: u64) -> Option<ChiSquare>
+    /// Shuffles the choices of a question `trials` times reproducibly from a seed,
+    /// and tests whether every order of the choices came up equally often.
+    ///
+    /// # Arguments
+    /// * `number_of_choices` - The number of choices, from 2 to 8.
+    /// * `trials` - The number of shuffles, which should be at least five times
+    ///   the number of the orders, that is, the factorial of `number_of_choices`.
+    /// * `seed` - The seed of the pseudo-random stream.
+    ///
+    /// # Output
+    /// `Option<ChiSquare>` - The result of the chi-square test over the orders,
+    /// or `None` if `number_of_choices` is out of range or `trials` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::shuffler::ShuffledQuestion;
+    /// let chi_square = ShuffledQuestion::test_uniformity(3, 1_800, 2026).unwrap();
+    /// assert_eq!(chi_square.get_degrees_of_freedom(), 5);     // 3! orders
+    /// assert!(chi_square.is_uniform(0.001));
+    /// assert!(ShuffledQuestion::test_uniformity(9, 1000, 2026).is_none());
+    /// ```
+    pub fn test_uniformity(number_of_choices: u8, trials: usize, seed: u64) -> Option<ChiSquare>
+    {
+        if !(2..=8).contains(&number_of_choices)
+            { return None; }
+        let mut source = PRNG::new_with_seeds(seed, seed.rotate_left(32));
+        let mut counts = vec![0_usize; (1..=number_of_choices as usize).product()];
+        for _ in 0..trials
+        {
+            let mut shuffled_question = ShuffledQuestion::new(0, number_of_choices);
+            shuffled_question.shuffle_with_source(&mut source);
+            counts[rank_order(shuffled_question.get_choices().iter().map(|&c| c as usize))] += 1;
+        }
+        ChiSquare::new_with_counts(&counts)
+    }
+}
+
+
+/// Represents a complete set of shuffled questions for a single student.
+#[derive(Debug, Clone)]
+pub struct ShuffledQSet
+{
+    student: Student,
+    questions: ShuffledQuestions,
+}
+
+impl ShuffledQSet
+{
+    // pub fn new_empty() -> Self
+    /// Creates a new, empty `ShuffledQSet` with an empty `Student` and no questions.
+    ///
+    /// # Output
+    /// `Self` - A new, empty `ShuffledQSet` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::ShuffledQSet;
+    /// let qset = ShuffledQSet::new_empty();
+    /// assert_eq!(qset.get_student().get_id(), "");
+    /// assert!(qset.get_shuffled_questions().is_empty());
+    /// ```
+    #[inline]
+    pub fn new_empty() -> Self
+    {
+        Self { student: Student::new_empty(), questions: ShuffledQuestions::new() }
+    }
+
+    // pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, student: &Student) -> Result<Self, GenerationError>
+    /// Creates a new set of shuffled questions for a student by randomly selecting a specified number of questions from a `QBank` within a given range.
+    /// Each selected question will belong to a unique group. The choices for each question are shuffled upon creation.
+    /// The selected questions also keep the constraints of `qbank`, as `QBank::push_constraint()` tells.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `start` - The smallest question ID to consider from the `QBank`.
+    /// * `end` - The largest question ID to consider from the `QBank`.
+    /// * `selected` - The number of questions to randomly select. Each selected question will have a unique group ID.
+    /// * `student` - The `Student` for whom this question set is.
+    ///
+    /// # Output
+    /// `Result<Self, GenerationError>` - A new `ShuffledQSet` instance, or an error if:
+    ///                  - The question range is invalid (start is 0, start > end, or selected is 0).
+    ///                  - Two or more questions of `qbank` have the same ID.
+    ///                  - The number of available unique question groups is less than `selected`.
+    ///                  - No selection keeps the constraints of `qbank` in a number of attempts.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{GenerationError, QBank, Student, Question, shuffler::ShuffledQSet};
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // id 1, group 1
+    /// qbank.push_question(Question::new(2, 1, 1, "Q2".to_string(), vec![])); // id 2, group 1
+    /// qbank.push_question(Question::new(3, 2, 1, "Q3".to_string(), vec![])); // id 3, group 2
+    /// qbank.push_question(Question::new(4, 3, 1, "Q4".to_string(), vec![])); // id 4, group 3
+    /// qbank.push_question(Question::new(5, 4, 1, "Q5".to_string(), vec![])); // id 5, group 4
+    ///
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// // Select 3 questions from unique groups between ID 1 and 5
+    /// let qset = ShuffledQSet::new(&qbank, 1, 5, 3, &student).unwrap();
+    /// assert_eq!(qset.get_student().get_name(), "Test");
+    /// assert_eq!(qset.get_shuffled_questions().len(), 3);
+    ///
+    /// // Try to select more questions than available unique groups (4 unique groups total)
+    /// let qset_none = ShuffledQSet::new(&qbank, 1, 5, 5, &student);
+    /// assert_eq!(qset_none.unwrap_err(), GenerationError::TooFewGroups { section: 0, start: 1, end: 5, groups: 4, requested: 5 });
+    ///
+    /// // Invalid range
+    /// let qset_invalid_range = ShuffledQSet::new(&qbank, 5, 1, 1, &student);
+    /// assert_eq!(qset_invalid_range.unwrap_err(), GenerationError::InvalidRange { section: 0, start: 5, end: 1 });
+    ///
+    /// // Selected count is 0
+    /// let qset_zero_selected = ShuffledQSet::new(&qbank, 1, 5, 0, &student);
+    /// assert_eq!(qset_zero_selected.unwrap_err(), GenerationError::NothingSelected);
+    ///
+    /// // The range is of IDs, not of positions, so it may go beyond the number of questions
+    /// let qset_ids = ShuffledQSet::new(&qbank, 3, 100, 3, &student).unwrap();
+    /// assert!(qset_ids.get_shuffled_questions().iter().all(|q| q.get_question() >= 3));
+    ///
+    /// // Questions 3 to 5 are all to be selected, but questions 3 and 5 exclude each other
+    /// let mut qbank_excluding = qbank.clone();
+    /// qbank_excluding.push_constraint(qrate::Constraint::Excludes { question: 3, other: 5 });
+    /// let qset_excluding = ShuffledQSet::new(&qbank_excluding, 3, 5, 3, &student);
+    /// assert!(matches!(qset_excluding.unwrap_err(), GenerationError::ConstraintsUnmet { .. }));
+    ///
+    /// // Duplicated IDs
+    /// qbank.push_question(Question::new(5, 5, 1, "Q5 again".to_string(), vec![]));
+    /// assert_eq!(ShuffledQSet::new(&qbank, 1, 5, 3, &student).unwrap_err(), GenerationError::DuplicateId { id: 5 });
+    /// ```
+    #[inline]
+    pub fn new(qbank: &QBank, start: u16, end: u16, selected: usize, student: &Student) -> Result<Self, GenerationError>
+    {
+        let blueprint = Blueprint::new_with_range(start, end, selected);
+        let pools = [QuestionPool::new(qbank, &blueprint)?];
+        Self::new_with_pools_source(&pools, student, &SetOptions { usage: &HashMap::new(), arranged: false }, &mut PRNG::new())
+    }
+
+    // pub fn new_with_seed(qbank: &QBank, start: u16, end: u16, selected: usize, student: &Student, seed: u64) -> Result<Self, GenerationError>
+    /// Creates a new set of shuffled questions for a student reproducibly from `seed`.
+    ///
+    /// The selection of the questions, the order of their choices and the order of
+    /// the questions themselves are all drawn from one pseudo-random stream seeded
+    /// with `seed`, so the same arguments always give the same `ShuffledQSet`.
+    /// Unlike `new()`, the returned set is already shuffled.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `start` - The smallest question ID to consider from the `QBank`.
+    /// * `end` - The largest question ID to consider from the `QBank`.
+    /// * `selected` - The number of questions to randomly select. Each selected question will have a unique group ID.
+    /// * `student` - The `Student` for whom this question set is.
+    /// * `seed` - The seed of the pseudo-random stream.
+    ///
+    /// # Output
+    /// `Result<Self, GenerationError>` - A new `ShuffledQSet` instance, or an error for the same reasons as `new()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Student, Question, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)])); }
+    ///
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let qset1 = ShuffledQSet::new_with_seed(&qbank, 1, 10, 5, &student, 2026).unwrap();
+    /// let qset2 = ShuffledQSet::new_with_seed(&qbank, 1, 10, 5, &student, 2026).unwrap();
+    /// for (q1, q2) in qset1.get_shuffled_questions().iter().zip(qset2.get_shuffled_questions())
+    /// {
+    ///     assert_eq!(q1.get_question(), q2.get_question());
+    ///     assert_eq!(q1.get_choices(), q2.get_choices());
+    /// }
+    /// ```
+    #[inline]
+    pub fn new_with_seed(qbank: &QBank, start: u16, end: u16, selected: usize, student: &Student, seed: u64) -> Result<Self, GenerationError>
+    {
+        Self::new_with_blueprint(qbank, &Blueprint::new_with_range(start, end, selected), student, seed)
+    }
+
+    // pub fn new_with_quotas(qbank: &QBank, start: u16, end: u16, quotas: &[(u8, usize)], student: &Student, seed: u64) -> Result<Self, GenerationError>
+    /// Creates a new set of shuffled questions for a student reproducibly from a seed,
+    /// taking the given number of questions from each category.
+    ///
+    /// Every student's set follows the same counts, so a grading scheme which assumes
+    /// a fixed mix of question types (such as Type A and Type B of the default header)
+    /// holds for everyone. As with `new()`, each selected question belongs to a unique group.
+    /// Like `new_with_seed()`, the returned set is already shuffled.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `start` - The smallest question ID to consider from the `QBank`.
+    /// * `end` - The largest question ID to consider from the `QBank`.
+    /// * `quotas` - Pairs of a category and the number of questions to select from it.
+    /// * `student` - The `Student` for whom this question set is.
+    /// * `seed` - The seed of the pseudo-random stream.
+    ///
+    /// # Output
+    /// `Result<Self, GenerationError>` - A new `ShuffledQSet` instance, or an error if:
+    ///                  - The question range is invalid (start is 0 or start > end).
+    ///                  - No question is to be selected in total.
+    ///                  - Two or more questions of `qbank` have the same ID.
+    ///                  - A category of the quotas has no question in the range.
+    ///                  - The quotas cannot be met with questions of distinct groups.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ GenerationError, QBank, Student, Question, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10    // Questions 1 to 6 are of Type A, and 7 to 10 are of Type B.
+    ///     { qbank.push_question(Question::new(id, id, if id <= 6 { 1 } else { 2 }, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    ///
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let qset = ShuffledQSet::new_with_quotas(&qbank, 1, 10, &[(1, 3), (2, 2)], &student, 2026).unwrap();
+    /// let type_a = qset.get_shuffled_questions().iter().filter(|q| q.get_question() <= 6).count();
+    /// let type_b = qset.get_shuffled_questions().iter().filter(|q| q.get_question() > 6).count();
+    /// assert_eq!((type_a, type_b), (3, 2));
+    ///
+    /// // There are only four questions of Type B.
+    /// let error = ShuffledQSet::new_with_quotas(&qbank, 1, 10, &[(1, 3), (2, 5)], &student, 2026).unwrap_err();
+    /// assert_eq!(error, GenerationError::TooFewGroups { section: 1, start: 1, end: 10, groups: 4, requested: 5 });
+    ///
+    /// // There is no question of category 3.
+    /// let error = ShuffledQSet::new_with_quotas(&qbank, 1, 10, &[(1, 3), (3, 1)], &student, 2026).unwrap_err();
+    /// assert_eq!(error, GenerationError::MissingCategory { section: 1, start: 1, end: 10, category: 3 });
+    /// ```
+    #[inline]
+    pub fn new_with_quotas(qbank: &QBank, start: u16, end: u16, quotas: &[(u8, usize)], student: &Student, seed: u64) -> Result<Self, GenerationError>
+    {
+        Self::new_with_blueprint(qbank, &Blueprint::new_with_quotas(start, end, quotas), student, seed)
+    }
+
+    // pub fn new_with_blueprint(qbank: &QBank, blueprint: &Blueprint, student: &Student, seed: u64) -> Result<Self, GenerationError>
+    /// Creates a new set of shuffled questions for a student reproducibly from a seed,
+    /// taking from each section of `blueprint` as many questions as the section asks for.
+    ///
+    /// Each selected question belongs to a unique group across all the sections.
+    /// Like `new_with_seed()`, the returned set is already shuffled.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `blueprint` - A reference to the `Blueprint` that the set is assembled from.
+    /// * `student` - The `Student` for whom this question set is.
+    /// * `seed` - The seed of the pseudo-random stream.
+    ///
+    /// # Output
+    /// `Result<Self, GenerationError>` - A new `ShuffledQSet` instance, or an error if:
+    ///                  - `blueprint` is not valid (see `Blueprint::validate()`).
+    ///                  - Two or more questions of `qbank` have the same ID.
+    ///                  - An anchor question is not in `qbank`.
+    ///                  - The anchor questions and the questions they require cannot keep the constraints of `qbank`.
+    ///                  - A section asks for a category of which its range has no question.
+    ///                  - The sections cannot be filled with questions of distinct groups.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, Constraint, GenerationError, QBank, Question, Section, Student, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=40    // Chapter 1 has questions 1 to 10, chapter 2 has 11 to 20, and so on.
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    ///
+    /// // 6 questions from chapters 1 to 3, and 3 from chapter 4
+    /// let blueprint = Blueprint::new(vec![Section::new_with_range(1, 30, 6), Section::new_with_range(31, 40, 3)]);
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let qset = ShuffledQSet::new_with_blueprint(&qbank, &blueprint, &student, 2026).unwrap();
+    /// assert_eq!(qset.get_shuffled_questions().len(), 9);
+    /// assert_eq!(qset.get_shuffled_questions().iter().filter(|q| q.get_question() > 30).count(), 3);
+    ///
+    /// // Chapter 4 has only 10 questions.
+    /// let too_many = Blueprint::new(vec![Section::new_with_range(1, 30, 6), Section::new_with_range(31, 40, 11)]);
+    /// let error = ShuffledQSet::new_with_blueprint(&qbank, &too_many, &student, 2026).unwrap_err();
+    /// assert_eq!(error.to_string(), "range 31..=40 has 10 distinct groups, 11 requested");
+    ///
+    /// // Chapters 1 and 2 have 20 questions, which two overlapping sections cannot share.
+    /// let overlapping = Blueprint::new(vec![Section::new_with_range(1, 20, 12), Section::new_with_range(11, 20, 10)]);
+    /// let error = ShuffledQSet::new_with_blueprint(&qbank, &overlapping, &student, 2026).unwrap_err();
+    /// assert_eq!(error, GenerationError::TooFewGroupsInTotal { groups: 20, requested: 22 });
+    ///
+    /// let mut missing = blueprint.clone();
+    /// missing.push_anchor(41);
+    /// assert_eq!(ShuffledQSet::new_with_blueprint(&qbank, &missing, &student, 2026).unwrap_err(), GenerationError::MissingAnchor { id: 41 });
+    ///
+    /// // Questions 1 and 40 are for everyone, on top of the 6 + 3 sampled questions.
+    /// let mut with_anchors = blueprint.clone();
+    /// with_anchors.set_anchors(vec![1, 40]);
+    /// let qset = ShuffledQSet::new_with_blueprint(&qbank, &with_anchors, &student, 7).unwrap();
+    /// assert_eq!(qset.get_shuffled_questions().len(), 11);
+    /// assert!(qset.get_shuffled_questions().iter().any(|q| q.get_question() == 1));
+    /// assert!(qset.get_shuffled_questions().iter().any(|q| q.get_question() == 40));
+    ///
+    /// // Anchor question 1 requires question 35, which takes one of the 3 slots of chapter 4.
+    /// let mut requiring = qbank.clone();
+    /// requiring.push_constraint(Constraint::Requires { question: 1, required: 35 });
+    /// for seed in 0..20
+    /// {
+    ///     let qset = ShuffledQSet::new_with_blueprint(&requiring, &with_anchors, &student, seed).unwrap();
+    ///     let ids: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    ///     assert_eq!(ids.len(), 11);
+    ///     assert!(ids.contains(&35));
+    ///     assert_eq!(ids.iter().filter(|&&id| id > 30).count(), 4);
+    /// }
+    ///
+    /// // Anchor question 40 requires question 41, which is not in the question bank.
+    /// requiring.push_constraint(Constraint::Requires { question: 40, required: 41 });
+    /// let error = ShuffledQSet::new_with_blueprint(&requiring, &with_anchors, &student, 7).unwrap_err();
+    /// assert_eq!(error.to_string(), "the anchor questions cannot keep the constraint that question 40 requires question 41");
+    /// ```
+    #[inline]
+    pub fn new_with_blueprint(qbank: &QBank, blueprint: &Blueprint, student: &Student, seed: u64) -> Result<Self, GenerationError>
+    {
+        Self::new_with_source(qbank, blueprint, student, &mut PRNG::new_with_seeds(seed, seed.rotate_left(32)))
+    }
+
+    // pub fn new_with_source(qbank: &QBank, blueprint: &Blueprint, student: &Student, source: &mut dyn RandomSource) -> Result<Self, GenerationError>
+    /// Creates a new set of shuffled questions for a student as `new_with_blueprint()` does,
+    /// drawing from the given `source` instead of a generator seeded with a seed.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` to draw questions from.
+    /// * `blueprint` - A reference to the `Blueprint` that the set is assembled from.
+    /// * `student` - The `Student` for whom this question set is.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Output
+    /// `Result<Self, GenerationError>` - A new `ShuffledQSet` instance, or an error for the same reasons as `new_with_blueprint()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ Blueprint, QBank, Question, Student, shuffler::ShuffledQSet };
+    ///
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=10
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let blueprint = Blueprint::new_with_range(1, 10, 4);
+    ///
+    /// let qset1 = ShuffledQSet::new_with_source(&qbank, &blueprint, &student, &mut Random::new_with_seeds(7, 7)).unwrap();
+    /// let qset2 = ShuffledQSet::new_with_source(&qbank, &blueprint, &student, &mut Random::new_with_seeds(7, 7)).unwrap();
+    /// assert_eq!(qset1.get_shuffled_questions().len(), 4);
+    /// assert_eq!(qset1.count_same_position(&qset2), 4);
+    /// ```
+    #[inline]
+    pub fn new_with_source(qbank: &QBank, blueprint: &Blueprint, student: &Student, source: &mut dyn RandomSource) -> Result<Self, GenerationError>
+    {
+        let pools = [QuestionPool::new(qbank, blueprint)?];
+        Self::new_with_pools_source(&pools, student, &SetOptions { usage: &HashMap::new(), arranged: true }, source)
+    }
+
+    // pub fn new_with_banks(qbanks: &[QBank], blueprints: &[Blueprint], student: &Student, seed: u64) -> Result<Self, GenerationError>
+    /// Creates a new set of shuffled questions for a student reproducibly from a seed,
+    /// drawing from each question bank of `qbanks` as its blueprint in `blueprints` asks.
+    ///
+    /// The questions of each bank are selected as `new_with_blueprint()` selects them,
+    /// so groups and anchors are kept within each bank. Each shuffled question
+    /// remembers the 0-based index of its bank, so question 3 of bank 0 and
+    /// question 3 of bank 1 are different questions. The questions of all the banks
+    /// are then shuffled together, or, if the blueprints ask for another
+    /// `QuestionOrder`, put in that order with the questions of each bank together.
+    /// All the blueprints must ask for the same `QuestionOrder`.
+    ///
+    /// # Arguments
+    /// * `qbanks` - The `QBank`s to draw questions from.
+    /// * `blueprints` - The `Blueprint` for each question bank of `qbanks`, in the same order.
+    /// * `student` - The `Student` for whom this question set is.
+    /// * `seed` - The seed of the pseudo-random stream.
+    ///
+    /// # Output
+    /// `Result<Self, GenerationError>` - A new `ShuffledQSet` instance, or an error if:
+    ///                  - No question bank is given.
+    ///                  - The numbers of question banks and blueprints differ.
+    ///                  - A blueprint asks for another `QuestionOrder` than the first one.
+    ///                  - The set cannot be drawn from a question bank for the reasons
+    ///                    that `new_with_blueprint()` explains, which is wrapped in `GenerationError::InBank`
+    ///                    if there are more than one question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, GenerationError, QBank, Question, QuestionOrder, Student, shuffler::ShuffledQSet };
+    /// let mut networks = QBank::new_with_default();
+    /// let mut security = QBank::new_with_default();
+    /// for id in 1..=10
+    /// {
+    ///     networks.push_question(Question::new(id, id, 1, format!("Network Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    ///     security.push_question(Question::new(id, id, 1, format!("Security Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)]));
+    /// }
+    /// let qbanks = vec![networks, security];
+    /// let blueprints = vec![Blueprint::new_with_range(1, 10, 3), Blueprint::new_with_range(1, 10, 2)];
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    ///
+    /// let qset = ShuffledQSet::new_with_banks(&qbanks, &blueprints, &student, 2026).unwrap();
+    /// assert_eq!(qset.get_shuffled_questions().len(), 5);
+    /// assert_eq!(qset.get_shuffled_questions().iter().filter(|q| q.get_bank() == 0).count(), 3);
+    /// assert_eq!(qset.get_shuffled_questions().iter().filter(|q| q.get_bank() == 1).count(), 2);
+    /// let again = ShuffledQSet::new_with_banks(&qbanks, &blueprints, &student, 2026).unwrap();
+    /// assert_eq!(qset.count_same_position(&again), 5);
+    ///
+    /// let too_many = vec![Blueprint::new_with_range(1, 10, 3), Blueprint::new_with_range(1, 10, 11)];
+    /// let error = ShuffledQSet::new_with_banks(&qbanks, &too_many, &student, 2026).unwrap_err();
+    /// assert_eq!(error.to_string(), "question bank 1: range 1..=10 has 10 distinct groups, 11 requested");
+    /// let error = ShuffledQSet::new_with_banks(&qbanks, &blueprints[..1], &student, 2026).unwrap_err();
+    /// assert_eq!(error, GenerationError::BlueprintCount { banks: 2, blueprints: 1 });
+    ///
+    /// let mut by_category = blueprints.clone();
+    /// by_category[1].set_order(QuestionOrder::ShuffledWithinCategory);
+    /// let error = ShuffledQSet::new_with_banks(&qbanks, &by_category, &student, 2026).unwrap_err();
+    /// assert_eq!(error, GenerationError::OrderMismatch { bank: 1 });
+    /// ```
+    #[inline]
+    pub fn new_with_banks(qbanks: &[QBank], blueprints: &[Blueprint], student: &Student, seed: u64) -> Result<Self, GenerationError>
+    {
+        let pools = QuestionPool::new_with_banks(qbanks, blueprints)?;
+        Self::new_with_pools_source(&pools, student, &SetOptions { usage: &HashMap::new(), arranged: true }, &mut PRNG::new_with_seeds(seed, seed.rotate_left(32)))
+    }
+
+    // pub(crate) fn new_with_pools_source(pools: &[QuestionPool], student: &Student, options: &SetOptions, source: &mut dyn RandomSource) -> Result<Self, GenerationError>
+    /// Does the work of all the other constructors with the pool of each question bank,
+    /// drawing from the given `source`, so that the pools are grouped once for many students.
+    /// With more than one pool, an error of a question bank is wrapped in `GenerationError::InBank`.
+    pub(crate) fn new_with_pools_source(pools: &[QuestionPool], student: &Student, options: &SetOptions, source: &mut dyn RandomSource) -> Result<Self, GenerationError>
+    {
+        let mut questions = ShuffledQuestions::new();
+        for (bank, pool) in pools.iter().enumerate()
+        {
+            let shuffled_qset = pool.select(student, options.usage, source)
+                .map_err(|error| if pools.len() == 1 { error } else { GenerationError::InBank { bank, error: Box::new(error) } })?;
+            for mut shuffled_question in shuffled_qset.questions
+            {
+                shuffled_question.set_bank(bank as u16);
+                questions.push(shuffled_question);
+            }
+        }
+        // With any order other than `QuestionOrder::Shuffled`, the questions of each bank stay together.
+        let order = pools[0].blueprint.get_order();
+        let order_key = |shuffled_question: &ShuffledQuestion| {
+            let pool = &pools[shuffled_question.get_bank() as usize];
+            (shuffled_question.get_bank(), pool.blueprint.get_order_key(pool.qbank, shuffled_question))
+        };
+        let mut shuffled_qset = Self { student: student.clone(), questions };
+        if options.arranged
+            { shuffled_qset.arrange_by_key(order, order_key, source); }
+        Ok(shuffled_qset)
+    }
+
+    // pub fn derive_seed(seed: u64, student: &Student) -> u64
+    /// Derives the seed of a student's question set from a master seed and the student's ID.
+    ///
+    /// The derivation is fixed and does not depend on the platform or the Rust version,
+    /// so a seed recorded today gives the same seed for the same student later.
+    /// Students with the same ID get the same seed.
+    ///
+    /// # Arguments
+    /// * `seed` - The master seed of the whole examination.
+    /// * `student` - The `Student` whose seed is derived.
+    ///
+    /// # Output
+    /// `u64` - The seed for the student's question set.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Student, shuffler::ShuffledQSet };
+    /// let alice = Student::new("Alice".to_string(), "1".to_string());
+    /// let bob = Student::new("Bob".to_string(), "2".to_string());
+    /// assert_eq!(ShuffledQSet::derive_seed(7, &alice), ShuffledQSet::derive_seed(7, &alice));
+    /// assert_ne!(ShuffledQSet::derive_seed(7, &alice), ShuffledQSet::derive_seed(7, &bob));
+    /// assert_ne!(ShuffledQSet::derive_seed(7, &alice), ShuffledQSet::derive_seed(8, &alice));
+    /// ```
+    pub fn derive_seed(seed: u64, student: &Student) -> u64
+    {
+        // FNV-1a over the student ID, then the SplitMix64 finalizer.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in student.get_id().bytes()
+            { hash = (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3); }
+        let mut z = hash ^ seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // pub(crate) fn check_blueprint(qbank: &QBank, blueprint: &Blueprint) -> Result<(), GenerationError>
+    /// Checks whether a question set can be assembled from `qbank` for `blueprint`,
+    /// without drawing any random number. The groups are matched to the wanted
+    /// questions in ascending order, and the largest number of questions that can be
+    /// matched does not depend on the order, so this fails exactly when
+    /// `new_with_blueprint()` fails for every seed.
+    #[inline]
+    pub(crate) fn check_blueprint(qbank: &QBank, blueprint: &Blueprint) -> Result<(), GenerationError>
+    {
+        QuestionPool::new(qbank, blueprint)?.check()
+    }
+
+    // fn match_groups(slots: &[usize], groups: &[u16], grouped_questions: &HashMap<u16, Vec<Vec<&Question>>>) -> Result<HashMap<u16, usize>, GenerationError>
+    /// Matches each of `slots` to a distinct group eligible for its section,
+    /// trying the groups in the order of `groups`.
+    /// Returns the slot of each matched group, or an error if some slot is left unmatched.
+    fn match_groups(slots: &[usize], groups: &[u16], grouped_questions: &HashMap<u16, Vec<Vec<&Question>>>) -> Result<HashMap<u16, usize>, GenerationError>
+    {
+        let mut owner: HashMap<u16, usize> = HashMap::new();    // group -> slot
+        let mut unassigned = 0;
+        for slot in 0..slots.len()
+        {
+            let mut visited = Vec::new();
+            if !Self::assign_group(slot, slots, groups, grouped_questions, &mut owner, &mut visited)
+                { unassigned += 1; }
+        }
+        if unassigned > 0
+            { return Err(GenerationError::TooFewGroupsInTotal { groups: slots.len() - unassigned, requested: slots.len() }); }
+        Ok(owner)
+    }
+
+    // fn assign_group(slot: usize, slots: &[usize], groups: &[u16], grouped_questions: &HashMap<u16, Vec<Vec<&Question>>>, owner: &mut HashMap<u16, usize>, visited: &mut Vec<u16>) -> bool
+    /// Tries to give `slot` a group which has a question for its section,
+    /// moving the slots which already own groups elsewhere if necessary.
+    fn assign_group(slot: usize, slots: &[usize], groups: &[u16], grouped_questions: &HashMap<u16, Vec<Vec<&Question>>>, owner: &mut HashMap<u16, usize>, visited: &mut Vec<u16>) -> bool
+    {
+        for &group in groups
+        {
+            if visited.contains(&group) || grouped_questions[&group][slots[slot]].is_empty()
+                { continue; }
+            visited.push(group);
+            let free = match owner.get(&group)
+            {
+                None => true,
+                Some(&other) => Self::assign_group(other, slots, groups, grouped_questions, owner, visited),
+            };
+            if free
+            {
+                owner.insert(group, slot);
+                return true;
+            }
+        }
+        false
+    }
+
+    // pub fn shuffle(&mut self)
+    /// Shuffles the order of the questions within the set.
+    ///
+    /// Every order of the questions is equally likely, as `test_uniformity()` checks.
+    /// 
+    /// # Examples
+    /// ```
+    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
+    /// use qrate::Question;
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
+    /// qbank.push_question(Question::new(2, 1, 1, "Q2".to_string(), vec![]));
+    /// qbank.push_question(Question::new(3, 1, 1, "Q3".to_string(), vec![]));
+    /// qbank.push_question(Question::new(4, 1, 1, "Q4".to_string(), vec![]));
+    /// qbank.push_question(Question::new(5, 1, 1, "Q5".to_string(), vec![]));
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let mut qset = ShuffledQSet::new(&qbank, &student, 1, 5).unwrap();
+    /// let original_order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// qset.shuffle();
+    /// let shuffled_order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// assert_eq!(original_order.len(), shuffled_order.len());
+    /// ```
+    #[inline]
+    pub fn shuffle(&mut self)
+    {
+        self.shuffle_with_source(&mut PRNG::new());
+    }
+
+    // pub fn shuffle_with_source(&mut self, source: &mut dyn RandomSource)
+    /// Shuffles the order of the questions within the set as `shuffle()` does,
+    /// drawing from the given `source` instead of the default generator.
+    ///
+    /// # Arguments
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ ShuffledQSet, ShuffledQuestion };
+    ///
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_shuffled_questions((1..=4).map(|question| ShuffledQuestion::new(question, 4)).collect());
+    /// let mut other = qset.clone();
+    /// qset.shuffle_with_source(&mut Random::new_with_seeds(7, 7));
+    /// other.shuffle_with_source(&mut Random::new_with_seeds(7, 7));
+    /// assert_eq!(qset.count_same_position(&other), 4);
+    /// let mut order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// order.sort();
+    /// assert_eq!(order, vec![1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn shuffle_with_source(&mut self, source: &mut dyn RandomSource)
+    {
+        shuffle_slice(&mut self.questions, source);
+    }
+
+    // pub fn arrange(&mut self, qbank: &QBank, blueprint: &Blueprint)
+    /// Puts the questions within the set in the order that `blueprint` asks for
+    /// with `Blueprint::get_order()`, shuffling them as far as the order allows.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` that the questions come from.
+    /// * `blueprint` - A reference to the `Blueprint` that the set was assembled from.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Question, QuestionOrder, ShuffledQSet, ShuffledQuestion };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=6
+    ///     { qbank.push_question(Question::new(id, id, if id <= 3 { 2 } else { 1 }, format!("Q{}", id), vec![])); }
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_shuffled_questions((1..=6).rev().map(|question| ShuffledQuestion::new(question, 0)).collect());
+    ///
+    /// let mut blueprint = Blueprint::new_with_range(1, 6, 6);
+    /// blueprint.set_order(QuestionOrder::ById);
+    /// qset.arrange(&qbank, &blueprint);
+    /// let order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// assert_eq!(order, vec![1, 2, 3, 4, 5, 6]);
+    ///
+    /// // Questions 4 to 6 are of Type A, and 1 to 3 of Type B.
+    /// blueprint.set_order(QuestionOrder::ShuffledWithinCategory);
+    /// qset.arrange(&qbank, &blueprint);
+    /// let order: Vec<u16> = qset.get_shuffled_questions().iter().map(|q| q.get_question()).collect();
+    /// assert!(order[..3].iter().all(|&id| id >= 4) && order[3..].iter().all(|&id| id <= 3));
+    /// ```
+    #[inline]
+    pub fn arrange(&mut self, qbank: &QBank, blueprint: &Blueprint)
+    {
+        self.arrange_with_source(qbank, blueprint, &mut PRNG::new());
+    }
+
+    // pub fn arrange_with_source(&mut self, qbank: &QBank, blueprint: &Blueprint, source: &mut dyn RandomSource)
+    /// Puts the questions within the set in order as `arrange()` does,
+    /// drawing from the given `source` instead of the default generator.
+    ///
+    /// With `QuestionOrder::Shuffled`, this is the same as `shuffle_with_source()`.
+    ///
+    /// # Arguments
+    /// * `qbank` - A reference to the `QBank` that the questions come from.
+    /// * `blueprint` - A reference to the `Blueprint` that the set was assembled from.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    pub fn arrange_with_source(&mut self, qbank: &QBank, blueprint: &Blueprint, source: &mut dyn RandomSource)
+    {
+        self.arrange_by_key(blueprint.get_order(), |shuffled_question| blueprint.get_order_key(qbank, shuffled_question), source);
+    }
+
+    // fn arrange_by_key<K: Ord>(&mut self, order: QuestionOrder, order_key: impl Fn(&ShuffledQuestion) -> K, source: &mut dyn RandomSource)
+    /// Shuffles the questions unless `order` keeps the order of the IDs,
+    /// and then sorts them stably by `order_key`, which leaves the questions
+    /// of the same key shuffled among themselves.
+    fn arrange_by_key<K: Ord>(&mut self, order: QuestionOrder, order_key: impl Fn(&ShuffledQuestion) -> K, source: &mut dyn RandomSource)
+    {
+        if order != QuestionOrder::ById
+            { self.shuffle_with_source(source); }
+        if order != QuestionOrder::Shuffled
+            { self.questions.sort_by_cached_key(order_key); }
+    }
+
+    // pub fn test_uniformity(number_of_questions: u16, trials: usize, seed: u64) -> Option<ChiSquare>
+    /// Shuffles a set of questions `trials` times reproducibly from a seed,
+    /// and tests whether every order of the questions came up equally often.
+    ///
+    /// # Arguments
+    /// * `number_of_questions` - The number of questions, from 2 to 8.
+    /// * `trials` - The number of shuffles, which should be at least five times
+    ///   the number of the orders, that is, the factorial of `number_of_questions`.
+    /// * `seed` - The seed of the pseudo-random stream.
+    ///
+    /// # Output
+    /// `Option<ChiSquare>` - The result of the chi-square test over the orders,
+    /// or `None` if `number_of_questions` is out of range or `trials` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::shuffler::ShuffledQSet;
+    /// let chi_square = ShuffledQSet::test_uniformity(4, 2_400, 2026).unwrap();
+    /// assert_eq!(chi_square.get_degrees_of_freedom(), 23);    // 4! orders
+    /// assert!(chi_square.is_uniform(0.001));
+    /// assert!(ShuffledQSet::test_uniformity(1, 1000, 2026).is_none());
+    /// ```
+    pub fn test_uniformity(number_of_questions: u16, trials: usize, seed: u64) -> Option<ChiSquare>
+    {
+        if !(2..=8).contains(&number_of_questions)
+            { return None; }
+        let mut source = PRNG::new_with_seeds(seed, seed.rotate_left(32));
+        let mut counts = vec![0_usize; (1..=number_of_questions as usize).product()];
+        for _ in 0..trials
+        {
+            let mut shuffled_qset = ShuffledQSet::new_empty();
+            shuffled_qset.set_shuffled_questions((1..=number_of_questions).map(|question| ShuffledQuestion::new(question, 0)).collect());
+            shuffled_qset.shuffle_with_source(&mut source);
+            counts[rank_order(shuffled_qset.get_shuffled_questions().iter().map(|q| q.get_question() as usize))] += 1;
+        }
+        ChiSquare::new_with_counts(&counts)
+    }
+
+    // pub fn get_student(&self) -> &Student
+    /// Gets a reference to the `Student` associated with this question set.
+    /// 
+    /// # Output
+    /// `&Student` - A reference to the student.
+    /// 
+    /// # Examples
+    /// ```
+    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
+    /// use qrate::Question;
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let qset = ShuffledQSet::new(&qbank, &student, 1, 1).unwrap();
+    /// assert_eq!(qset.get_student().get_name(), "Test");
+    /// ```
+    #[inline]
+    pub fn get_student(&self) -> &Student
+    {
+        &self.student
+    }
+
+    // pub fn set_student(&mut self, student: &Student)
+    /// Sets the `Student` for this question set.
+    /// 
+    /// # Arguments
+    /// * `student` - A reference to the new `Student`.
+    /// 
+    /// # Examples
+    /// ```
+    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
+    /// use qrate::Question;
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
+    /// let mut student1 = Student::new("Test1".to_string(), "123".to_string());
+    /// let student2 = Student::new("Test2".to_string(), "456".to_string());
+    /// let mut qset = ShuffledQSet::new(&qbank, &student1, 1, 1).unwrap();
+    /// qset.set_student(&student2);
+    /// assert_eq!(qset.get_student().get_name(), "Test2");
+    /// ```
+    #[inline]
+    pub fn set_student(&mut self, student: &Student)
+    {
+        self.student = student.clone();
+    }
+
+    // pub fn get_shuffled_questions(&self) -> &ShuffledQuestions
+    /// Gets a reference to the shuffled questions.
+    /// 
+    /// # Output
+    /// `&ShuffledQuestions` - A reference to the vector of `ShuffledQuestion`s.
+    /// 
+    /// # Examples
+    /// ```
+    /// use qrate::{QBank, Student, shuffler::ShuffledQSet};
+    /// use qrate::Question;
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
+    /// qbank.push_question(Question::new(2, 1, 1, "Q2".to_string(), vec![]));
+    /// qbank.push_question(Question::new(3, 1, 1, "Q3".to_string(), vec![]));
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let qset = ShuffledQSet::new(&qbank, &student, 1, 3).unwrap();
+    /// assert_eq!(qset.get_shuffled_questions().len(), 3);
+    /// ```
+    #[inline]
+    pub fn get_shuffled_questions(&self) -> &ShuffledQuestions
+    {
+        &self.questions
+    }
+
+    // pub fn set_shuffled_questions(&mut self, questions: ShuffledQuestions)
+    /// Replaces the shuffled questions in this set.
+    /// 
+    /// # Arguments
+    /// * `questions` - The new vector of `ShuffledQuestion`s.
+    /// 
+    /// # Examples
+    /// ```
+    /// use qrate::{QBank, Student, shuffler::{ShuffledQSet, ShuffledQuestion}};
+    /// use qrate::Question;
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![])); // Add a question
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    /// let mut qset = ShuffledQSet::new(&qbank, &student, 1, 1).unwrap();
+    /// let new_questions = vec![ShuffledQuestion::new(10, 4), ShuffledQuestion::new(11, 4)];
+    /// qset.set_shuffled_questions(new_questions);
+    /// assert_eq!(qset.get_shuffled_questions().len(), 2);
+    /// assert_eq!(qset.get_shuffled_questions()[0].get_question(), 10);
+    /// ```
+    #[inline]
+    pub fn set_shuffled_questions(&mut self, questions: ShuffledQuestions)
+    {
+        self.questions = questions;
+    }
+
+    // pub fn get_shuffled_question(&self, question_number: u16) -> Option<&ShuffledQuestion>
+    /// Retrieves a reference to a `ShuffledQuestion` by its 1-based question number.
+    ///
+    /// # Arguments
+    /// * `question_number` - The 1-based index of the question to retrieve.
     ///
     /// # Output
     /// `Option<&ShuffledQuestion>` - An `Option` containing a reference to the `ShuffledQuestion` if found,
@@ -485,9 +1671,596 @@ impl ShuffledQSet
     {
         if question_number == 0 { None } else { Some(&self.questions[(question_number - 1) as usize]) }
     }
+
+    // pub fn count_overlap(&self, other: &ShuffledQSet) -> usize
+    /// Counts the questions that this set and `other` have in common,
+    /// whatever their order and the order of their choices are.
+    ///
+    /// # Arguments
+    /// * `other` - A reference to the other `ShuffledQSet`.
+    ///
+    /// # Output
+    /// `usize` - The number of original questions found in both sets.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ ShuffledQSet, ShuffledQuestion };
+    /// let mut qset1 = ShuffledQSet::new_empty();
+    /// qset1.set_shuffled_questions(vec![ShuffledQuestion::new(1, 4), ShuffledQuestion::new(2, 4), ShuffledQuestion::new(3, 4)]);
+    /// let mut qset2 = ShuffledQSet::new_empty();
+    /// qset2.set_shuffled_questions(vec![ShuffledQuestion::new(3, 4), ShuffledQuestion::new(4, 4), ShuffledQuestion::new(1, 4)]);
+    /// assert_eq!(qset1.count_overlap(&qset2), 2);
+    /// assert_eq!(qset1.count_overlap(&qset1), 3);
+    /// ```
+    pub fn count_overlap(&self, other: &ShuffledQSet) -> usize
+    {
+        self.questions.iter()
+            .filter(|mine| other.questions.iter().any(|theirs| theirs.is_same_question(mine)))
+            .count()
+    }
+
+    // pub(crate) fn count_overlap_in_bank(&self, other: &ShuffledQSet, bank: u16) -> usize
+    /// Counts the questions of the question bank of index `bank` that this set and `other` have in common.
+    #[inline]
+    pub(crate) fn count_overlap_in_bank(&self, other: &ShuffledQSet, bank: u16) -> usize
+    {
+        self.questions.iter()
+            .filter(|mine| mine.bank == bank && other.questions.iter().any(|theirs| theirs.is_same_question(mine)))
+            .count()
+    }
+
+    // pub fn count_same_position(&self, other: &ShuffledQSet) -> usize
+    /// Counts the positions at which this set and `other` have the same question.
+    ///
+    /// # Arguments
+    /// * `other` - A reference to the other `ShuffledQSet`.
+    ///
+    /// # Output
+    /// `usize` - The number of positions holding the same original question in both sets.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ ShuffledQSet, ShuffledQuestion };
+    /// let mut qset1 = ShuffledQSet::new_empty();
+    /// qset1.set_shuffled_questions(vec![ShuffledQuestion::new(1, 4), ShuffledQuestion::new(2, 4), ShuffledQuestion::new(3, 4)]);
+    /// let mut qset2 = ShuffledQSet::new_empty();
+    /// qset2.set_shuffled_questions(vec![ShuffledQuestion::new(1, 4), ShuffledQuestion::new(3, 4), ShuffledQuestion::new(2, 4)]);
+    /// assert_eq!(qset1.count_same_position(&qset2), 1);
+    /// assert_eq!(qset1.count_overlap(&qset2), 3);
+    /// ```
+    pub fn count_same_position(&self, other: &ShuffledQSet) -> usize
+    {
+        self.questions.iter().zip(other.questions.iter())
+            .filter(|(mine, theirs)| mine.is_same_question(theirs))
+            .count()
+    }
+
+    // pub fn balance_answers(&mut self, qbanks: &[QBank]) -> bool
+    /// Reshuffles the choices of all the questions so that the correct answers
+    /// are spread evenly over the positions across the whole paper.
+    ///
+    /// # Arguments
+    /// * `qbanks` - The `QBank`s that the questions were drawn from, in the order of
+    ///   the indices that `ShuffledQuestion::get_bank()` gives. A set drawn from one
+    ///   question bank takes `std::slice::from_ref(&qbank)`.
+    ///
+    /// # Output
+    /// `bool` - `true` on success, or `false` if the question bank of a question
+    /// is not in `qbanks` or the question is not found in its question bank.
+    /// On failure, the choices are left partly reshuffled.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ Blueprint, QBank, Question, Student, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=20
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false), ("D".to_string(), false)])); }
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    ///
+    /// let mut qset = ShuffledQSet::new(&qbank, 1, 20, 12, &student).unwrap();
+    /// let qbanks = std::slice::from_ref(&qbank);
+    /// assert!(qset.balance_answers(qbanks));
+    /// assert_eq!(qset.count_answer_positions(qbanks).unwrap(), vec![3, 3, 3, 3]);
+    ///
+    /// // A set drawn from two question banks needs both of them.
+    /// let blueprint = Blueprint::new_with_range(1, 20, 2);
+    /// let mut qset = ShuffledQSet::new_with_banks(&[qbank.clone(), qbank.clone()], &[blueprint.clone(), blueprint], &student, 7).unwrap();
+    /// assert!(!qset.balance_answers(qbanks));
+    /// assert!(qset.balance_answers(&[qbank.clone(), qbank.clone()]));
+    /// ```
+    #[inline]
+    pub fn balance_answers(&mut self, qbanks: &[QBank]) -> bool
+    {
+        self.balance_answers_with_source(qbanks, &mut PRNG::new())
+    }
+
+    // pub fn balance_answers_with_source(&mut self, qbanks: &[QBank], source: &mut dyn RandomSource) -> bool
+    /// Reshuffles the choices of all the questions as `balance_answers()` does,
+    /// drawing from the given `source` instead of the default generator.
+    ///
+    /// # Arguments
+    /// * `qbanks` - The `QBank`s that the questions were drawn from, in the order of
+    ///   the indices that `ShuffledQuestion::get_bank()` gives.
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Output
+    /// `bool` - `true` on success, or `false` if the question bank of a question
+    /// is not in `qbanks` or the question is not found in its question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::{ QBank, Question, Student, shuffler::ShuffledQSet };
+    /// let mut qbank = QBank::new_with_default();
+    /// for id in 1..=8
+    ///     { qbank.push_question(Question::new(id, id, 1, format!("Q{}", id), vec![("A".to_string(), true), ("B".to_string(), false)])); }
+    /// let student = Student::new("Test".to_string(), "123".to_string());
+    ///
+    /// let mut qset = ShuffledQSet::new(&qbank, 1, 8, 6, &student).unwrap();
+    /// assert!(qset.balance_answers_with_source(&[qbank.clone()], &mut Random::new_with_seeds(7, 7)));
+    /// assert_eq!(qset.count_answer_positions(&[qbank]).unwrap(), vec![3, 3]);
+    /// ```
+    pub fn balance_answers_with_source(&mut self, qbanks: &[QBank], source: &mut dyn RandomSource) -> bool
+    {
+        let mut answer_counts = Vec::new();
+        for shuffled_question in self.questions.iter_mut()
+        {
+            let origin = match Self::find_origin(qbanks, shuffled_question)
+            {
+                Some(origin) => origin,
+                None => return false,
+            };
+            shuffled_question.shuffle_balanced_with_source(origin, &mut answer_counts, source);
+        }
+        true
+    }
+
+    // pub fn count_answer_positions(&self, qbanks: &[QBank]) -> Option<Vec<usize>>
+    /// Counts the correct answers at each position of the choices across the whole paper.
+    ///
+    /// # Arguments
+    /// * `qbanks` - The `QBank`s that the questions were drawn from, in the order of
+    ///   the indices that `ShuffledQuestion::get_bank()` gives.
+    ///
+    /// # Output
+    /// `Option<Vec<usize>>` - The number of correct answers at each 0-based position,
+    /// or `None` if the question bank of a question is not in `qbanks` or the question
+    /// is not found in its question bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::{ QBank, Question, ShuffledQSet, ShuffledQuestion };
+    /// let mut qbank = QBank::new_with_default();
+    /// qbank.push_question(Question::new(1, 1, 1, "Q1".to_string(), vec![("A".to_string(), true), ("B".to_string(), false), ("C".to_string(), false)]));
+    /// qbank.push_question(Question::new(2, 2, 2, "Q2".to_string(), vec![("A".to_string(), true), ("B".to_string(), true), ("C".to_string(), false)]));
+    /// let (mut sq1, mut sq2) = (ShuffledQuestion::new(1, 3), ShuffledQuestion::new(2, 3));
+    /// sq1.set_choices(vec![2, 1, 3]);     // The answer is at the second position.
+    /// sq2.set_choices(vec![3, 1, 2]);     // The answers are at the second and the third positions.
+    /// let mut qset = ShuffledQSet::new_empty();
+    /// qset.set_shuffled_questions(vec![sq1.clone(), sq2]);
+    /// assert_eq!(qset.count_answer_positions(&[qbank.clone()]).unwrap(), vec![0, 2, 1]);
+    ///
+    /// // Question 1 of the second question bank has its answer at the third choice.
+    /// let mut other = QBank::new_with_default();
+    /// other.push_question(Question::new(1, 1, 1, "Other Q1".to_string(), vec![("A".to_string(), false), ("B".to_string(), false), ("C".to_string(), true)]));
+    /// let mut sq3 = ShuffledQuestion::new(1, 3);
+    /// sq3.set_bank(1);
+    /// sq3.set_choices(vec![3, 1, 2]);     // The answer is at the first position.
+    /// qset.set_shuffled_questions(vec![sq1, sq3]);
+    /// assert_eq!(qset.count_answer_positions(&[qbank.clone(), other]).unwrap(), vec![1, 1, 0]);
+    /// assert_eq!(qset.count_answer_positions(&[qbank]), None);
+    /// ```
+    pub fn count_answer_positions(&self, qbanks: &[QBank]) -> Option<Vec<usize>>
+    {
+        let mut answer_counts = Vec::new();
+        for shuffled_question in &self.questions
+        {
+            let origin = Self::find_origin(qbanks, shuffled_question)?;
+            for (p, choice) in shuffled_question.get_shuffled_choices(origin)?.iter().enumerate()
+            {
+                if answer_counts.len() <= p
+                    { answer_counts.resize(p + 1, 0); }
+                if choice.1
+                    { answer_counts[p] += 1; }
+            }
+        }
+        Some(answer_counts)
+    }
+
+    // fn find_origin<'b>(qbanks: &'b [QBank], shuffled_question: &ShuffledQuestion) -> Option<&'b Question>
+    /// Finds the original question of `shuffled_question` in the question bank of its index in `qbanks`.
+    #[inline]
+    fn find_origin<'b>(qbanks: &'b [QBank], shuffled_question: &ShuffledQuestion) -> Option<&'b Question>
+    {
+        qbanks.get(shuffled_question.get_bank() as usize)?.get_question_by_id(shuffled_question.get_question())
+    }
+
+    // pub(crate) fn arrange_apart<K: PartialEq>(&mut self, others: &[&ShuffledQSet], order_key: impl Fn(&ShuffledQuestion) -> K) -> bool
+    /// Rearranges the questions so that no question is at the same position as
+    /// in any of `others`. Each question is tried at its current position first,
+    /// and only goes to the positions held by questions of the same `order_key`,
+    /// so that the order that the blueprint asks for is kept.
+    /// Returns `false`, leaving the order as it is, if there is no such arrangement.
+    pub(crate) fn arrange_apart<K: PartialEq>(&mut self, others: &[&ShuffledQSet], order_key: impl Fn(&ShuffledQuestion) -> K) -> bool
+    {
+        let n = self.questions.len();
+        let keys: Vec<K> = self.questions.iter().map(order_key).collect();
+        let allowed: Vec<Vec<bool>> = self.questions.iter().enumerate()
+            .map(|(q, mine)| (0..n).map(|p| keys[p] == keys[q] && others.iter().all(|other| other.questions.get(p).is_none_or(|theirs| !theirs.is_same_question(mine)))).collect())
+            .collect();
+        let mut holder: Vec<Option<usize>> = vec![None; n];     // position -> question
+        for q in 0..n
+        {
+            let mut visited = vec![false; n];
+            if !Self::place_question(q, &allowed, &mut holder, &mut visited)
+                { return false; }
+        }
+        let questions = holder.iter().map(|q| self.questions[q.unwrap()].clone()).collect();
+        self.questions = questions;
+        true
+    }
+
+    // fn place_question(q: usize, allowed: &[Vec<bool>], holder: &mut [Option<usize>], visited: &mut [bool]) -> bool
+    /// Tries to give question `q` an allowed position, starting from its current one,
+    /// moving the questions which already hold positions elsewhere if necessary.
+    fn place_question(q: usize, allowed: &[Vec<bool>], holder: &mut [Option<usize>], visited: &mut [bool]) -> bool
+    {
+        let n = holder.len();
+        for k in 0..n
+        {
+            let p = (q + k) % n;
+            if visited[p] || !allowed[q][p]
+                { continue; }
+            visited[p] = true;
+            let free = match holder[p]
+            {
+                None => true,
+                Some(other) => Self::place_question(other, allowed, holder, visited),
+            };
+            if free
+            {
+                holder[p] = Some(q);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+
+/// The options of `ShuffledQSet::new_with_pools_source()`.
+pub(crate) struct SetOptions<'a>
+{
+    pub(crate) usage: &'a HashMap<(u16, u16), usize>,   // (bank, question) -> the number of students who have got it so far, of which the fewer are preferred
+    pub(crate) arranged: bool,                          // Whether the questions are put in the order of the blueprint, or left in the order of the selection
+}
+
+
+/// The questions of a question bank grouped for a blueprint, from which
+/// the question sets of many students are selected without grouping them again.
+pub(crate) struct QuestionPool<'a>
+{
+    qbank: &'a QBank,
+    blueprint: &'a Blueprint,
+    bank: u16,                                                  // The 0-based index of the question bank among those of an exam
+    anchors: Vec<&'a Question>,
+    required: Vec<&'a Question>,                                // The questions that the anchor questions require
+    grouped_questions: HashMap<u16, Vec<Vec<&'a Question>>>,    // group -> the eligible questions of each section
+    groups: Vec<u16>,                                           // The groups in ascending order
+    slots: Vec<usize>,                                          // The section of each question to sample
+}
+
+impl<'a> QuestionPool<'a>
+{
+    /// The number of times the questions of a set are selected before giving up the constraints of the question bank.
+    const CONSTRAINT_ATTEMPTS: u64 = 256;
+
+    // pub(crate) fn new(qbank: &'a QBank, blueprint: &'a Blueprint) -> Result<Self, GenerationError>
+    /// Groups the questions of `qbank` for the sections of `blueprint`, leaving out
+    /// the groups of the anchor questions and of the questions they require.
+    /// Returns an error if no set can be selected whatever the random numbers are,
+    /// as far as each section can tell on its own.
+    pub(crate) fn new(qbank: &'a QBank, blueprint: &'a Blueprint) -> Result<Self, GenerationError>
+    {
+        blueprint.validate()?;
+        if let Some(id) = qbank.find_duplicate_id()
+            { return Err(GenerationError::DuplicateId { id }); }
+
+        // Anchor questions are not sampled, and nor are the other questions of their groups.
+        let mut anchors = Vec::new();
+        for &id in blueprint.get_anchors()
+            { anchors.push(qbank.get_question_by_id(id).ok_or(GenerationError::MissingAnchor { id })?); }
+
+        // The questions which the anchor questions require are in every set too. Each of them
+        // takes a slot of a section it belongs to, and the other questions of its group are not sampled.
+        let sections = blueprint.get_sections();
+        let mut slots: Vec<usize> = sections.iter().enumerate().flat_map(|(i, section)| std::iter::repeat_n(i, section.get_count())).collect();
+        let mut fixed = anchors.clone();
+        let mut i = 0;
+        while i < fixed.len()
+        {
+            let id = fixed[i].get_id();
+            for &constraint in qbank.get_constraints()
+            {
+                let Constraint::Requires { question, required } = constraint
+                    else { continue; };
+                if question != id || fixed.iter().any(|q| q.get_id() == required)
+                    { continue; }
+                let question = qbank.get_question_by_id(required).ok_or(GenerationError::AnchorConstraint { constraint })?;
+                let slot = slots.iter().position(|&section| sections[section].contains(question));
+                match slot
+                {
+                    Some(slot) if !fixed.iter().any(|q| q.get_group() == question.get_group()) => { slots.remove(slot); },
+                    _ => { return Err(GenerationError::AnchorConstraint { constraint }); },
+                }
+                fixed.push(question);
+            }
+            i += 1;
+        }
+        let fixed_ids: HashSet<u16> = fixed.iter().map(|q| q.get_id()).collect();
+        if let Some(&constraint) = qbank.find_broken_constraint(&fixed_ids)
+            { return Err(GenerationError::AnchorConstraint { constraint }); }
+        let fixed_groups: HashSet<u16> = fixed.iter().map(|q| q.get_group()).collect();
+
+        // A question is left out if the questions it requires cannot all be selected with it
+        // whatever else is selected, e.g. when one of them is in no section or in a group left out.
+        let can_be_selected = |question: &Question|
+        {
+            let mut ids = fixed_ids.clone();
+            let mut groups = HashSet::from([question.get_group()]);
+            ids.insert(question.get_id());
+            for id in qbank.get_required(question.get_id())
+            {
+                if fixed_ids.contains(&id)
+                    { continue; }
+                match qbank.get_question_by_id(id)
+                {
+                    Some(other) if !fixed_groups.contains(&other.get_group()) && groups.insert(other.get_group())
+                            && sections.iter().any(|section| section.contains(other)) => { ids.insert(id); },
+                    _ => { return false; },
+                }
+            }
+            qbank.find_broken_constraint(&ids).is_none()
+        };
+
+        // For each group, the eligible questions of each section
+        let mut grouped_questions: HashMap<u16, Vec<Vec<&Question>>> = HashMap::new();
+        for question in qbank.get_questions()
+        {
+            if fixed_groups.contains(&question.get_group()) || !can_be_selected(question)
+                { continue; }
+            for (i, section) in sections.iter().enumerate()
+            {
+                if section.contains(question)
+                    { grouped_questions.entry(question.get_group()).or_insert_with(|| vec![Vec::new(); sections.len()])[i].push(question); }
+            }
+        }
+        for (i, section) in sections.iter().enumerate()
+        {
+            let groups = grouped_questions.values().filter(|questions| !questions[i].is_empty()).count();
+            let requested = slots.iter().filter(|&&slot| slot == i).count();
+            if groups >= requested
+                { continue; }
+            let (start, end) = (section.get_start(), section.get_end());
+            let missing = section.get_categories().iter().find(|&&category| !grouped_questions.values().any(|questions| questions[i].iter().any(|q| q.get_category() == category)));
+            return Err(match missing
+            {
+                Some(&category) => GenerationError::MissingCategory { section: i, start, end, category },
+                None => GenerationError::TooFewGroups { section: i, start, end, groups, requested },
+            });
+        }
+
+        // Sorted so that a seeded stream always gives the same order of groups.
+        let mut groups: Vec<u16> = grouped_questions.keys().cloned().collect();
+        groups.sort_unstable();
+        let required = fixed.split_off(anchors.len());
+        Ok(Self { qbank, blueprint, bank: 0, anchors, required, grouped_questions, groups, slots })
+    }
+
+    // pub(crate) fn new_with_banks(qbanks: &'a [QBank], blueprints: &'a [Blueprint]) -> Result<Vec<Self>, GenerationError>
+    /// Groups the questions of each question bank of `qbanks` for its blueprint
+    /// in `blueprints`, returning the pools in the same order.
+    /// Returns an error if no question bank is given, if the numbers of question
+    /// banks and blueprints differ, if the blueprints ask for different orders,
+    /// since the questions of all the banks are put in one order, or, wrapped in `GenerationError::InBank`,
+    /// if the pool of a question bank cannot be made.
+    pub(crate) fn new_with_banks(qbanks: &'a [QBank], blueprints: &'a [Blueprint]) -> Result<Vec<Self>, GenerationError>
+    {
+        if qbanks.is_empty()
+            { return Err(GenerationError::MissingBank { bank: 0 }); }
+        if qbanks.len() != blueprints.len()
+            { return Err(GenerationError::BlueprintCount { banks: qbanks.len(), blueprints: blueprints.len() }); }
+        if let Some(bank) = blueprints.iter().position(|blueprint| blueprint.get_order() != blueprints[0].get_order())
+            { return Err(GenerationError::OrderMismatch { bank }); }
+        qbanks.iter().zip(blueprints).enumerate()
+            .map(|(bank, (qbank, blueprint))| match Self::new(qbank, blueprint)
+            {
+                Ok(pool) => Ok(Self { bank: bank as u16, ..pool }),
+                Err(error) => Err(GenerationError::InBank { bank, error: Box::new(error) }),
+            })
+            .collect()
+    }
+
+    // pub(crate) fn get_qbank(&self) -> &'a QBank
+    /// Gets the question bank that the questions are drawn from.
+    #[inline]
+    pub(crate) fn get_qbank(&self) -> &'a QBank
+    {
+        self.qbank
+    }
+
+    // pub(crate) fn get_blueprint(&self) -> &'a Blueprint
+    /// Gets the blueprint that the questions are grouped for.
+    #[inline]
+    pub(crate) fn get_blueprint(&self) -> &'a Blueprint
+    {
+        self.blueprint
+    }
+
+    // pub(crate) fn check(&self) -> Result<(), GenerationError>
+    /// Checks whether a set can be selected at all, without drawing any random number.
+    /// The groups are matched in ascending order, and the largest number of questions
+    /// that can be matched does not depend on the order, so this fails exactly when
+    /// `select()` fails for every random stream, as long as no question requires another.
+    #[inline]
+    pub(crate) fn check(&self) -> Result<(), GenerationError>
+    {
+        ShuffledQSet::match_groups(&self.slots, &self.groups, &self.grouped_questions).map(|_| ())
+    }
+
+    // fn assign(&self, slots: &[usize], groups: &[u16]) -> Result<Vec<(usize, u16)>, GenerationError>
+    /// Matches each of `slots` to a distinct group of `groups` eligible for its section,
+    /// and returns the section and the group of each slot in the order of `slots`.
+    fn assign(&self, slots: &[usize], groups: &[u16]) -> Result<Vec<(usize, u16)>, GenerationError>
+    {
+        let owner = ShuffledQSet::match_groups(slots, groups, &self.grouped_questions)?;
+        let mut assigned: Vec<(usize, u16)> = owner.into_iter().map(|(group, slot)| (slot, group)).collect();
+        assigned.sort_unstable();
+        Ok(assigned.into_iter().map(|(slot, group)| (slots[slot], group)).collect())
+    }
+
+    // pub(crate) fn select(&self, student: &Student, usage: &HashMap<(u16, u16), usize>, source: &mut dyn RandomSource) -> Result<ShuffledQSet, GenerationError>
+    /// Selects the questions of a student's set, drawing from the given `source`.
+    ///
+    /// If the question bank has constraints, a question is drawn only if neither it
+    /// nor any question it requires is excluded by the questions selected before, and
+    /// the questions it requires are selected with it. If the groups left cannot fill
+    /// the slots left then, the questions are selected again, at most `CONSTRAINT_ATTEMPTS`
+    /// times. The sets which keep the constraints are therefore not all equally likely.
+    pub(crate) fn select(&self, student: &Student, usage: &HashMap<(u16, u16), usize>, source: &mut dyn RandomSource) -> Result<ShuffledQSet, GenerationError>
+    {
+        // Without constraints, the first selection is always kept.
+        let attempts = if self.qbank.get_constraints().is_empty() { 1 } else { Self::CONSTRAINT_ATTEMPTS };
+        for _ in 0..attempts
+        {
+            if let Some(selected_shuffled_questions) = self.select_questions(usage, source)?
+            {
+                let mut shuffled_qset = ShuffledQSet { student: student.clone(), questions: selected_shuffled_questions };
+                // Every question has been taken from `qbank`, so the balancing cannot fail.
+                if self.blueprint.is_answer_balanced()
+                    { shuffled_qset.balance_answers_with_source(std::slice::from_ref(self.qbank), source); }
+                return Ok(shuffled_qset);
+            }
+        }
+        Err(GenerationError::ConstraintsUnmet { student: student.get_id().clone(), attempts })
+    }
+
+    // fn select_questions(&self, usage: &HashMap<(u16, u16), usize>, source: &mut dyn RandomSource) -> Result<Option<ShuffledQuestions>, GenerationError>
+    /// Selects the questions of a set once, drawing from the given `source`.
+    /// Returns `None` if the questions required by a selected question leave
+    /// some slot without a group.
+    ///
+    /// A group may have questions for several sections, so picking groups section
+    /// by section can fail even though the blueprint can be met. Instead, each wanted
+    /// question is matched to a group eligible for its section by augmenting paths,
+    /// after the groups have been put in a random order. Groups whose questions are
+    /// less used according to `usage` come first, and so do such questions within a group.
+    /// The questions required by a selected question take slots of their sections,
+    /// and the groups left are matched again to the slots left.
+    fn select_questions(&self, usage: &HashMap<(u16, u16), usize>, source: &mut dyn RandomSource) -> Result<Option<ShuffledQuestions>, GenerationError>
+    {
+        let grouped_questions = &self.grouped_questions;
+        let mut groups = self.groups.clone();
+        shuffle_slice(&mut groups, source);
+        let used = |question: &&Question| usage.get(&(self.bank, question.get_id())).copied().unwrap_or(0);
+        if !usage.is_empty()
+            { groups.sort_by_key(|group| grouped_questions[group].iter().flatten().map(used).min()); }
+
+        let sections = self.blueprint.get_sections();
+        let constraints = self.qbank.get_constraints();
+        let mut assigned = self.assign(&self.slots, &groups)?;
+        let mut selected: HashSet<u16> = self.anchors.iter().chain(&self.required).map(|q| q.get_id()).collect();
+        let mut taken_groups = HashSet::new();
+        // A question is not selected if it or a question it requires is excluded by a question already selected.
+        let can_be_added = |question: &Question, selected: &HashSet<u16>, taken_groups: &HashSet<u16>|
+        {
+            let required: Vec<&Question> = self.qbank.get_required(question.get_id()).into_iter()
+                .filter(|id| !selected.contains(id)).filter_map(|id| self.qbank.get_question_by_id(id)).collect();
+            !required.iter().any(|q| taken_groups.contains(&q.get_group()))
+                && !std::iter::once(question).chain(required).any(|q| constraints.iter().any(|constraint| constraint.excludes(q.get_id(), selected)))
+        };
+        let mut selected_shuffled_questions = ShuffledQuestions::new();
+        let mut next = 0;
+        while next < assigned.len()
+        {
+            let (section, group) = assigned[next];
+            next += 1;
+            let allowed: Vec<&Question> = grouped_questions[&group][section].iter().filter(|q| can_be_added(q, &selected, &taken_groups)).copied().collect();
+            let least_used = allowed.iter().map(used).min().unwrap_or(0);
+            let questions_in_group: Vec<&Question> = allowed.into_iter().filter(|q| used(q) == least_used).collect();
+            if questions_in_group.is_empty()
+                { return Ok(None); }
+            let question_index = source.random_under(questions_in_group.len());
+            let original_question = questions_in_group[question_index];
+            selected.insert(original_question.get_id());
+            taken_groups.insert(group);
+            selected_shuffled_questions.push(Self::draw_question(original_question, source)?);
+
+            let required: Vec<&Question> = self.qbank.get_required(original_question.get_id()).into_iter()
+                .filter(|id| !selected.contains(id)).filter_map(|id| self.qbank.get_question_by_id(id)).collect();
+            if required.is_empty()
+                { continue; }
+            let mut slots: Vec<usize> = assigned[next..].iter().map(|&(section, _)| section).collect();
+            for question in required
+            {
+                let Some(slot) = slots.iter().position(|&section| sections[section].contains(question))
+                    else { return Ok(None); };
+                slots.remove(slot);
+                selected.insert(question.get_id());
+                taken_groups.insert(question.get_group());
+                selected_shuffled_questions.push(Self::draw_question(question, source)?);
+            }
+            let groups_left: Vec<u16> = groups.iter().filter(|group| !taken_groups.contains(group)).copied().collect();
+            match self.assign(&slots, &groups_left)
+            {
+                Ok(reassigned) => { assigned = reassigned; },
+                Err(_) => { return Ok(None); },
+            }
+            next = 0;
+        }
+        for &question in self.required.iter().chain(&self.anchors)
+            { selected_shuffled_questions.push(Self::draw_question(question, source)?); }
+        Ok(Some(selected_shuffled_questions))
+    }
+
+    // fn draw_question(question: &Question, source: &mut dyn RandomSource) -> Result<ShuffledQuestion, GenerationError>
+    /// Makes the `ShuffledQuestion` of `question` for a student, sampling its
+    /// distractors, shuffling its choices and drawing the values of its parameters.
+    fn draw_question(question: &Question, source: &mut dyn RandomSource) -> Result<ShuffledQuestion, GenerationError>
+    {
+        let mut shuffled_question = ShuffledQuestion::new_with_question(question);
+        shuffled_question.sample_choices_with_source(question, source);
+        shuffled_question.shuffle_with_source(source);
+        if !shuffled_question.draw_values_with_source(question, source)
+            { return Err(GenerationError::ValuesUnmet { id: question.get_id(), attempts: ShuffledQuestion::VALUE_ATTEMPTS }); }
+        Ok(shuffled_question)
+    }
 }
 
 
+// fn shuffle_slice<T>(items: &mut [T], source: &mut dyn RandomSource)
+/// Puts `items` in a uniformly random order, drawing from the given `source`.
+fn shuffle_slice<T>(items: &mut [T], source: &mut dyn RandomSource)
+{
+    for i in (1..items.len()).rev()
+        { items.swap(i, source.random_under(i + 1)); }
+}
+
+// fn rank_order(order: impl Iterator<Item = usize>) -> usize
+/// Numbers an order of 1-based items from 0 to the factorial of their count
+/// minus one, so that every order has its own number.
+fn rank_order(order: impl Iterator<Item = usize>) -> usize
+{
+    let order: Vec<usize> = order.collect();
+    let mut rank = 0;
+    for (i, &item) in order.iter().enumerate()
+    {
+        let smaller_later = order[i + 1..].iter().filter(|&&later| later < item).count();
+        rank = rank * (order.len() - i) + smaller_later;
+    }
+    rank
+}
+
 /*
 pub struct Exam
 {
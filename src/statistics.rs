@@ -0,0 +1,210 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// Represents Pearson's chi-square goodness-of-fit test against the uniform distribution.
+///
+/// It tells how likely observed counts, such as how often each order of the choices
+/// came up, are if every category is equally likely. A small p-value means that
+/// some categories come up more often than others.
+#[derive(Debug, Clone, Copy)]
+pub struct ChiSquare
+{
+    statistic: f64,
+    degrees_of_freedom: usize,
+}
+
+impl ChiSquare
+{
+    // pub fn new_with_counts(observed: &[usize]) -> Option<Self>
+    /// Carries out the test on the observed counts of equally likely categories.
+    ///
+    /// # Arguments
+    /// * `observed` - How many times each category was observed.
+    ///
+    /// # Output
+    /// `Option<Self>` - The result of the test, or `None` if there are fewer
+    /// than two categories or nothing was observed.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::ChiSquare;
+    /// let chi_square = ChiSquare::new_with_counts(&[10, 10, 10, 10]).unwrap();
+    /// assert_eq!(chi_square.get_statistic(), 0.0);
+    /// assert_eq!(chi_square.get_degrees_of_freedom(), 3);
+    ///
+    /// let chi_square = ChiSquare::new_with_counts(&[30, 10]).unwrap();
+    /// assert_eq!(chi_square.get_statistic(), 10.0);
+    /// assert!(ChiSquare::new_with_counts(&[5]).is_none());
+    /// assert!(ChiSquare::new_with_counts(&[0, 0]).is_none());
+    /// ```
+    pub fn new_with_counts(observed: &[usize]) -> Option<Self>
+    {
+        let total: usize = observed.iter().sum();
+        if observed.len() < 2 || total == 0
+            { return None; }
+        let expected = total as f64 / observed.len() as f64;
+        let statistic = observed.iter().map(|&o| (o as f64 - expected).powi(2) / expected).sum();
+        Some(Self { statistic, degrees_of_freedom: observed.len() - 1 })
+    }
+
+    // pub fn get_statistic(&self) -> f64
+    /// Gets the chi-square statistic.
+    ///
+    /// # Output
+    /// `f64` - The sum of the squared differences between the observed and
+    /// the expected counts, each divided by the expected count.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::ChiSquare;
+    /// assert_eq!(ChiSquare::new_with_counts(&[12, 8]).unwrap().get_statistic(), 0.8);
+    /// ```
+    #[inline]
+    pub fn get_statistic(&self) -> f64
+    {
+        self.statistic
+    }
+
+    // pub fn get_degrees_of_freedom(&self) -> usize
+    /// Gets the degrees of freedom, which is one less than the number of categories.
+    ///
+    /// # Output
+    /// `usize` - The degrees of freedom.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::ChiSquare;
+    /// assert_eq!(ChiSquare::new_with_counts(&[1, 2, 3, 4, 5, 6]).unwrap().get_degrees_of_freedom(), 5);
+    /// ```
+    #[inline]
+    pub fn get_degrees_of_freedom(&self) -> usize
+    {
+        self.degrees_of_freedom
+    }
+
+    // pub fn get_p_value(&self) -> f64
+    /// Gets the probability that uniformly distributed counts would give
+    /// a statistic at least as large as this one.
+    ///
+    /// # Output
+    /// `f64` - The p-value between 0 and 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::ChiSquare;
+    /// // With two degrees of freedom, the p-value is exp(-statistic / 2).
+    /// let chi_square = ChiSquare::new_with_counts(&[20, 10, 0]).unwrap();
+    /// assert!((chi_square.get_p_value() - (-chi_square.get_statistic() / 2.0).exp()).abs() < 1e-9);
+    ///
+    /// // A coin landing heads 5980 times out of 11000 is far from fair.
+    /// let chi_square = ChiSquare::new_with_counts(&[5980, 5020]).unwrap();
+    /// assert!((chi_square.get_statistic() - 83.78).abs() < 0.01);
+    /// assert!(chi_square.get_p_value() < 1e-10);
+    /// assert_eq!(ChiSquare::new_with_counts(&[7, 7]).unwrap().get_p_value(), 1.0);
+    /// ```
+    pub fn get_p_value(&self) -> f64
+    {
+        upper_regularized_gamma(self.degrees_of_freedom as f64 / 2.0, self.statistic / 2.0)
+    }
+
+    // pub fn is_uniform(&self, significance: f64) -> bool
+    /// Checks whether the counts are consistent with a uniform distribution.
+    ///
+    /// # Arguments
+    /// * `significance` - The significance level, such as 0.01 for 1%.
+    ///
+    /// # Output
+    /// `bool` - `true` if the p-value is not below `significance`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::ChiSquare;
+    /// assert!(ChiSquare::new_with_counts(&[251, 249, 260, 240]).unwrap().is_uniform(0.01));
+    /// assert!(!ChiSquare::new_with_counts(&[400, 200, 200, 200]).unwrap().is_uniform(0.01));
+    /// ```
+    #[inline]
+    pub fn is_uniform(&self, significance: f64) -> bool
+    {
+        self.get_p_value() >= significance
+    }
+}
+
+
+// fn ln_gamma(x: f64) -> f64
+/// Computes the natural logarithm of the gamma function for `x` of at least 0.5
+/// by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64
+{
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9, 676.520_368_121_885_1, -1_259.139_216_722_402_8,
+        771.323_428_777_653_1, -176.615_029_162_140_6, 12.507_343_278_686_905,
+        -0.138_571_095_265_720_12, 9.984_369_578_019_572e-6, 1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1)
+        { sum += c / (x + i as f64); }
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+// fn upper_regularized_gamma(a: f64, x: f64) -> f64
+/// Computes the upper regularized incomplete gamma function Q(a, x),
+/// by its series below a + 1 and by its continued fraction above.
+fn upper_regularized_gamma(a: f64, x: f64) -> f64
+{
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+    const MAX_ITERATIONS: usize = 1000;
+
+    if x <= 0.0
+        { return 1.0; }
+    let front = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0
+    {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..MAX_ITERATIONS
+        {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON
+                { break; }
+        }
+        (1.0 - sum * front).clamp(0.0, 1.0)
+    }
+    else
+    {
+        // Lentz's method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..MAX_ITERATIONS
+        {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY
+                { d = TINY; }
+            c = b + an / c;
+            if c.abs() < TINY
+                { c = TINY; }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON
+                { break; }
+        }
+        (front * h).clamp(0.0, 1.0)
+    }
+}
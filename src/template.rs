@@ -0,0 +1,402 @@
+// Copyright 2026 PARK Youngho.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your option.
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+///////////////////////////////////////////////////////////////////////////////
+
+
+use crate::RandomSource;
+
+/// A type alias for a vector of `Parameter`s.
+pub type Parameters = Vec<Parameter>;
+
+/// A type alias for the values given to the parameters of a question, as pairs of a name and a value.
+pub type Values = Vec<(String, i64)>;
+
+/// Represents a parameter of a templated question, which takes a random
+/// integer value from `min` to `max`, both inclusive, for each student.
+///
+/// The text and the choices of the question refer to the parameter by its name
+/// in placeholders such as `{a}` or `{a * b + 1}`, as `fill_in()` tells.
+///
+/// # Examples
+/// ```
+/// use qrate::Parameter;
+/// let parameter = Parameter::new("a".to_string(), 1, 9);
+/// assert_eq!(parameter.get_name(), "a");
+/// assert_eq!((parameter.get_min(), parameter.get_max()), (1, 9));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter
+{
+    name: String,
+    min: i64,
+    max: i64,
+}
+
+impl Parameter
+{
+    // pub fn new(name: String, min: i64, max: i64) -> Self
+    /// Creates a new `Parameter` which takes a value from `min` to `max`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the parameter, made of ASCII letters, digits and underscores,
+    ///   and not starting with a digit.
+    /// * `min` - The smallest value of the parameter (inclusive).
+    /// * `max` - The largest value of the parameter (inclusive).
+    ///
+    /// # Output
+    /// `Self` - A new `Parameter` instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Parameter;
+    /// let parameter = Parameter::new("n".to_string(), -5, 5);
+    /// assert_eq!(parameter.get_min(), -5);
+    /// ```
+    #[inline]
+    pub fn new(name: String, min: i64, max: i64) -> Self
+    {
+        Self { name, min, max }
+    }
+
+    // pub fn get_name(&self) -> &String
+    /// Gets the name of the parameter.
+    ///
+    /// # Output
+    /// `&String` - A reference to the name of the parameter.
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Parameter;
+    /// assert_eq!(Parameter::new("b".to_string(), 10, 99).get_name(), "b");
+    /// ```
+    #[inline]
+    pub fn get_name(&self) -> &String
+    {
+        &self.name
+    }
+
+    // pub fn get_min(&self) -> i64
+    /// Gets the smallest value of the parameter.
+    ///
+    /// # Output
+    /// `i64` - The smallest value (inclusive).
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Parameter;
+    /// assert_eq!(Parameter::new("b".to_string(), 10, 99).get_min(), 10);
+    /// ```
+    #[inline]
+    pub fn get_min(&self) -> i64
+    {
+        self.min
+    }
+
+    // pub fn get_max(&self) -> i64
+    /// Gets the largest value of the parameter.
+    ///
+    /// # Output
+    /// `i64` - The largest value (inclusive).
+    ///
+    /// # Examples
+    /// ```
+    /// use qrate::Parameter;
+    /// assert_eq!(Parameter::new("b".to_string(), 10, 99).get_max(), 99);
+    /// ```
+    #[inline]
+    pub fn get_max(&self) -> i64
+    {
+        self.max
+    }
+
+    // pub fn draw_with_source(&self, source: &mut dyn RandomSource) -> i64
+    /// Draws a value of the parameter uniformly from its range.
+    /// If `min` is greater than `max`, `min` is the only value.
+    ///
+    /// # Arguments
+    /// * `source` - The `RandomSource` to draw the random numbers from.
+    ///
+    /// # Output
+    /// `i64` - A value from `min` to `max`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cryptocol::random::Random;
+    /// use qrate::Parameter;
+    /// let parameter = Parameter::new("a".to_string(), 3, 7);
+    /// let mut source = Random::new_with_seeds(2026, 2026);
+    /// for _ in 0..20
+    ///     { assert!((3..=7).contains(&parameter.draw_with_source(&mut source))); }
+    /// ```
+    pub fn draw_with_source(&self, source: &mut dyn RandomSource) -> i64
+    {
+        if self.min >= self.max
+            { return self.min; }
+        let span = self.max.abs_diff(self.min);
+        match span.checked_add(1)
+        {
+            Some(count) => self.min.wrapping_add(source.random_under(count as usize) as i64),
+            None => source.random_u64() as i64,     // The whole range of i64
+        }
+    }
+}
+
+
+// pub fn evaluate(expression: &str, values: &[(String, i64)]) -> Option<i64>
+/// Evaluates an integer expression in which the names of parameters stand for their values.
+///
+/// The expression may have integers, names, parentheses, unary `-`, and the binary
+/// operators `+`, `-`, `*`, `/` and `%` with their usual precedence. The division
+/// and the remainder truncate toward zero as in C.
+///
+/// # Arguments
+/// * `expression` - The expression, such as `"a * b + 1"`.
+/// * `values` - The values of the parameters, as pairs of a name and a value.
+///
+/// # Output
+/// `Option<i64>` - The value of the expression, or `None` if it cannot be parsed,
+/// refers to an unknown name, divides by zero, overflows, or nests parentheses
+/// and unary `-` more than 64 levels deep.
+///
+/// # Examples
+/// ```
+/// use qrate::template::evaluate;
+/// let values = vec![("a".to_string(), 7), ("b".to_string(), 2)];
+/// assert_eq!(evaluate("a + b * 3", &values), Some(13));
+/// assert_eq!(evaluate("(a + b) * 3", &values), Some(27));
+/// assert_eq!(evaluate("-a / b", &values), Some(-3));
+/// assert_eq!(evaluate("a % b", &values), Some(1));
+/// assert_eq!(evaluate("a / (b - 2)", &values), None);
+/// assert_eq!(evaluate("a + c", &values), None);
+/// assert_eq!(evaluate("-(-(a))", &values), Some(7));
+/// assert_eq!(evaluate(&format!("{}a{}", "(".repeat(100_000), ")".repeat(100_000)), &values), None);
+/// ```
+pub fn evaluate(expression: &str, values: &[(String, i64)]) -> Option<i64>
+{
+    let mut parser = Parser { chars: expression.chars().collect(), position: 0, depth: 0, values };
+    let value = parser.parse_sum()?;
+    parser.skip_spaces();
+    (parser.position == parser.chars.len()).then_some(value)
+}
+
+// pub fn fill_in(text: &str, values: &[(String, i64)]) -> Option<String>
+/// Replaces every placeholder of `text`, which is an expression in braces
+/// such as `{a}` or `{a * b}`, with the value of the expression.
+///
+/// A brace that belongs to the text itself, such as one of a C block, is written twice:
+/// `{{` stands for `{` and `}}` for `}`.
+///
+/// # Arguments
+/// * `text` - The text with the placeholders.
+/// * `values` - The values of the parameters, as pairs of a name and a value.
+///
+/// # Output
+/// `Option<String>` - The text with the values filled in, or `None` if a brace is
+/// not matched or a placeholder cannot be evaluated as `evaluate()` tells.
+///
+/// # Examples
+/// ```
+/// use qrate::template::fill_in;
+/// let values = vec![("a".to_string(), 3), ("b".to_string(), 4)];
+/// assert_eq!(fill_in("What is {a} + {b}?", &values).unwrap(), "What is 3 + 4?");
+/// assert_eq!(fill_in("{a * b}", &values).unwrap(), "12");
+/// assert_eq!(fill_in("while (i < {a}) {{ i++; }}", &values).unwrap(), "while (i < 3) { i++; }");
+/// assert!(fill_in("What is {a + }?", &values).is_none());
+/// assert!(fill_in("What is {a?", &values).is_none());
+/// ```
+pub fn fill_in(text: &str, values: &[(String, i64)]) -> Option<String>
+{
+    let mut filled = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next()
+    {
+        match c
+        {
+            '{' if chars.peek() == Some(&'{') =>
+            {
+                chars.next();
+                filled.push('{');
+            },
+            '}' if chars.peek() == Some(&'}') =>
+            {
+                chars.next();
+                filled.push('}');
+            },
+            '{' =>
+            {
+                let mut expression = String::new();
+                loop
+                {
+                    match chars.next()?
+                    {
+                        '}' => break,
+                        c => expression.push(c),
+                    }
+                }
+                filled.push_str(&evaluate(&expression, values)?.to_string());
+            },
+            '}' => return None,
+            c => filled.push(c),
+        }
+    }
+    Some(filled)
+}
+
+// pub(crate) fn parameters_to_text(parameters: &[Parameter]) -> String
+/// Converts parameters into the text `"a=1..9,b=10..99"` in which they are stored.
+pub(crate) fn parameters_to_text(parameters: &[Parameter]) -> String
+{
+    parameters.iter().map(|p| format!("{}={}..{}", p.name, p.min, p.max)).collect::<Vec<String>>().join(",")
+}
+
+// pub(crate) fn text_to_parameters(text: &str) -> Option<Parameters>
+/// Converts the text `"a=1..9,b=10..99"` back into parameters.
+/// Returns `None` if any element is not a name with a range.
+pub(crate) fn text_to_parameters(text: &str) -> Option<Parameters>
+{
+    if text.trim().is_empty()
+        { return Some(Parameters::new()); }
+    text.split(',').map(|element| {
+        let (name, range) = element.split_once('=')?;
+        let (min, max) = range.split_once("..")?;
+        let name = name.trim();
+        is_name(name).then(|| Some(Parameter::new(name.to_string(), min.trim().parse().ok()?, max.trim().parse().ok()?)))?
+    }).collect()
+}
+
+// pub(crate) fn values_to_text(values: &[(String, i64)]) -> String
+/// Converts the values of parameters into the text `"a=3,b=42"` in which they are stored.
+pub(crate) fn values_to_text(values: &[(String, i64)]) -> String
+{
+    values.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<String>>().join(",")
+}
+
+// pub(crate) fn text_to_values(text: &str) -> Option<Values>
+/// Converts the text `"a=3,b=42"` back into the values of parameters.
+/// Returns `None` if any element is not a name with a number.
+pub(crate) fn text_to_values(text: &str) -> Option<Values>
+{
+    if text.trim().is_empty()
+        { return Some(Values::new()); }
+    text.split(',').map(|element| {
+        let (name, value) = element.split_once('=')?;
+        let name = name.trim();
+        is_name(name).then(|| Some((name.to_string(), value.trim().parse().ok()?)))?
+    }).collect()
+}
+
+// fn is_name(name: &str) -> bool
+/// Checks whether `name` can be the name of a parameter.
+fn is_name(name: &str) -> bool
+{
+    name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+
+/// A recursive descent parser of the expressions that `evaluate()` takes.
+struct Parser<'a>
+{
+    chars: Vec<char>,
+    position: usize,
+    depth: usize,       // The number of parentheses and unary `-` around the current position
+    values: &'a [(String, i64)],
+}
+
+impl Parser<'_>
+{
+    /// The deepest nesting of parentheses and unary `-` that is parsed,
+    /// so that a deeply nested expression cannot overflow the stack.
+    const MAX_DEPTH: usize = 64;
+
+    // fn skip_spaces(&mut self)
+    /// Moves past the white spaces at the current position.
+    fn skip_spaces(&mut self)
+    {
+        while self.chars.get(self.position).is_some_and(|c| c.is_whitespace())
+            { self.position += 1; }
+    }
+
+    // fn next_operator(&mut self, operators: &[char]) -> Option<char>
+    /// Takes the next character if it is one of `operators`.
+    fn next_operator(&mut self, operators: &[char]) -> Option<char>
+    {
+        self.skip_spaces();
+        let c = *self.chars.get(self.position)?;
+        if !operators.contains(&c)
+            { return None; }
+        self.position += 1;
+        Some(c)
+    }
+
+    // fn parse_sum(&mut self) -> Option<i64>
+    /// Parses terms joined by `+` and `-`.
+    fn parse_sum(&mut self) -> Option<i64>
+    {
+        let mut value = self.parse_product()?;
+        while let Some(operator) = self.next_operator(&['+', '-'])
+        {
+            let operand = self.parse_product()?;
+            value = if operator == '+' { value.checked_add(operand)? } else { value.checked_sub(operand)? };
+        }
+        Some(value)
+    }
+
+    // fn parse_product(&mut self) -> Option<i64>
+    /// Parses factors joined by `*`, `/` and `%`.
+    fn parse_product(&mut self) -> Option<i64>
+    {
+        let mut value = self.parse_factor()?;
+        while let Some(operator) = self.next_operator(&['*', '/', '%'])
+        {
+            let operand = self.parse_factor()?;
+            value = match operator
+            {
+                '*' => value.checked_mul(operand)?,
+                '/' => value.checked_div(operand)?,
+                _ => value.checked_rem(operand)?,
+            };
+        }
+        Some(value)
+    }
+
+    // fn parse_factor(&mut self) -> Option<i64>
+    /// Parses a number, a name, an expression in parentheses, or a negated factor.
+    fn parse_factor(&mut self) -> Option<i64>
+    {
+        if self.next_operator(&['-']).is_some()
+            { return self.parse_nested(Self::parse_factor)?.checked_neg(); }
+        if self.next_operator(&['(']).is_some()
+        {
+            let value = self.parse_nested(Self::parse_sum)?;
+            self.next_operator(&[')'])?;
+            return Some(value);
+        }
+
+        let start = self.position;
+        while self.chars.get(self.position).is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+            { self.position += 1; }
+        let token: String = self.chars[start..self.position].iter().collect();
+        if token.starts_with(|c: char| c.is_ascii_digit())
+            { token.parse().ok() }
+        else
+            { self.values.iter().find(|(name, _)| *name == token).map(|(_, value)| *value) }
+    }
+
+    // fn parse_nested(&mut self, parse: fn(&mut Self) -> Option<i64>) -> Option<i64>
+    /// Parses with `parse` one level deeper, or returns `None` past `MAX_DEPTH` levels.
+    fn parse_nested(&mut self, parse: fn(&mut Self) -> Option<i64>) -> Option<i64>
+    {
+        if self.depth >= Self::MAX_DEPTH
+            { return None; }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+}